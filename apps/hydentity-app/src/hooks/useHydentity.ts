@@ -14,6 +14,7 @@
 import { useState, useEffect, useCallback, useMemo } from 'react';
 import { useConnection, useWallet } from '@solana/wallet-adapter-react';
 import {
+  AccountInfo,
   Connection,
   PublicKey,
   Transaction,
@@ -35,6 +36,8 @@ const SNS_NAME_PROGRAM_ID = new PublicKey('namesLPneVptA9Z5rqUDD9tMTWEJwofgaYwp8
 const VAULT_SEED = Buffer.from('vault');
 const VAULT_AUTH_SEED = Buffer.from('vault_auth');
 const POLICY_SEED = Buffer.from('policy');
+const POLICY_HISTORY_SEED = Buffer.from('policy_history');
+const OWNER_INDEX_SEED = Buffer.from('owner_index');
 
 /**
  * Compute Anchor instruction discriminator
@@ -200,6 +203,40 @@ function getPolicyPda(snsNameAccount: PublicKey, programId: PublicKey): [PublicK
   );
 }
 
+/**
+ * Derive PDA for policy history
+ */
+function getPolicyHistoryPda(snsNameAccount: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [POLICY_HISTORY_SEED, snsNameAccount.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Derive PDA for an owner's vault index
+ */
+function getOwnerIndexPda(owner: PublicKey, programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [OWNER_INDEX_SEED, owner.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Parse the vault addresses listed in an OwnerIndex account
+ * Layout: discriminator (8) | owner (32) | vaults (u32 length + 32 bytes each) | bump (1)
+ */
+function parseOwnerIndex(data: Buffer): PublicKey[] {
+  const count = data.readUInt32LE(40);
+  const vaults: PublicKey[] = [];
+  for (let i = 0; i < count; i++) {
+    const offset = 44 + i * 32;
+    vaults.push(new PublicKey(data.subarray(offset, offset + 32)));
+  }
+  return vaults;
+}
+
 /**
  * NOTE: getSnsNameAccount is now handled by the SNS adapter.
  * Use snsAdapter.getDomainKey(domain) instead.
@@ -217,6 +254,7 @@ async function buildInitializeVaultInstruction(
   const [vault] = getVaultPda(snsNameAccount, programId);
   const [vaultAuthority] = getVaultAuthorityPda(snsNameAccount, programId);
   const [policy] = getPolicyPda(snsNameAccount, programId);
+  const [ownerIndex] = getOwnerIndexPda(owner, programId);
 
   // Compute discriminator dynamically to ensure correctness
   const discriminator = await computeDiscriminator('initialize_vault');
@@ -229,6 +267,7 @@ async function buildInitializeVaultInstruction(
     { pubkey: vault, isSigner: false, isWritable: true },           // vault (init)
     { pubkey: vaultAuthority, isSigner: false, isWritable: true },  // vault_authority (init)
     { pubkey: policy, isSigner: false, isWritable: true },          // policy (init)
+    { pubkey: ownerIndex, isSigner: false, isWritable: true },      // owner_index
     { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
   ];
 
@@ -299,23 +338,35 @@ export function useHydentity() {
       const vaultDiscriminator = await computeAccountDiscriminator('NameVault');
       console.log('NameVault discriminator:', Array.from(vaultDiscriminator));
 
-      // Fetch all NameVault accounts owned by this wallet
-      // Filter by:
+      // Prefer the wallet's on-chain vault index. Wallets whose vaults predate
+      // the index fall back to scanning NameVault accounts, filtered by:
       // 1. Account data size
       // 2. Owner field (at offset 8, after discriminator)
-      const accounts = await connection.getProgramAccounts(programId, {
-        filters: [
-          // Filter by data size (NameVault::LEN)
-          { dataSize: NAME_VAULT_SIZE },
-          // Filter by owner field (offset 8 = after discriminator)
-          {
-            memcmp: {
-              offset: 8,
-              bytes: publicKey.toBase58(),
+      let accounts: { pubkey: PublicKey; account: AccountInfo<Buffer> }[];
+      const [ownerIndex] = getOwnerIndexPda(publicKey, programId);
+      const ownerIndexInfo = await connection.getAccountInfo(ownerIndex);
+      if (ownerIndexInfo) {
+        const vaultKeys = parseOwnerIndex(Buffer.from(ownerIndexInfo.data));
+        const vaultInfos = await connection.getMultipleAccountsInfo(vaultKeys);
+        accounts = vaultKeys.flatMap((pubkey, i) => {
+          const account = vaultInfos[i];
+          return account ? [{ pubkey, account }] : [];
+        });
+      } else {
+        accounts = [...await connection.getProgramAccounts(programId, {
+          filters: [
+            // Filter by data size (NameVault::LEN)
+            { dataSize: NAME_VAULT_SIZE },
+            // Filter by owner field (offset 8 = after discriminator)
+            {
+              memcmp: {
+                offset: 8,
+                bytes: publicKey.toBase58(),
+              },
             },
-          },
-        ],
-      });
+          ],
+        })];
+      }
 
       console.log(`Found ${accounts.length} vault account(s)`);
 
@@ -1072,10 +1123,13 @@ export function useHydentity() {
         { pubkey: SNS_NAME_PROGRAM_ID, isSigner: false, isWritable: false }, // sns_name_program
       ];
 
+      // allow_program_owned = false: only wallet (system-owned) destinations
+      const instructionData = Buffer.concat([discriminator, Buffer.from([0])]);
+
       const instruction = new TransactionInstruction({
         keys,
         programId,
-        data: discriminator,
+        data: instructionData,
       });
 
       const transaction = new Transaction().add(instruction);
@@ -1383,6 +1437,12 @@ export function useHydentity() {
       const [vaultAuthority] = getVaultAuthorityPda(snsNameAccount, programId);
       const [policy] = getPolicyPda(snsNameAccount, programId);
 
+      // The index to update belongs to the vault's owner, read from the vault (offset 8)
+      const vaultInfo = await connection.getAccountInfo(vault);
+      if (!vaultInfo) throw new Error('Vault not found');
+      const vaultOwner = new PublicKey(vaultInfo.data.subarray(8, 40));
+      const [ownerIndex] = getOwnerIndexPda(vaultOwner, programId);
+
       const discriminator = await computeDiscriminator('close_vault');
 
       const keys = [
@@ -1391,6 +1451,7 @@ export function useHydentity() {
         { pubkey: vault, isSigner: false, isWritable: true },              // vault
         { pubkey: vaultAuthority, isSigner: false, isWritable: true },     // vault_authority
         { pubkey: policy, isSigner: false, isWritable: true },             // policy
+        { pubkey: ownerIndex, isSigner: false, isWritable: true },         // owner_index
       ];
 
       const instruction = new TransactionInstruction({
@@ -1457,6 +1518,14 @@ export function useHydentity() {
 
       const [vault] = getVaultPda(snsNameAccount, programId);
       const [policy] = getPolicyPda(snsNameAccount, programId);
+      const [policyHistory] = getPolicyHistoryPda(snsNameAccount, programId);
+
+      // The vault moves from the previous owner's index (owner at offset 8) to ours
+      const vaultInfo = await connection.getAccountInfo(vault);
+      if (!vaultInfo) throw new Error('Vault not found');
+      const previousOwner = new PublicKey(vaultInfo.data.subarray(8, 40));
+      const [previousOwnerIndex] = getOwnerIndexPda(previousOwner, programId);
+      const [newOwnerIndex] = getOwnerIndexPda(publicKey, programId);
 
       const discriminator = await computeDiscriminator('claim_vault');
 
@@ -1465,6 +1534,10 @@ export function useHydentity() {
         { pubkey: snsNameAccount, isSigner: false, isWritable: false },    // sns_name_account
         { pubkey: vault, isSigner: false, isWritable: true },              // vault
         { pubkey: policy, isSigner: false, isWritable: true },             // policy
+        { pubkey: policyHistory, isSigner: false, isWritable: true },      // policy_history
+        { pubkey: previousOwnerIndex, isSigner: false, isWritable: true }, // previous_owner_index
+        { pubkey: newOwnerIndex, isSigner: false, isWritable: true },      // new_owner_index
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }, // system_program
       ];
 
       const instruction = new TransactionInstruction({
@@ -0,0 +1,42 @@
+//! Splices the shared policy limits into the circuit module
+//!
+//! Arcis rejects `use` and `include!` inside `#[encrypted]`, so the constants
+//! from the on-chain program's `limits.rs` are inlined at the `@limits` marker.
+
+use std::{env, fs, path::Path};
+
+const LIMITS: &str = "../programs/hydentity/src/limits.rs";
+const CIRCUITS: &str = "src/circuits.rs";
+const MARKER: &str = "// @limits:";
+
+fn main() {
+    println!("cargo:rerun-if-changed={LIMITS}");
+    println!("cargo:rerun-if-changed={CIRCUITS}");
+
+    let limits = fs::read_to_string(LIMITS).expect("read limits.rs");
+    let circuits = fs::read_to_string(CIRCUITS).expect("read circuits.rs");
+
+    // Inner doc comments are only valid at the top of a file
+    let limits: String = limits
+        .lines()
+        .filter(|line| !line.starts_with("//!"))
+        .map(|line| format!("    {line}\n"))
+        .collect();
+
+    let mut spliced = false;
+    let out: String = circuits
+        .lines()
+        .map(|line| {
+            if line.trim_start().starts_with(MARKER) {
+                spliced = true;
+                limits.clone()
+            } else {
+                format!("{line}\n")
+            }
+        })
+        .collect();
+    assert!(spliced, "{CIRCUITS} is missing the `{MARKER}` marker");
+
+    let dest = Path::new(&env::var("OUT_DIR").unwrap()).join("circuits.rs");
+    fs::write(dest, out).expect("write circuits.rs");
+}
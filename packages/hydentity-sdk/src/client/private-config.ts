@@ -28,7 +28,7 @@ import { VAULT_SEED } from '../constants';
 /**
  * Privacy preset levels
  */
-export type PrivacyPreset = 'low' | 'medium' | 'high';
+export type PrivacyPreset = 'low' | 'medium' | 'high' | 'paranoid';
 
 /**
  * Preset configurations
@@ -61,6 +61,13 @@ export const PRIVACY_PRESETS: Record<PrivacyPreset, {
     maxDelaySeconds: 28800,   // 8 hours
     description: 'Maximum privacy with longer delays',
   },
+  paranoid: {
+    minSplits: 5,
+    maxSplits: 10,
+    minDelaySeconds: 21600,   // 6 hours
+    maxDelaySeconds: 86400,   // 24 hours
+    description: 'Highest split count and delays, random destinations',
+  },
 };
 
 /**
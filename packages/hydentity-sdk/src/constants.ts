@@ -12,6 +12,7 @@ export const VAULT_SEED = Buffer.from('vault');
 export const VAULT_AUTH_SEED = Buffer.from('vault_auth');
 export const POLICY_SEED = Buffer.from('policy');
 export const DELEGATE_SEED = Buffer.from('delegate');
+export const POLICY_HISTORY_SEED = Buffer.from('policy_history');
 
 /**
  * Default policy values (Medium privacy preset)
@@ -55,7 +56,10 @@ export const RELAYER_BASE_URL = 'https://relayer.umbraprivacy.com/';
 export const DEFAULT_RELAYER_TIMEOUT_MS = 30_000;
 
 /**
- * Maximum destinations in privacy policy
+ * Privacy policy limits (must match programs/hydentity/src/limits.rs)
  */
-export const MAX_DESTINATIONS = 10;
+export const MAX_DESTINATIONS = 5;
+export const MAX_SPLITS = 10;
+export const MIN_DELAY_FLOOR_SECONDS = 60;
+export const MAX_DELAY_CEILING_SECONDS = 604800; // 7 days
 
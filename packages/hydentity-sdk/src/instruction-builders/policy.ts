@@ -1,9 +1,15 @@
 import {
   PublicKey,
+  SystemProgram,
   TransactionInstruction,
 } from '@solana/web3.js';
 import { HYDENTITY_PROGRAM_ID } from '../constants';
-import { getNameVaultPda, getPrivacyPolicyPda, getDelegateSessionPda } from '../utils/pda';
+import {
+  getNameVaultPda,
+  getPrivacyPolicyPda,
+  getPolicyHistoryPda,
+  getDelegateSessionPda,
+} from '../utils/pda';
 import {
   UpdatePolicyParams,
   Distribution,
@@ -113,6 +119,33 @@ function serializeUpdatePolicyParams(params: UpdatePolicyParams): Buffer {
     parts.push(Buffer.from([0]));
   }
 
+  // enforceDestinationAllowlist: Option<bool>
+  if (params.enforceDestinationAllowlist !== undefined && params.enforceDestinationAllowlist !== null) {
+    parts.push(Buffer.from([1, params.enforceDestinationAllowlist ? 1 : 0]));
+  } else {
+    parts.push(Buffer.from([0]));
+  }
+
+  // allowlistDelaySeconds: Option<u32>
+  if (params.allowlistDelaySeconds !== undefined && params.allowlistDelaySeconds !== null) {
+    const buf = Buffer.alloc(5);
+    buf[0] = 1;
+    buf.writeUInt32LE(params.allowlistDelaySeconds, 1);
+    parts.push(buf);
+  } else {
+    parts.push(Buffer.from([0]));
+  }
+
+  // policyUpdateDelaySeconds: Option<u32>
+  if (params.policyUpdateDelaySeconds !== undefined && params.policyUpdateDelaySeconds !== null) {
+    const buf = Buffer.alloc(5);
+    buf[0] = 1;
+    buf.writeUInt32LE(params.policyUpdateDelaySeconds, 1);
+    parts.push(buf);
+  } else {
+    parts.push(Buffer.from([0]));
+  }
+
   return Buffer.concat(parts);
 }
 
@@ -133,6 +166,7 @@ export function buildUpdatePolicyInstruction(
 ): TransactionInstruction {
   const [vault] = getNameVaultPda(snsNameAccount);
   const [policy] = getPrivacyPolicyPda(snsNameAccount);
+  const [policyHistory] = getPolicyHistoryPda(snsNameAccount);
 
   // Instruction discriminator for "update_policy"
   const discriminator = Buffer.from([
@@ -145,8 +179,10 @@ export function buildUpdatePolicyInstruction(
   const keys = [
     { pubkey: authority, isSigner: true, isWritable: true },
     { pubkey: snsNameAccount, isSigner: false, isWritable: false },
-    { pubkey: vault, isSigner: false, isWritable: false },
+    { pubkey: vault, isSigner: false, isWritable: true },
     { pubkey: policy, isSigner: false, isWritable: true },
+    { pubkey: policyHistory, isSigner: false, isWritable: true },
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
   ];
 
   // Add optional delegate session
@@ -175,6 +211,9 @@ export function createUpdatePolicyParams(
     privacyMode: PrivacyMode;
     destinationMode: DestinationMode;
     destinations: PublicKey[];
+    enforceDestinationAllowlist: boolean;
+    allowlistDelaySeconds: number;
+    policyUpdateDelaySeconds: number;
   }>
 ): UpdatePolicyParams {
   return {
@@ -187,6 +226,9 @@ export function createUpdatePolicyParams(
     privacyMode: updates.privacyMode ?? null,
     destinationMode: updates.destinationMode ?? null,
     destinations: updates.destinations ?? null,
+    enforceDestinationAllowlist: updates.enforceDestinationAllowlist ?? null,
+    allowlistDelaySeconds: updates.allowlistDelaySeconds ?? null,
+    policyUpdateDelaySeconds: updates.policyUpdateDelaySeconds ?? null,
   };
 }
 
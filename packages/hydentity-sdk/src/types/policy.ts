@@ -107,6 +107,9 @@ export interface UpdatePolicyParams {
   privacyMode?: PrivacyMode | null;
   destinationMode?: DestinationMode | null;
   destinations?: PublicKey[] | null;
+  enforceDestinationAllowlist?: boolean | null;
+  allowlistDelaySeconds?: number | null;
+  policyUpdateDelaySeconds?: number | null;
 }
 
 /**
@@ -5,6 +5,7 @@ import {
   VAULT_AUTH_SEED,
   POLICY_SEED,
   DELEGATE_SEED,
+  POLICY_HISTORY_SEED,
 } from '../constants';
 import type { ProgramDerivedAddress } from '../types/solana';
 
@@ -53,6 +54,21 @@ export function getPrivacyPolicyPda(
   return [pda as ProgramDerivedAddress, bump];
 }
 
+/**
+ * Derive the PolicyHistory PDA for an SNS name account
+ * @param snsNameAccount - The SNS name account public key
+ * @returns [PDA, bump]
+ */
+export function getPolicyHistoryPda(
+  snsNameAccount: PublicKey
+): [ProgramDerivedAddress, number] {
+  const [pda, bump] = PublicKey.findProgramAddressSync(
+    [POLICY_HISTORY_SEED, snsNameAccount.toBuffer()],
+    HYDENTITY_PROGRAM_ID
+  );
+  return [pda as ProgramDerivedAddress, bump];
+}
+
 /**
  * Derive the DelegateSession PDA for an SNS name account and delegate
  * @param snsNameAccount - The SNS name account public key
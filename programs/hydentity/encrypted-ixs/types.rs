@@ -5,17 +5,10 @@
 
 use arcis_imports::*;
 
-/// Maximum number of destination wallets per vault
-pub const MAX_DESTINATIONS: usize = 5;
-
-/// Maximum number of splits per withdrawal
-pub const MAX_SPLITS: usize = 10;
-
-/// Minimum delay between splits (60 seconds = 1 minute)
-pub const MIN_DELAY_FLOOR_SECONDS: u32 = 60;
-
-/// Maximum delay between splits (7 days)
-pub const MAX_DELAY_CEILING_SECONDS: u32 = 604800;
+/// Policy limits, shared with the on-chain program's `PrivacyPolicy::validate`
+#[path = "../src/limits.rs"]
+mod limits;
+pub use limits::*;
 
 /// Private vault configuration - stored encrypted on-chain
 /// 
@@ -5,12 +5,85 @@ pub const VAULT_SEED: &[u8] = b"vault";
 pub const VAULT_AUTH_SEED: &[u8] = b"vault_auth";
 pub const POLICY_SEED: &[u8] = b"policy";
 pub const DELEGATE_SEED: &[u8] = b"delegate";
+pub const OWNER_SET_SEED: &[u8] = b"owner_set";
+pub const RECOVERY_CONFIG_SEED: &[u8] = b"recovery";
+pub const RECOVERY_REQUEST_SEED: &[u8] = b"recovery_request";
+pub const OWNER_TRANSFER_SEED: &[u8] = b"owner_transfer";
+pub const WITHDRAWAL_CONFIG_SEED: &[u8] = b"withdraw_config";
+pub const DIRECT_WITHDRAWAL_SEED: &[u8] = b"direct_withdrawal";
+pub const OUTFLOW_LIMIT_SEED: &[u8] = b"outflow_limit";
+pub const PENDING_POLICY_SEED: &[u8] = b"pending_policy";
+pub const POLICY_HISTORY_SEED: &[u8] = b"policy_history";
+pub const SUBDOMAIN_LINK_SEED: &[u8] = b"subdomain";
+pub const DOMAIN_LISTING_SEED: &[u8] = b"listing";
+pub const RECLAIM_CONFIG_SEED: &[u8] = b"reclaim_config";
+pub const PENDING_RECLAIM_SEED: &[u8] = b"pending_reclaim";
+pub const VAULT_PROFILE_SEED: &[u8] = b"vault_profile";
+pub const PROFILE_REGISTRY_SEED: &[u8] = b"profile_registry";
+pub const OWNER_INDEX_SEED: &[u8] = b"owner_index";
 
 /// SNS Name Service Program ID (same on mainnet and devnet)
 pub const SNS_NAME_PROGRAM_ID: Pubkey = pubkey!("namesLPneVptA9Z5rqUDD9tMTWEJwofgaYwp8cawRkX");
 
-/// Maximum number of destination addresses in privacy policy
-pub const MAX_DESTINATIONS: usize = 10;
+/// Parent name of every .sol domain (names with any other parent are subdomains)
+pub const SOL_TLD_AUTHORITY: Pubkey = pubkey!("58PwtjSDuFHuUkYjH9BYnnQKHfwo9reZhC2zMJv9JPkx");
+
+/// Bonfida name tokenizer program (wraps SNS domains as NFTs)
+/// The program only derives the tokenizer's PDAs and never calls it
+pub const NAME_TOKENIZER_PROGRAM_ID: Pubkey = pubkey!("nftD3vbNkNqfj2Sd3HZwbpw4BxxKWr4AjGb9X38JeZk");
+
+/// Seed of the tokenizer's NFT mint PDA for a domain
+pub const TOKENIZED_NAME_MINT_SEED: &[u8] = b"tokenized_name";
+
+/// SNS records program (V2 records such as a domain's SOL address)
+pub const SNS_RECORDS_PROGRAM_ID: Pubkey = pubkey!("HP3D4D1ZCmohQGFVms2SS4LCANgJyksBf5s1F77FuFjZ");
+
+/// Prefix hashed with a name to derive its SNS name account
+pub const SNS_HASH_PREFIX: &[u8] = b"SPL Name Service";
+
+/// Policy limits shared with the MPC circuits
+pub use crate::limits::*;
+
+/// Maximum number of co-owner keys in a vault owner set
+pub const MAX_OWNER_SET_SIGNERS: usize = 10;
+
+/// Maximum number of recovery guardians per vault
+pub const MAX_GUARDIANS: usize = 10;
+
+/// Minimum veto window before a guardian-approved recovery can execute
+pub const MIN_RECOVERY_TIMELOCK_SECONDS: i64 = 86_400; // 24 hours
+
+/// Maximum delay an owner can put on a proposed owner key rotation
+pub const MAX_OWNER_TRANSFER_DELAY_SECONDS: i64 = 2_592_000; // 30 days
+
+/// Minimum owner inactivity before a beneficiary can claim the vault
+pub const MIN_INACTIVITY_WINDOW_SECONDS: i64 = 2_592_000; // 30 days
+
+/// Maximum delay on queued direct withdrawals
+pub const MAX_DIRECT_WITHDRAWAL_DELAY_SECONDS: i64 = 2_592_000; // 30 days
+
+/// Maximum delay on two-step domain reclaims
+pub const MAX_RECLAIM_DELAY_SECONDS: i64 = 2_592_000; // 30 days
+
+/// Maximum number of open profiles per domain
+pub const MAX_VAULT_PROFILES: usize = 8;
+
+/// Maximum length of a profile label (bytes)
+pub const MAX_PROFILE_LABEL_LEN: usize = 32;
+
+/// Rolling outflow window, tracked in hourly buckets (24h)
+pub const OUTFLOW_WINDOW_BUCKETS: usize = 24;
+pub const OUTFLOW_BUCKET_SECONDS: i64 = 3_600;
+pub const OUTFLOW_WINDOW_SECONDS: i64 = OUTFLOW_BUCKET_SECONDS * OUTFLOW_WINDOW_BUCKETS as i64;
+
+/// Minimum queue delay for a queued direct withdrawal to exceed the outflow limit
+pub const MIN_OUTFLOW_OVERRIDE_DELAY_SECONDS: i64 = 86_400; // 24 hours
+
+/// Maximum activation delay for timelocked policy updates
+pub const MAX_POLICY_UPDATE_DELAY_SECONDS: u32 = 2_592_000; // 30 days
+
+/// Number of policy changes kept in the PolicyHistory ring buffer
+pub const POLICY_HISTORY_ENTRIES: usize = 16;
 
 /// Default policy values (Medium preset)
 pub const DEFAULT_MIN_SPLITS: u8 = 2;
@@ -21,7 +94,11 @@ pub const DEFAULT_MAX_DELAY_SECONDS: u32 = 1800; // 30 minutes
 /// Delegate permission flags
 pub const PERMISSION_UPDATE_POLICY: u8 = 1 << 0;
 pub const PERMISSION_DEPOSIT_UMBRA: u8 = 1 << 1;
-pub const PERMISSION_ALL: u8 = PERMISSION_UPDATE_POLICY | PERMISSION_DEPOSIT_UMBRA;
+pub const PERMISSION_SUB_DELEGATE: u8 = 1 << 2;
+pub const PERMISSION_ALL: u8 = PERMISSION_UPDATE_POLICY | PERMISSION_DEPOSIT_UMBRA | PERMISSION_SUB_DELEGATE;
+
+/// Maximum length of a delegation chain (owner-granted root session included)
+pub const MAX_DELEGATION_DEPTH: usize = 3;
 
 /// Minimum dust threshold in lamports (to prevent spam splits)
 pub const DUST_THRESHOLD_LAMPORTS: u64 = 10_000; // 0.00001 SOL
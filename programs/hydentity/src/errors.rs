@@ -26,13 +26,13 @@ pub enum HydentityError {
     #[msg("Invalid policy configuration")]
     InvalidPolicyConfig,
 
-    #[msg("Too many destinations specified (max 10)")]
+    #[msg("Too many destinations specified (max 5)")]
     TooManyDestinations,
 
-    #[msg("Invalid split range: min_splits must be <= max_splits")]
+    #[msg("Invalid split range: need 1 <= min_splits <= max_splits <= 10")]
     InvalidSplitRange,
 
-    #[msg("Invalid delay range: min_delay must be <= max_delay")]
+    #[msg("Invalid delay range: need 60s <= min_delay <= max_delay <= 7 days")]
     InvalidDelayRange,
 
     #[msg("Insufficient vault balance for operation")]
@@ -120,5 +120,221 @@ pub enum HydentityError {
 
     #[msg("New owner is the same as current vault owner")]
     VaultOwnerUnchanged,
-}
 
+    // ===== Owner Set Errors =====
+
+    #[msg("Invalid owner set: check signer list and threshold")]
+    InvalidOwnerSet,
+
+    #[msg("Not enough owner set signatures")]
+    OwnerSetThresholdNotMet,
+
+    #[msg("The vault's owner set account must be provided")]
+    OwnerSetRequired,
+
+    // ===== Recovery Errors =====
+
+    #[msg("Invalid recovery config: check guardians, threshold and timelock")]
+    InvalidRecoveryConfig,
+
+    #[msg("Signer is not a recovery guardian for this vault")]
+    NotAGuardian,
+
+    #[msg("Guardian has already approved this recovery")]
+    RecoveryAlreadyApproved,
+
+    #[msg("Not enough guardian approvals for recovery")]
+    RecoveryThresholdNotMet,
+
+    #[msg("Recovery timelock has not elapsed")]
+    RecoveryTimelockActive,
+
+    #[msg("Vault owner changed since recovery was initiated")]
+    RecoveryStale,
+
+    #[msg("A recovery request is already pending for this vault")]
+    RecoveryPending,
+
+    // ===== Owner Transfer Errors =====
+    #[msg("Invalid owner transfer delay")]
+    InvalidOwnerTransferDelay,
+
+    #[msg("Owner transfer delay has not elapsed")]
+    OwnerTransferTimelockActive,
+
+    #[msg("Vault owner changed since the transfer was proposed")]
+    OwnerTransferStale,
+
+    // ===== Inheritance Errors =====
+    #[msg("Invalid beneficiary or inactivity window")]
+    InvalidInheritanceConfig,
+
+    #[msg("Signer is not the vault beneficiary")]
+    NotBeneficiary,
+
+    #[msg("Owner has not been inactive long enough")]
+    OwnerStillActive,
+
+    // ===== Direct Withdrawal Timelock Errors =====
+    #[msg("Invalid direct withdrawal timelock settings")]
+    InvalidWithdrawalTimelock,
+
+    #[msg("Direct withdrawal timelock config account is required")]
+    WithdrawalConfigRequired,
+
+    #[msg("Direct withdrawals above the threshold must be queued")]
+    DirectWithdrawalRequiresQueue,
+
+    #[msg("Queued withdrawal is still locked")]
+    WithdrawalLocked,
+
+    #[msg("Vault owner changed since the withdrawal was queued")]
+    WithdrawalStale,
+
+    // ===== Outflow Limit Errors =====
+    #[msg("Invalid outflow limit account")]
+    InvalidOutflowLimit,
+
+    #[msg("Rolling 24h outflow limit exceeded; a guardian co-sign or timelocked withdrawal is required")]
+    OutflowLimitExceeded,
+
+    // ===== Destination Allowlist Errors =====
+    #[msg("Destination is not on the policy allowlist")]
+    DestinationNotAllowlisted,
+
+    #[msg("Destination list changed recently; allowlist delay has not elapsed")]
+    AllowlistChangePending,
+
+    #[msg("Delegates cannot change the destination allowlist")]
+    AllowlistRequiresOwner,
+
+    // ===== Policy Timelock Errors =====
+    #[msg("Invalid policy update delay")]
+    InvalidPolicyUpdateDelay,
+
+    #[msg("Policy updates are timelocked and must be queued")]
+    PolicyUpdateRequiresQueue,
+
+    #[msg("Queued policy update is not active yet")]
+    PolicyUpdateLocked,
+
+    #[msg("Policy or vault owner changed since the update was queued")]
+    PolicyUpdateStale,
+
+    // ===== Policy Validation Errors =====
+    #[msg("Destination list contains a duplicate address")]
+    DuplicateDestination,
+
+    #[msg("Destination list contains the zero address")]
+    ZeroAddressDestination,
+
+    // ===== Policy Preset Errors =====
+    #[msg("Unknown policy preset")]
+    InvalidPolicyPreset,
+
+    // ===== Domain Control Errors =====
+    #[msg("Domain has a new owner: the new owner must call claim_vault first")]
+    DomainClaimPending,
+
+    // ===== Subdomain Errors =====
+    #[msg("Name is not a subdomain")]
+    NotASubdomain,
+
+    #[msg("Parent domain account does not match the subdomain's parent")]
+    InvalidParentDomain,
+
+    #[msg("Parent domain has not enabled control over its subdomains")]
+    ParentControlDisabled,
+
+    #[msg("Subdomain vaults must be created with initialize_subdomain_vault")]
+    SubdomainRequiresParent,
+
+    // ===== Tokenized Domain Errors =====
+    #[msg("Domain is tokenized: pass the token account holding its NFT")]
+    TokenizedDomainProofRequired,
+
+    #[msg("Token account does not hold this domain's NFT")]
+    TokenizedDomainNotHeld,
+
+    // ===== SNS Record Errors =====
+    #[msg("SNS records program failed to write the SOL record")]
+    SolRecordWriteFailed,
+
+    #[msg("SOL record does not point at the vault authority")]
+    SolRecordMismatch,
+
+    // ===== Domain Sale Errors =====
+    #[msg("Listing price must be greater than zero")]
+    InvalidListingPrice,
+
+    #[msg("Proceeds destination cannot receive the listing's payment asset")]
+    InvalidProceedsDestination,
+
+    #[msg("Listing is reserved for another buyer")]
+    ListingBuyerMismatch,
+
+    #[msg("Listing price differs from the price the buyer agreed to")]
+    ListingPriceMismatch,
+
+    #[msg("Vault owner changed since the domain was listed")]
+    ListingStale,
+
+    #[msg("Delegate session was granted before the vault was sold")]
+    DelegateStale,
+
+    // ===== Reclaim Timelock Errors =====
+    #[msg("Reclaim delay exceeds the maximum (30 days)")]
+    InvalidReclaimDelay,
+
+    #[msg("Reclaim timelock config account is required")]
+    ReclaimConfigRequired,
+
+    #[msg("Reclaim timelock is active: use initiate_reclaim and complete_reclaim")]
+    ReclaimRequiresQueue,
+
+    #[msg("Pending reclaim is still locked")]
+    ReclaimLocked,
+
+    #[msg("Vault owner changed since the reclaim was initiated")]
+    ReclaimStale,
+
+    // ===== Vault Profile Errors =====
+    #[msg("Profile label must be 1-32 characters of a-z, 0-9, '-' or '_'")]
+    InvalidProfileLabel,
+
+    #[msg("A profile with this label already exists")]
+    ProfileLabelTaken,
+
+    #[msg("Maximum number of profiles reached")]
+    TooManyProfiles,
+
+    #[msg("No profile with this label or id")]
+    ProfileNotFound,
+
+    #[msg("Profile id must be the registry's next profile id")]
+    ProfileIdMismatch,
+
+    #[msg("Close the domain's profiles first")]
+    ProfilesStillOpen,
+
+    // ===== Owner Index Errors =====
+    #[msg("Owner index account does not match the vault owner")]
+    InvalidOwnerIndex,
+
+    // ===== Sub-Delegation Errors =====
+    #[msg("Parent delegate session has been revoked")]
+    ParentDelegateRevoked,
+
+    #[msg("Delegate chain does not match the parent sessions provided")]
+    InvalidDelegateChain,
+
+    #[msg("Delegation chain is too deep")]
+    DelegationDepthExceeded,
+
+    #[msg("Child session cannot outlive or outrank its parent")]
+    SubDelegationExceedsParent,
+
+    // ===== Policy Limit Errors =====
+    #[msg("Amount is too small to split into min_splits above the dust threshold")]
+    SplitAmountBelowDust,
+}
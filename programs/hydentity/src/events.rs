@@ -1,15 +1,239 @@
 use anchor_lang::prelude::*;
+use crate::state::{PolicyActor, PolicyPreset};
+
+/// Layout version carried by every event as its first field
+///
+/// Bump when any event gains, loses or reorders a field so indexers can
+/// select the matching decoder.
+pub const EVENT_SCHEMA_VERSION: u8 = 1;
 
 #[event]
 pub struct VaultInitialized {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub sns_name: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PolicyUpdated {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub actor_type: PolicyActor,
+    pub policy_nonce: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DelegateAdded {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub delegate: Pubkey,
+    pub granted_by: Pubkey,
+    /// Parent session for sub-delegations (default for owner-granted sessions)
+    pub parent_session: Pubkey,
+    pub permissions: u8,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DelegateRevoked {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub delegate: Pubkey,
+    pub revoked_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UmbraDeposited {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DirectWithdrawal {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DomainTransferred {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub sns_name: Pubkey,
+    pub vault_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DomainReclaimed {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub sns_name: Pubkey,
+    pub destination: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DomainStateSynced {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub sns_owner: Pubkey,
+    pub domain_transferred: bool,
+    /// True if the domain is owned by neither the vault owner nor the vault authority
+    pub owner_lost_domain: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SubdomainPolicyUpdated {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub parent_controls_subdomains: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SubdomainVaultInitialized {
+    pub version: u8,
     pub vault: Pubkey,
     pub owner: Pubkey,
+    pub parent_name: Pubkey,
+    pub subdomain_name: Pubkey,
+    pub managed_by_parent: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SolRecordSet {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub sns_name: Pubkey,
+    pub record: Pubkey,
+    /// The vault authority the record now resolves to
+    pub target: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DomainListed {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub sns_name: Pubkey,
+    pub seller: Pubkey,
+    pub price: u64,
+    /// Payment mint (default pubkey = SOL)
+    pub mint: Pubkey,
+    /// Reserved buyer (default pubkey = anyone)
+    pub buyer: Pubkey,
+    pub release_domain: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DomainSold {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub sns_name: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+    pub mint: Pubkey,
+    /// True if the SNS domain went to the buyer, false if the buyer took over the vault
+    pub release_domain: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DomainDelisted {
+    pub version: u8,
+    pub vault: Pubkey,
     pub sns_name: Pubkey,
     pub timestamp: i64,
 }
 
+#[event]
+pub struct ReclaimTimelockUpdated {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub delay_seconds: i64,
+    pub effective_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReclaimInitiated {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub destination: Pubkey,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReclaimCancelled {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub cancelled_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultRebound {
+    pub version: u8,
+    pub old_vault: Pubkey,
+    pub new_vault: Pubkey,
+    pub old_sns_name: Pubkey,
+    pub new_sns_name: Pubkey,
+    pub owner: Pubkey,
+    pub sol_moved: u64,
+    pub token_accounts_moved: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultProfileCreated {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub profile: Pubkey,
+    pub profile_id: u8,
+    pub vault_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultProfileClosed {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub profile: Pubkey,
+    pub profile_id: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProfileDeposit {
+    pub version: u8,
+    pub profile: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct ConfigStored {
+    pub version: u8,
     pub vault: Pubkey,
     pub config_hash: [u8; 32],
     pub timestamp: i64,
@@ -17,6 +241,7 @@ pub struct ConfigStored {
 
 #[event]
 pub struct WithdrawalRequested {
+    pub version: u8,
     pub vault: Pubkey,
     pub amount: u64,
     pub computation_offset: u64,
@@ -25,6 +250,7 @@ pub struct WithdrawalRequested {
 
 #[event]
 pub struct WithdrawalPlanGenerated {
+    pub version: u8,
     pub vault: Pubkey,
     pub plan_id: [u8; 16],
     pub total_splits: u8,
@@ -33,6 +259,7 @@ pub struct WithdrawalPlanGenerated {
 
 #[event]
 pub struct WithdrawalCompleted {
+    pub version: u8,
     pub vault: Pubkey,
     pub total_amount: u64,
     pub split_count: u8,
@@ -41,6 +268,7 @@ pub struct WithdrawalCompleted {
 
 #[event]
 pub struct WithdrawalCancelled {
+    pub version: u8,
     pub vault: Pubkey,
     pub refunded_amount: u64,
     pub completed_splits: u8,
@@ -49,15 +277,249 @@ pub struct WithdrawalCancelled {
 
 #[event]
 pub struct VaultClosed {
+    pub version: u8,
     pub vault: Pubkey,
     pub owner: Pubkey,
     pub timestamp: i64,
 }
 
+#[event]
+pub struct OwnerSetUpdated {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub signer_count: u8,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OwnerSetRemoved {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecoveryConfigUpdated {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub guardian_count: u8,
+    pub threshold: u8,
+    pub timelock_seconds: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecoveryInitiated {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub guardian: Pubkey,
+    pub proposed_owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecoveryApproved {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub guardian: Pubkey,
+    pub approvals: u8,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecoveryCancelled {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub proposed_owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecoveryExecuted {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OwnerTransferProposed {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub current_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub executable_at: i64,
+    pub reset_destinations: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OwnerTransferCancelled {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub new_owner: Pubkey,
+    pub cancelled_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OwnerTransferAccepted {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub destinations_reset: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BeneficiaryUpdated {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub beneficiary: Pubkey,
+    pub inactivity_window_seconds: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InheritanceClaimed {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub previous_owner: Pubkey,
+    pub beneficiary: Pubkey,
+    pub domain_transferred: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalTimelockUpdated {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub threshold: u64,
+    pub delay_seconds: i64,
+    pub security_key: Pubkey,
+    pub effective_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DirectWithdrawalQueued {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub withdrawal_id: u64,
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub unlock_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DirectWithdrawalExecuted {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub withdrawal_id: u64,
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DirectWithdrawalCancelled {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub withdrawal_id: u64,
+    pub cancelled_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OutflowLimitUpdated {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub daily_limit: u64,
+    pub effective_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OutflowLimitOverridden {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub window_total: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PolicyUpdateQueued {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub queued_by: Pubkey,
+    pub base_nonce: u64,
+    pub effective_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PolicyUpdateApplied {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub policy_nonce: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PolicyUpdateCancelled {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub cancelled_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PolicyPresetApplied {
+    pub version: u8,
+    pub vault: Pubkey,
+    pub preset: PolicyPreset,
+    pub policy_nonce: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct VaultClaimed {
+    pub version: u8,
     pub vault: Pubkey,
     pub previous_owner: Pubkey,
     pub new_owner: Pubkey,
     pub timestamp: i64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{Discriminator, Event};
+
+    #[test]
+    fn test_version_follows_discriminator() {
+        let vault = Pubkey::new_unique();
+        let event = VaultClosed {
+            version: EVENT_SCHEMA_VERSION,
+            vault,
+            owner: Pubkey::new_unique(),
+            timestamp: 7,
+        };
+
+        // Indexers read the version before choosing a decoder
+        let data = event.data();
+        assert_eq!(&data[..VaultClosed::DISCRIMINATOR.len()], VaultClosed::DISCRIMINATOR);
+        assert_eq!(data[VaultClosed::DISCRIMINATOR.len()], EVENT_SCHEMA_VERSION);
+        assert_eq!(&data[VaultClosed::DISCRIMINATOR.len() + 1..][..32], vault.as_ref());
+    }
+}
@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::constants::{VAULT_SEED, POLICY_SEED, POLICY_HISTORY_SEED, OWNER_TRANSFER_SEED};
+use crate::errors::HydentityError;
+use crate::events::{OwnerTransferAccepted, EVENT_SCHEMA_VERSION};
+use crate::state::{
+    NameVault, PendingOwnerTransfer, PrivacyPolicy, PolicyHistory, PolicyActor, move_owner_index_entry,
+    is_domain_holder_signer,
+};
+
+/// Accept a proposed owner transfer
+///
+/// Second step of the key rotation. The new key signs to take over
+/// `NameVault.owner`. Unlike `claim_vault`, the privacy policy and
+/// encrypted config are preserved; destinations are only reset to the
+/// new owner if the proposal opted in.
+#[derive(Accounts)]
+pub struct AcceptOwnerTransfer<'info> {
+    /// The proposed new owner (must be signer, pays for growing their index and the policy history)
+    #[account(mut)]
+    pub new_owner: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault whose owner is being rotated
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The vault's privacy policy
+    #[account(
+        mut,
+        seeds = [POLICY_SEED, sns_name_account.key().as_ref()],
+        bump = policy.bump,
+        constraint = policy.vault == vault.key() @ HydentityError::InvalidPolicyConfig,
+    )]
+    pub policy: Account<'info, PrivacyPolicy>,
+
+    /// Audit trail of policy changes (created on first use)
+    #[account(
+        init_if_needed,
+        payer = new_owner,
+        space = PolicyHistory::LEN,
+        seeds = [POLICY_HISTORY_SEED, sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub policy_history: Box<Account<'info, PolicyHistory>>,
+
+    /// The pending transfer being accepted
+    #[account(
+        mut,
+        close = proposed_by,
+        seeds = [OWNER_TRANSFER_SEED, sns_name_account.key().as_ref()],
+        bump = pending_transfer.bump,
+        constraint = pending_transfer.vault == vault.key() @ HydentityError::InvalidVault,
+        constraint = pending_transfer.new_owner == new_owner.key() @ HydentityError::Unauthorized
+    )]
+    pub pending_transfer: Account<'info, PendingOwnerTransfer>,
+
+    /// The account that paid for the proposal (receives rent)
+    /// CHECK: Validated against pending_transfer.proposed_by
+    #[account(
+        mut,
+        address = pending_transfer.proposed_by @ HydentityError::InvalidVault
+    )]
+    pub proposed_by: UncheckedAccount<'info>,
+
+    /// The previous owner's vault index (vault removed, if the index exists)
+    /// CHECK: Address and contents validated by remove_from_owner_index
+    #[account(mut)]
+    pub previous_owner_index: UncheckedAccount<'info>,
+
+    /// The new owner's vault index (created or grown as needed)
+    /// CHECK: Address and contents validated by add_to_owner_index
+    #[account(mut)]
+    pub new_owner_index: UncheckedAccount<'info>,
+
+    /// System program (for creating or growing the new owner's index)
+    pub system_program: Program<'info, System>,
+
+    /// Optional: the domain's current holder, co-signing a rotation that
+    /// leaves the domain with the previous key
+    pub domain_holder: Option<Signer<'info>>,
+
+    /// Token account holding the domain's NFT (tokenized domains only)
+    pub nft_token_account: Option<Account<'info, TokenAccount>>,
+}
+
+pub fn handler(ctx: Context<AcceptOwnerTransfer>) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    let previous_owner = ctx.accounts.vault.owner;
+    let pending = &ctx.accounts.pending_transfer;
+    pending.verify_acceptable(&previous_owner, now)?;
+
+    let new_owner = ctx.accounts.new_owner.key();
+    let reset_destinations = pending.reset_destinations;
+    let domain_holder_signed = is_domain_holder_signer(
+        &ctx.accounts.sns_name_account,
+        ctx.accounts.nft_token_account.as_deref(),
+        ctx.accounts.domain_holder.as_ref().map(|holder| holder.as_ref()),
+    )?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.rotate_owner(new_owner, domain_holder_signed);
+    vault.touch_owner_activity(now);
+
+    // Move the vault between the owners' indexes
+    move_owner_index_entry(
+        &ctx.accounts.previous_owner_index,
+        &previous_owner,
+        &ctx.accounts.new_owner_index,
+        &new_owner,
+        &ctx.accounts.vault.key(),
+        &ctx.accounts.new_owner.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    if reset_destinations {
+        let policy = &mut ctx.accounts.policy;
+        let previous = (**policy).clone();
+        policy.destinations = vec![new_owner];
+        policy.update(&previous, now)?;
+
+        let history = &mut ctx.accounts.policy_history;
+        history.initialize_if_needed(policy.vault, ctx.bumps.policy_history);
+        history.record(policy, new_owner, PolicyActor::NewOwner, now)?;
+    }
+
+    emit!(OwnerTransferAccepted {
+        version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.vault.key(),
+        previous_owner,
+        new_owner,
+        destinations_reset: reset_destinations,
+        timestamp: now,
+    });
+
+    log_sensitive!("Owner transfer accepted by: {}", new_owner);
+    log_sensitive!("Previous owner: {}", previous_owner);
+
+    Ok(())
+}
@@ -1,15 +1,16 @@
 use anchor_lang::prelude::*;
-use crate::constants::{VAULT_SEED, DELEGATE_SEED};
+use crate::constants::{VAULT_SEED, DELEGATE_SEED, OWNER_SET_SEED};
 use crate::errors::HydentityError;
-use crate::state::{NameVault, DelegateSession};
+use crate::events::{DelegateAdded, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, DelegateSession, VaultOwnerSet, verify_owner_authority};
 
 /// Add a delegate with time-bounded execution permissions
 /// 
-/// Only the vault owner can add delegates. Delegates can be granted
-/// specific permissions to act on behalf of the owner.
+/// Only the vault owner (or an owner set quorum) can add delegates.
+/// Delegates can be granted specific permissions to act on behalf of the owner.
 #[derive(Accounts)]
 pub struct AddDelegate<'info> {
-    /// The vault owner (must be signer)
+    /// The vault owner or an owner set member (must be signer)
     #[account(mut)]
     pub owner: Signer<'info>,
     
@@ -19,10 +20,10 @@ pub struct AddDelegate<'info> {
     
     /// The vault (for ownership verification)
     #[account(
+        mut,
         seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
         bump = vault.bump,
-        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName,
-        constraint = vault.owner == owner.key() @ HydentityError::Unauthorized
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
     )]
     pub vault: Account<'info, NameVault>,
     
@@ -42,6 +43,15 @@ pub struct AddDelegate<'info> {
     
     /// System program for account creation
     pub system_program: Program<'info, System>,
+    
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
 }
 
 pub fn handler(
@@ -52,6 +62,18 @@ pub fn handler(
     let clock = Clock::get()?;
     let now = clock.unix_timestamp;
     
+    // A sold domain freezes the previous owner until the buyer claims the vault
+    ctx.accounts.vault.verify_domain_control(&ctx.accounts.sns_name_account)?;
+    
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+    
+    ctx.accounts.vault.touch_owner_activity(now);
+    
     // Validate expiration is in the future
     if expires_at <= now {
         return Err(HydentityError::DelegateExpired.into());
@@ -75,9 +97,20 @@ pub fn handler(
         now,
     );
     
-    msg!("Delegate added: {}", delegate);
-    msg!("Permissions: {:#b}", permissions);
-    msg!("Expires at: {}", expires_at);
+    emit!(DelegateAdded {
+        version: EVENT_SCHEMA_VERSION,
+        vault: vault.key(),
+        delegate,
+        granted_by: owner,
+        parent_session: Pubkey::default(),
+        permissions,
+        expires_at,
+        timestamp: now,
+    });
+    
+    log_sensitive!("Delegate added: {}", delegate);
+    log_info!("Permissions: {:#b}", permissions);
+    log_info!("Expires at: {}", expires_at);
     
     Ok(())
 }
@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, DELEGATE_SEED, MAX_DELEGATION_DEPTH};
+use crate::errors::HydentityError;
+use crate::events::{DelegateAdded, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, DelegateSession};
+
+/// Issue a child session from an existing delegate session
+///
+/// A delegate holding `PERMISSION_SUB_DELEGATE` can grant a narrower,
+/// shorter-lived session to another key (e.g. an automation bot) without
+/// the vault owner signing. The child records its parent session and is
+/// invalidated as soon as any session up the chain is revoked or expires.
+///
+/// Remaining accounts: the parent session's own ancestors, nearest first.
+#[derive(Accounts)]
+pub struct AddSubDelegate<'info> {
+    /// The parent delegate (must be signer, pays for the child session)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault the sessions belong to
+    #[account(
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The caller's own delegate session
+    #[account(
+        seeds = [DELEGATE_SEED, sns_name_account.key().as_ref(), authority.key().as_ref()],
+        bump = parent_session.bump,
+        constraint = parent_session.vault == vault.key() @ HydentityError::UnauthorizedDelegate
+    )]
+    pub parent_session: Account<'info, DelegateSession>,
+
+    /// The child delegate's public key
+    /// CHECK: Any valid pubkey can be a delegate
+    pub delegate: UncheckedAccount<'info>,
+
+    /// The child session PDA to be created
+    #[account(
+        init,
+        payer = authority,
+        space = DelegateSession::LEN,
+        seeds = [DELEGATE_SEED, sns_name_account.key().as_ref(), delegate.key().as_ref()],
+        bump
+    )]
+    pub delegate_session: Account<'info, DelegateSession>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<AddSubDelegate>,
+    expires_at: i64,
+    permissions: u8,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    // A sold domain freezes the previous owner until the buyer claims the vault
+    ctx.accounts.vault.verify_domain_control(&ctx.accounts.sns_name_account)?;
+
+    // Validate expiration is in the future
+    if expires_at <= now {
+        return Err(HydentityError::DelegateExpired.into());
+    }
+
+    let parent = &ctx.accounts.parent_session;
+
+    // The parent must itself be live and allowed to sub-delegate
+    if !parent.is_valid(now) {
+        return Err(HydentityError::DelegateExpired.into());
+    }
+    if !parent.can_sub_delegate() {
+        return Err(HydentityError::InsufficientPermissions.into());
+    }
+    let parent_depth = parent.validate_chain(now, &ctx.accounts.vault, ctx.remaining_accounts)?;
+    require!(
+        parent_depth + 1 < MAX_DELEGATION_DEPTH,
+        HydentityError::DelegationDepthExceeded
+    );
+
+    // The child can only narrow the parent's grant
+    require!(
+        permissions & !parent.permissions == 0,
+        HydentityError::SubDelegationExceedsParent
+    );
+    require!(
+        expires_at <= parent.expires_at,
+        HydentityError::SubDelegationExceedsParent
+    );
+
+    let authority = ctx.accounts.authority.key();
+    let parent_key = parent.key();
+    let vault = &ctx.accounts.vault;
+    let delegate = ctx.accounts.delegate.key();
+    let sns_name = ctx.accounts.sns_name_account.key();
+
+    // Initialize the child session
+    let delegate_session = &mut ctx.accounts.delegate_session;
+    delegate_session.initialize(
+        vault.key(),
+        sns_name,
+        delegate,
+        authority,
+        expires_at,
+        permissions,
+        ctx.bumps.delegate_session,
+        now,
+    );
+    delegate_session.parent = parent_key;
+
+    emit!(DelegateAdded {
+        version: EVENT_SCHEMA_VERSION,
+        vault: vault.key(),
+        delegate,
+        granted_by: authority,
+        parent_session: parent_key,
+        permissions,
+        expires_at,
+        timestamp: now,
+    });
+
+    log_sensitive!("Sub-delegate added: {}", delegate);
+    log_sensitive!("Parent session: {}", parent_key);
+    log_info!("Permissions: {:#b}", permissions);
+    log_info!("Expires at: {}", expires_at);
+
+    Ok(())
+}
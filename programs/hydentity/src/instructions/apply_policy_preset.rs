@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, POLICY_SEED, POLICY_HISTORY_SEED, DELEGATE_SEED, OWNER_SET_SEED};
+use crate::errors::HydentityError;
+use crate::events::{PolicyPresetApplied, EVENT_SCHEMA_VERSION};
+use crate::instructions::update_policy::authorize_policy_change;
+use crate::state::{NameVault, PrivacyPolicy, PolicyHistory, PolicyPreset, DelegateSession, VaultOwnerSet};
+
+/// Apply a named privacy preset to the policy
+///
+/// Sets splits, delays, distribution, privacy mode and destination mode in
+/// one step and records the preset. Destinations are left unchanged.
+#[derive(Accounts)]
+pub struct ApplyPolicyPreset<'info> {
+    /// The caller (owner, owner set member or delegate)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault (for ownership verification)
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The privacy policy to update
+    #[account(
+        mut,
+        seeds = [POLICY_SEED, sns_name_account.key().as_ref()],
+        bump = policy.bump,
+        constraint = policy.vault == vault.key() @ HydentityError::InvalidPolicyConfig
+    )]
+    pub policy: Account<'info, PrivacyPolicy>,
+
+    /// Audit trail of policy changes (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = PolicyHistory::LEN,
+        seeds = [POLICY_HISTORY_SEED, sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub policy_history: Box<Account<'info, PolicyHistory>>,
+
+    /// System program (for creating the history account)
+    pub system_program: Program<'info, System>,
+
+    /// Optional delegate session (if caller is not the owner)
+    /// Child sessions must pass their parent sessions as remaining accounts
+    #[account(
+        seeds = [DELEGATE_SEED, sns_name_account.key().as_ref(), authority.key().as_ref()],
+        bump = delegate_session.bump,
+        constraint = delegate_session.vault == vault.key() @ HydentityError::UnauthorizedDelegate
+    )]
+    pub delegate_session: Option<Account<'info, DelegateSession>>,
+
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+}
+
+pub fn handler(ctx: Context<ApplyPolicyPreset>, preset: PolicyPreset) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    // A sold domain freezes the previous owner until the buyer claims the vault
+    ctx.accounts.vault.verify_domain_control(&ctx.accounts.sns_name_account)?;
+
+    let actor = authorize_policy_change(
+        &ctx.accounts.vault,
+        &ctx.accounts.authority.to_account_info(),
+        ctx.accounts.delegate_session.as_deref(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+        now,
+    )?;
+
+    // Timelocked policies only change through queue_policy_update
+    if ctx.accounts.policy.is_timelocked() {
+        return Err(HydentityError::PolicyUpdateRequiresQueue.into());
+    }
+
+    if actor.is_owner() {
+        ctx.accounts.vault.touch_owner_activity(now);
+    }
+
+    let policy = &mut ctx.accounts.policy;
+    let previous = (**policy).clone();
+    policy.apply_preset(preset)?;
+    policy.update(&previous, now)?;
+
+    let history = &mut ctx.accounts.policy_history;
+    history.initialize_if_needed(policy.vault, ctx.bumps.policy_history);
+    history.record(policy, ctx.accounts.authority.key(), actor, now)?;
+
+    emit!(PolicyPresetApplied {
+        version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.vault.key(),
+        preset,
+        policy_nonce: policy.policy_nonce,
+        timestamp: now,
+    });
+
+    log_info!("Policy preset {:?} applied. New nonce: {}", preset, policy.policy_nonce);
+
+    Ok(())
+}
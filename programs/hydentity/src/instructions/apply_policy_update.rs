@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, POLICY_SEED, POLICY_HISTORY_SEED, PENDING_POLICY_SEED};
+use crate::errors::HydentityError;
+use crate::events::{PolicyUpdateApplied, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, PrivacyPolicy, PolicyHistory, PendingPolicyUpdate};
+
+/// Apply a queued policy update once its delay has elapsed
+///
+/// Permissionless: anyone can crank a matured update. The pending account
+/// is closed and its rent returned to the original payer.
+#[derive(Accounts)]
+pub struct ApplyPolicyUpdate<'info> {
+    /// Any account may apply the update (pays for the history account if needed)
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault the policy belongs to
+    #[account(
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The privacy policy to update
+    #[account(
+        mut,
+        seeds = [POLICY_SEED, sns_name_account.key().as_ref()],
+        bump = policy.bump,
+        constraint = policy.vault == vault.key() @ HydentityError::InvalidPolicyConfig
+    )]
+    pub policy: Account<'info, PrivacyPolicy>,
+
+    /// The matured update
+    #[account(
+        mut,
+        close = payer,
+        seeds = [PENDING_POLICY_SEED, sns_name_account.key().as_ref()],
+        bump = pending_update.bump,
+        constraint = pending_update.vault == vault.key() @ HydentityError::InvalidVault
+    )]
+    pub pending_update: Account<'info, PendingPolicyUpdate>,
+
+    /// The account that paid for the update (receives rent)
+    /// CHECK: Validated against pending_update.payer
+    #[account(
+        mut,
+        address = pending_update.payer @ HydentityError::InvalidVault
+    )]
+    pub payer: UncheckedAccount<'info>,
+
+    /// Audit trail of policy changes (created on first use)
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = PolicyHistory::LEN,
+        seeds = [POLICY_HISTORY_SEED, sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub policy_history: Box<Account<'info, PolicyHistory>>,
+
+    /// System program (for creating the history account)
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ApplyPolicyUpdate>) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    // A sold domain freezes the previous owner until the buyer claims the vault
+    ctx.accounts.vault.verify_domain_control(&ctx.accounts.sns_name_account)?;
+
+    let pending = &ctx.accounts.pending_update;
+    let policy = &mut ctx.accounts.policy;
+    pending.verify_applicable(&ctx.accounts.vault.owner, policy.policy_nonce, now)?;
+
+    // The nonce only advances here, never at queue time
+    let previous = (**policy).clone();
+    pending.params.apply(policy, now);
+    policy.update(&previous, now)?;
+
+    // Attribute the change to whoever queued it
+    let history = &mut ctx.accounts.policy_history;
+    history.initialize_if_needed(policy.vault, ctx.bumps.policy_history);
+    history.record(policy, pending.queued_by, pending.actor_type, now)?;
+
+    emit!(PolicyUpdateApplied {
+        version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.vault.key(),
+        policy_nonce: policy.policy_nonce,
+        timestamp: now,
+    });
+
+    log_info!("Queued policy update applied. New nonce: {}", policy.policy_nonce);
+
+    Ok(())
+}
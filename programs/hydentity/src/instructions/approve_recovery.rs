@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, RECOVERY_CONFIG_SEED, RECOVERY_REQUEST_SEED};
+use crate::errors::HydentityError;
+use crate::events::{RecoveryApproved, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, RecoveryConfig, RecoveryRequest};
+
+/// Approve a pending recovery request
+///
+/// Once the guardian threshold is reached, the veto timelock starts.
+#[derive(Accounts)]
+pub struct ApproveRecovery<'info> {
+    /// A registered guardian (must be signer)
+    pub guardian: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault being recovered
+    #[account(
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The vault's recovery config
+    #[account(
+        seeds = [RECOVERY_CONFIG_SEED, sns_name_account.key().as_ref()],
+        bump = recovery_config.bump,
+        constraint = recovery_config.vault == vault.key() @ HydentityError::InvalidRecoveryConfig
+    )]
+    pub recovery_config: Account<'info, RecoveryConfig>,
+
+    /// The pending recovery request
+    #[account(
+        mut,
+        seeds = [RECOVERY_REQUEST_SEED, sns_name_account.key().as_ref()],
+        bump = recovery_request.bump,
+        constraint = recovery_request.vault == vault.key() @ HydentityError::InvalidVault
+    )]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+}
+
+pub fn handler(ctx: Context<ApproveRecovery>) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    let guardian = ctx.accounts.guardian.key();
+    let request = &mut ctx.accounts.recovery_request;
+    request.approve(guardian, &ctx.accounts.recovery_config, now)?;
+
+    emit!(RecoveryApproved {
+        version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.vault.key(),
+        guardian,
+        approvals: request.approvals.len() as u8,
+        executable_at: request.executable_at,
+        timestamp: now,
+    });
+
+    log_sensitive!("Recovery approved by guardian: {}", guardian);
+    if request.executable_at != 0 {
+        log_info!("Recovery executable at: {}", request.executable_at);
+    }
+
+    Ok(())
+}
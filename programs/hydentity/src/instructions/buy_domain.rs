@@ -0,0 +1,280 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Token, TokenAccount};
+use crate::constants::{
+    VAULT_SEED, VAULT_AUTH_SEED, POLICY_SEED, POLICY_HISTORY_SEED, DOMAIN_LISTING_SEED,
+    WITHDRAWAL_CONFIG_SEED, RECOVERY_CONFIG_SEED, SNS_NAME_PROGRAM_ID,
+};
+use crate::errors::HydentityError;
+use crate::events::{DomainSold, EVENT_SCHEMA_VERSION};
+use crate::state::{
+    NameVault, VaultAuthority, PrivacyPolicy, PolicyHistory, PolicyActor, DomainListing,
+    DirectWithdrawalConfig, read_sns_owner, move_owner_index_entry, close_program_account,
+};
+use super::reclaim_domain::build_sns_transfer_instruction;
+
+/// Buy a listed domain
+///
+/// Pays the listing price to the seller's proceeds destination and hands
+/// over the domain in one transaction, so neither side can be left with
+/// only half of the trade. Depending on the listing, the vault authority
+/// transfers the SNS domain to the buyer (who can then `claim_vault`), or
+/// the buyer becomes the vault's owner with the domain still held by the
+/// vault authority.
+///
+/// A re-keyed vault starts over for the buyer: the seller's recovery and
+/// withdrawal timelock configs are closed, and the seller's delegate
+/// sessions and outflow limits stop applying (see `NameVault::rekeyed_at`).
+#[derive(Accounts)]
+pub struct BuyDomain<'info> {
+    /// The buyer (must be signer, pays the price)
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// The SNS name account being sold
+    /// CHECK: Must be owned by the SNS Name Program; owner field read manually
+    #[account(
+        mut,
+        constraint = sns_name_account.owner == &SNS_NAME_PROGRAM_ID @ HydentityError::InvalidSnsName
+    )]
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault holding the domain
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName,
+        constraint = vault.domain_transferred @ HydentityError::DomainNotTransferred,
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The vault authority PDA (current SNS owner, signs the domain transfer)
+    #[account(
+        seeds = [VAULT_AUTH_SEED, sns_name_account.key().as_ref()],
+        bump = vault_authority.bump,
+    )]
+    pub vault_authority: Account<'info, VaultAuthority>,
+
+    /// The privacy policy (reset for the buyer when the vault is re-keyed)
+    #[account(
+        mut,
+        seeds = [POLICY_SEED, sns_name_account.key().as_ref()],
+        bump = policy.bump,
+        constraint = policy.vault == vault.key() @ HydentityError::InvalidPolicyConfig,
+    )]
+    pub policy: Account<'info, PrivacyPolicy>,
+
+    /// Audit trail of policy changes (created on first use)
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = PolicyHistory::LEN,
+        seeds = [POLICY_HISTORY_SEED, sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub policy_history: Box<Account<'info, PolicyHistory>>,
+
+    /// The listing being settled
+    #[account(
+        mut,
+        close = listed_by,
+        seeds = [DOMAIN_LISTING_SEED, sns_name_account.key().as_ref()],
+        bump = listing.bump,
+        constraint = listing.vault == vault.key() @ HydentityError::InvalidVault
+    )]
+    pub listing: Account<'info, DomainListing>,
+
+    /// The account that paid for the listing (receives rent)
+    /// CHECK: Validated against listing.listed_by
+    #[account(
+        mut,
+        address = listing.listed_by @ HydentityError::InvalidVault
+    )]
+    pub listed_by: UncheckedAccount<'info>,
+
+    /// Receives the payment
+    /// CHECK: Validated against listing.proceeds_destination
+    #[account(
+        mut,
+        address = listing.proceeds_destination @ HydentityError::InvalidProceedsDestination
+    )]
+    pub proceeds_destination: UncheckedAccount<'info>,
+
+    /// Optional: Buyer's token account for SPL payments
+    #[account(
+        mut,
+        token::authority = buyer,
+        constraint = buyer_token_account.mint == listing.mint @ HydentityError::InvalidMint
+    )]
+    pub buyer_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// The seller's vault index (vault removed when the vault is re-keyed)
+    /// CHECK: Address and contents validated by remove_from_owner_index
+    #[account(mut)]
+    pub seller_owner_index: UncheckedAccount<'info>,
+
+    /// The buyer's vault index (vault added when the vault is re-keyed)
+    /// CHECK: Address and contents validated by add_to_owner_index
+    #[account(mut)]
+    pub buyer_owner_index: UncheckedAccount<'info>,
+
+    /// The seller's withdrawal timelock config (required if configured, closed when the vault is re-keyed)
+    #[account(
+        mut,
+        seeds = [WITHDRAWAL_CONFIG_SEED, sns_name_account.key().as_ref()],
+        bump = withdrawal_config.bump,
+        constraint = withdrawal_config.vault == vault.key() @ HydentityError::InvalidVault
+    )]
+    pub withdrawal_config: Option<Account<'info, DirectWithdrawalConfig>>,
+
+    /// The seller's recovery config PDA (may be uninitialized, closed when the vault is re-keyed)
+    /// CHECK: Address checked by seeds; closed by close_program_account if it exists
+    #[account(
+        mut,
+        seeds = [RECOVERY_CONFIG_SEED, sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub recovery_config: UncheckedAccount<'info>,
+
+    /// SNS Name Program
+    /// CHECK: Validated by constraint
+    #[account(
+        constraint = sns_name_program.key() == SNS_NAME_PROGRAM_ID @ HydentityError::InvalidSnsName
+    )]
+    pub sns_name_program: UncheckedAccount<'info>,
+
+    /// Token program for SPL payments
+    pub token_program: Program<'info, Token>,
+
+    /// System program for SOL payments, the history account and the buyer's index
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<BuyDomain>, price: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    let buyer = ctx.accounts.buyer.key();
+    let seller = ctx.accounts.vault.owner;
+    let vault_authority = ctx.accounts.vault_authority.key();
+
+    ctx.accounts.listing.verify_purchase(&seller, &buyer, price)?;
+
+    // The domain must still be in escrow with the vault authority
+    require_keys_eq!(
+        read_sns_owner(&ctx.accounts.sns_name_account)?,
+        vault_authority,
+        HydentityError::DomainNotTransferred
+    );
+
+    let listing = &ctx.accounts.listing;
+    if listing.is_sol() {
+        let cpi_accounts = system_program::Transfer {
+            from: ctx.accounts.buyer.to_account_info(),
+            to: ctx.accounts.proceeds_destination.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, price)?;
+    } else {
+        let buyer_token_account = ctx.accounts.buyer_token_account
+            .as_ref()
+            .ok_or(HydentityError::InvalidMint)?;
+
+        let cpi_accounts = token::Transfer {
+            from: buyer_token_account.to_account_info(),
+            to: ctx.accounts.proceeds_destination.to_account_info(),
+            authority: ctx.accounts.buyer.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, price)?;
+    }
+
+    let release_domain = listing.release_domain;
+    let mint = listing.mint;
+    if release_domain {
+        let sns_name_key = ctx.accounts.sns_name_account.key();
+        let bump = ctx.accounts.vault_authority.bump;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            VAULT_AUTH_SEED,
+            sns_name_key.as_ref(),
+            &[bump],
+        ]];
+
+        let transfer_ix = build_sns_transfer_instruction(sns_name_key, vault_authority, buyer);
+        invoke_signed(
+            &transfer_ix,
+            &[
+                ctx.accounts.sns_name_account.to_account_info(),
+                ctx.accounts.vault_authority.to_account_info(),
+            ],
+            signer_seeds,
+        ).map_err(|_| HydentityError::SnsTransferFailed)?;
+
+        require_keys_eq!(
+            read_sns_owner(&ctx.accounts.sns_name_account)?,
+            buyer,
+            HydentityError::SnsTransferFailed
+        );
+
+        // The seller is frozen out until the buyer calls claim_vault
+        let vault = &mut ctx.accounts.vault;
+        vault.sync_domain_state(&buyer, &vault_authority);
+        vault.owner_rotated = false;
+    } else {
+        // Close the seller's withdrawal timelock and recovery guardians
+        if ctx.accounts.vault.has_withdrawal_timelock {
+            let withdrawal_config = ctx.accounts.withdrawal_config
+                .as_ref()
+                .ok_or(HydentityError::WithdrawalConfigRequired)?;
+            withdrawal_config.close(ctx.accounts.listed_by.to_account_info())?;
+        }
+        close_program_account(
+            &ctx.accounts.recovery_config.to_account_info(),
+            &ctx.accounts.listed_by.to_account_info(),
+        )?;
+
+        ctx.accounts.vault.rekey_for_sale(buyer, now);
+
+        // Reset policy for the buyer
+        let policy = &mut ctx.accounts.policy;
+        policy.destinations = vec![buyer];
+        policy.policy_nonce = policy.policy_nonce
+            .checked_add(1)
+            .ok_or(HydentityError::ArithmeticOverflow)?;
+        policy.updated_at = now;
+
+        let history = &mut ctx.accounts.policy_history;
+        history.initialize_if_needed(policy.vault, ctx.bumps.policy_history);
+        history.record(policy, buyer, PolicyActor::Buyer, now)?;
+
+        // Move the vault between the owners' indexes
+        move_owner_index_entry(
+            &ctx.accounts.seller_owner_index,
+            &seller,
+            &ctx.accounts.buyer_owner_index,
+            &buyer,
+            &ctx.accounts.vault.key(),
+            &ctx.accounts.buyer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+    }
+
+    emit!(DomainSold {
+        version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.vault.key(),
+        sns_name: ctx.accounts.sns_name_account.key(),
+        seller,
+        buyer,
+        price,
+        mint,
+        release_domain,
+        timestamp: now,
+    });
+
+    log_info!("Domain sale settled (domain released: {})", release_domain);
+    log_sensitive!("Domain sold by {} to {} for {}", seller, buyer, price);
+
+    Ok(())
+}
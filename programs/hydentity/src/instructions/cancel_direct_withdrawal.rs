@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, OWNER_SET_SEED, RECOVERY_CONFIG_SEED, WITHDRAWAL_CONFIG_SEED, DIRECT_WITHDRAWAL_SEED};
+use crate::errors::HydentityError;
+use crate::events::{DirectWithdrawalCancelled, EVENT_SCHEMA_VERSION};
+use crate::state::{
+    NameVault, DirectWithdrawalConfig, PendingDirectWithdrawal, RecoveryConfig, VaultOwnerSet, VaultProfile,
+    verify_owner_authority,
+};
+
+/// Cancel a queued direct withdrawal
+///
+/// Can be signed by the vault owner (or an owner set quorum), any
+/// recovery guardian, or the configured security key. Pass the profile
+/// to cancel a withdrawal queued with `queue_profile_withdrawal`.
+#[derive(Accounts)]
+pub struct CancelDirectWithdrawal<'info> {
+    /// The owner, a guardian or the security key (must be signer)
+    pub authority: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault the withdrawal was queued against
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The vault's timelock config (holds the security key)
+    #[account(
+        seeds = [WITHDRAWAL_CONFIG_SEED, sns_name_account.key().as_ref()],
+        bump = withdrawal_config.bump,
+        constraint = withdrawal_config.vault == vault.key() @ HydentityError::WithdrawalConfigRequired
+    )]
+    pub withdrawal_config: Account<'info, DirectWithdrawalConfig>,
+
+    /// The queued withdrawal to cancel
+    #[account(
+        mut,
+        close = requested_by,
+        seeds = [
+            DIRECT_WITHDRAWAL_SEED,
+            profile.as_ref().map_or(vault.key(), |profile| profile.key()).as_ref(),
+            &pending_withdrawal.withdrawal_id.to_le_bytes(),
+        ],
+        bump = pending_withdrawal.bump,
+        constraint = pending_withdrawal.vault == profile.as_ref().map_or(vault.key(), |profile| profile.key())
+            @ HydentityError::InvalidVault
+    )]
+    pub pending_withdrawal: Account<'info, PendingDirectWithdrawal>,
+
+    /// The account that paid for the request (receives rent)
+    /// CHECK: Validated against pending_withdrawal.requested_by
+    #[account(
+        mut,
+        address = pending_withdrawal.requested_by @ HydentityError::InvalidVault
+    )]
+    pub requested_by: UncheckedAccount<'info>,
+
+    /// Optional recovery config, when cancelling as a guardian
+    #[account(
+        seeds = [RECOVERY_CONFIG_SEED, sns_name_account.key().as_ref()],
+        bump = recovery_config.bump,
+        constraint = recovery_config.vault == vault.key() @ HydentityError::InvalidRecoveryConfig
+    )]
+    pub recovery_config: Option<Account<'info, RecoveryConfig>>,
+
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+
+    /// Optional profile, when the withdrawal was queued against one
+    #[account(
+        constraint = profile.vault == vault.key() @ HydentityError::ProfileNotFound
+    )]
+    pub profile: Option<Account<'info, VaultProfile>>,
+}
+
+pub fn handler(ctx: Context<CancelDirectWithdrawal>) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    let authority = ctx.accounts.authority.key();
+
+    let is_security_key = ctx.accounts.withdrawal_config.is_security_key(&authority, now);
+    let is_guardian = ctx.accounts.recovery_config
+        .as_deref()
+        .is_some_and(|config| config.is_guardian(&authority));
+
+    if !is_security_key && !is_guardian {
+        verify_owner_authority(
+            &ctx.accounts.vault,
+            &ctx.accounts.authority.to_account_info(),
+            ctx.accounts.owner_set.as_deref(),
+            ctx.remaining_accounts,
+        )?;
+
+        ctx.accounts.vault.touch_owner_activity(now);
+    }
+
+    let withdrawal_id = ctx.accounts.pending_withdrawal.withdrawal_id;
+
+    emit!(DirectWithdrawalCancelled {
+        version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.pending_withdrawal.vault,
+        withdrawal_id,
+        cancelled_by: authority,
+        timestamp: now,
+    });
+
+    log_sensitive!("Direct withdrawal #{} cancelled by: {}", withdrawal_id, authority);
+
+    Ok(())
+}
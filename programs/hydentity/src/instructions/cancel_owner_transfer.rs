@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, OWNER_SET_SEED, OWNER_TRANSFER_SEED};
+use crate::errors::HydentityError;
+use crate::events::{OwnerTransferCancelled, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, PendingOwnerTransfer, VaultOwnerSet, verify_owner_authority};
+
+/// Cancel a pending owner transfer
+///
+/// The current owner (or an owner set quorum) can withdraw the proposal,
+/// and the proposed new owner can decline it.
+#[derive(Accounts)]
+pub struct CancelOwnerTransfer<'info> {
+    /// The vault owner, an owner set member, or the proposed new owner (must be signer)
+    pub authority: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault whose owner transfer is cancelled
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The pending transfer to cancel
+    #[account(
+        mut,
+        close = proposed_by,
+        seeds = [OWNER_TRANSFER_SEED, sns_name_account.key().as_ref()],
+        bump = pending_transfer.bump,
+        constraint = pending_transfer.vault == vault.key() @ HydentityError::InvalidVault
+    )]
+    pub pending_transfer: Account<'info, PendingOwnerTransfer>,
+
+    /// The account that paid for the proposal (receives rent)
+    /// CHECK: Validated against pending_transfer.proposed_by
+    #[account(
+        mut,
+        address = pending_transfer.proposed_by @ HydentityError::InvalidVault
+    )]
+    pub proposed_by: UncheckedAccount<'info>,
+
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+}
+
+pub fn handler(ctx: Context<CancelOwnerTransfer>) -> Result<()> {
+    let clock = Clock::get()?;
+    let authority = ctx.accounts.authority.key();
+    let new_owner = ctx.accounts.pending_transfer.new_owner;
+
+    // The proposed owner can always decline
+    if authority != new_owner {
+        verify_owner_authority(
+            &ctx.accounts.vault,
+            &ctx.accounts.authority.to_account_info(),
+            ctx.accounts.owner_set.as_deref(),
+            ctx.remaining_accounts,
+        )?;
+
+        ctx.accounts.vault.touch_owner_activity(clock.unix_timestamp);
+    }
+
+    emit!(OwnerTransferCancelled {
+        version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.vault.key(),
+        new_owner,
+        cancelled_by: authority,
+        timestamp: clock.unix_timestamp,
+    });
+
+    log_sensitive!("Owner transfer cancelled by: {}", authority);
+
+    Ok(())
+}
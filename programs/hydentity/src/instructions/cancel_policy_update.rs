@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, OWNER_SET_SEED, PENDING_POLICY_SEED};
+use crate::errors::HydentityError;
+use crate::events::{PolicyUpdateCancelled, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, PendingPolicyUpdate, VaultOwnerSet, verify_owner_authority};
+
+/// Cancel a queued policy update (owner or owner set quorum only)
+#[derive(Accounts)]
+pub struct CancelPolicyUpdate<'info> {
+    /// The vault owner or an owner set member (must be signer)
+    pub owner: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault the update was queued against
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The queued update to cancel
+    #[account(
+        mut,
+        close = payer,
+        seeds = [PENDING_POLICY_SEED, sns_name_account.key().as_ref()],
+        bump = pending_update.bump,
+        constraint = pending_update.vault == vault.key() @ HydentityError::InvalidVault
+    )]
+    pub pending_update: Account<'info, PendingPolicyUpdate>,
+
+    /// The account that paid for the update (receives rent)
+    /// CHECK: Validated against pending_update.payer
+    #[account(
+        mut,
+        address = pending_update.payer @ HydentityError::InvalidVault
+    )]
+    pub payer: UncheckedAccount<'info>,
+
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+}
+
+pub fn handler(ctx: Context<CancelPolicyUpdate>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    ctx.accounts.vault.touch_owner_activity(clock.unix_timestamp);
+
+    emit!(PolicyUpdateCancelled {
+        version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.vault.key(),
+        cancelled_by: ctx.accounts.owner.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    log_info!("Queued policy update cancelled");
+
+    Ok(())
+}
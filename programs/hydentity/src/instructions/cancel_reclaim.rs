@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, OWNER_SET_SEED, PENDING_RECLAIM_SEED};
+use crate::errors::HydentityError;
+use crate::events::{ReclaimCancelled, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, VaultOwnerSet, PendingReclaim, verify_owner_authority};
+
+/// Cancel a pending domain reclaim
+///
+/// The domain stays with the vault authority. Rent goes back to whoever
+/// initiated the reclaim.
+#[derive(Accounts)]
+pub struct CancelReclaim<'info> {
+    /// The vault owner or an owner set member (must be signer)
+    pub owner: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault whose reclaim is cancelled
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The pending reclaim to cancel
+    #[account(
+        mut,
+        close = initiated_by,
+        seeds = [PENDING_RECLAIM_SEED, sns_name_account.key().as_ref()],
+        bump = pending_reclaim.bump,
+        constraint = pending_reclaim.vault == vault.key() @ HydentityError::InvalidVault
+    )]
+    pub pending_reclaim: Account<'info, PendingReclaim>,
+
+    /// The account that paid for the request (receives rent)
+    /// CHECK: Validated against pending_reclaim.initiated_by
+    #[account(
+        mut,
+        address = pending_reclaim.initiated_by @ HydentityError::InvalidVault
+    )]
+    pub initiated_by: UncheckedAccount<'info>,
+
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+}
+
+pub fn handler(ctx: Context<CancelReclaim>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    ctx.accounts.vault.touch_owner_activity(clock.unix_timestamp);
+
+    emit!(ReclaimCancelled {
+        version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.vault.key(),
+        cancelled_by: ctx.accounts.owner.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    log_info!("Pending domain reclaim cancelled");
+
+    Ok(())
+}
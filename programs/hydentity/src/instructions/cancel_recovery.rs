@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, OWNER_SET_SEED, RECOVERY_REQUEST_SEED};
+use crate::errors::HydentityError;
+use crate::events::{RecoveryCancelled, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, RecoveryRequest, VaultOwnerSet, verify_owner_authority};
+
+/// Veto a pending recovery request
+///
+/// The current owner (or an owner set quorum) can cancel a recovery at
+/// any time before it executes. Rent is returned to the guardian who
+/// opened the request.
+#[derive(Accounts)]
+pub struct CancelRecovery<'info> {
+    /// The vault owner or an owner set member (must be signer)
+    pub owner: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault being recovered
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The recovery request to cancel
+    #[account(
+        mut,
+        close = initiator,
+        seeds = [RECOVERY_REQUEST_SEED, sns_name_account.key().as_ref()],
+        bump = recovery_request.bump,
+        constraint = recovery_request.vault == vault.key() @ HydentityError::InvalidVault
+    )]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+
+    /// The guardian who opened the request (receives rent)
+    /// CHECK: Validated against recovery_request.initiator
+    #[account(
+        mut,
+        address = recovery_request.initiator @ HydentityError::InvalidVault
+    )]
+    pub initiator: UncheckedAccount<'info>,
+
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+}
+
+pub fn handler(ctx: Context<CancelRecovery>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    ctx.accounts.vault.touch_owner_activity(clock.unix_timestamp);
+
+    emit!(RecoveryCancelled {
+        version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.vault.key(),
+        proposed_owner: ctx.accounts.recovery_request.proposed_owner,
+        timestamp: clock.unix_timestamp,
+    });
+
+    log_info!("Recovery vetoed by owner");
+
+    Ok(())
+}
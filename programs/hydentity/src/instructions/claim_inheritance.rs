@@ -0,0 +1,179 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{program::invoke_signed, instruction::Instruction};
+use crate::constants::{VAULT_SEED, VAULT_AUTH_SEED, POLICY_SEED, POLICY_HISTORY_SEED, SNS_NAME_PROGRAM_ID};
+use crate::errors::HydentityError;
+use crate::events::{InheritanceClaimed, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, VaultAuthority, PrivacyPolicy, PolicyHistory, PolicyActor, move_owner_index_entry};
+
+/// Take over a vault after the owner's inactivity window has passed
+///
+/// The beneficiary becomes `NameVault.owner` and the policy destinations
+/// are reset to the beneficiary. The domain must be held by the vault
+/// authority (`domain_transferred`); SNS ownership is transferred to the
+/// beneficiary along with the vault.
+#[derive(Accounts)]
+pub struct ClaimInheritance<'info> {
+    /// The configured beneficiary (must be signer, pays for growing their index and the policy history)
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field and by the SNS Name Program during CPI
+    #[account(mut)]
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault being inherited
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName,
+        constraint = vault.has_beneficiary() && vault.beneficiary == beneficiary.key() @ HydentityError::NotBeneficiary
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The vault authority PDA (signs the SNS transfer)
+    #[account(
+        seeds = [VAULT_AUTH_SEED, sns_name_account.key().as_ref()],
+        bump = vault_authority.bump,
+    )]
+    pub vault_authority: Account<'info, VaultAuthority>,
+
+    /// The privacy policy (destinations reset to the beneficiary)
+    #[account(
+        mut,
+        seeds = [POLICY_SEED, sns_name_account.key().as_ref()],
+        bump = policy.bump,
+        constraint = policy.vault == vault.key() @ HydentityError::InvalidPolicyConfig,
+    )]
+    pub policy: Account<'info, PrivacyPolicy>,
+
+    /// Audit trail of policy changes (created on first use)
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        space = PolicyHistory::LEN,
+        seeds = [POLICY_HISTORY_SEED, sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub policy_history: Box<Account<'info, PolicyHistory>>,
+
+    /// SNS Name Program
+    /// CHECK: Validated by constraint
+    #[account(
+        constraint = sns_name_program.key() == SNS_NAME_PROGRAM_ID @ HydentityError::InvalidSnsName
+    )]
+    pub sns_name_program: UncheckedAccount<'info>,
+
+    /// The previous owner's vault index (vault removed, if the index exists)
+    /// CHECK: Address and contents validated by remove_from_owner_index
+    #[account(mut)]
+    pub previous_owner_index: UncheckedAccount<'info>,
+
+    /// The new owner's vault index (created or grown as needed)
+    /// CHECK: Address and contents validated by add_to_owner_index
+    #[account(mut)]
+    pub new_owner_index: UncheckedAccount<'info>,
+
+    /// System program (for creating or growing the new owner's index)
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimInheritance>) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    ctx.accounts.vault.verify_inheritance_claim(now)?;
+
+    let beneficiary = ctx.accounts.beneficiary.key();
+    let previous_owner = ctx.accounts.vault.owner;
+    let domain_transferred = ctx.accounts.vault.domain_transferred;
+
+    // Hand the domain over with the vault
+    let sns_name_account = &ctx.accounts.sns_name_account;
+    let vault_authority = &ctx.accounts.vault_authority;
+
+    let name_data = sns_name_account.try_borrow_data()?;
+    let current_owner_bytes: [u8; 32] = name_data[32..64].try_into()
+        .map_err(|_| HydentityError::InvalidSnsName)?;
+    let current_owner = Pubkey::new_from_array(current_owner_bytes);
+
+    require!(
+        current_owner == vault_authority.key(),
+        HydentityError::DomainNotTransferred
+    );
+    drop(name_data);
+
+    let mut data = Vec::with_capacity(33);
+    data.push(2); // Transfer instruction tag
+    data.extend_from_slice(beneficiary.as_ref());
+
+    let transfer_ix = Instruction {
+        program_id: SNS_NAME_PROGRAM_ID,
+        accounts: vec![
+            anchor_lang::solana_program::instruction::AccountMeta::new(*sns_name_account.key, false),
+            anchor_lang::solana_program::instruction::AccountMeta::new_readonly(vault_authority.key(), true),
+        ],
+        data,
+    };
+
+    let sns_name_key = ctx.accounts.vault.sns_name;
+    let bump = vault_authority.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        VAULT_AUTH_SEED,
+        sns_name_key.as_ref(),
+        &[bump],
+    ]];
+
+    invoke_signed(
+        &transfer_ix,
+        &[
+            sns_name_account.to_account_info(),
+            vault_authority.to_account_info(),
+        ],
+        signer_seeds,
+    ).map_err(|_| HydentityError::SnsTransferFailed)?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.rotate_owner(beneficiary, false);
+    vault.set_domain_transferred(false);
+    vault.clear_beneficiary();
+    vault.touch_owner_activity(now);
+
+    // Move the vault between the owners' indexes
+    move_owner_index_entry(
+        &ctx.accounts.previous_owner_index,
+        &previous_owner,
+        &ctx.accounts.new_owner_index,
+        &beneficiary,
+        &ctx.accounts.vault.key(),
+        &ctx.accounts.beneficiary.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    // Reset policy destinations for the beneficiary
+    let policy = &mut ctx.accounts.policy;
+    policy.destinations = vec![beneficiary];
+    policy.policy_nonce = policy.policy_nonce
+        .checked_add(1)
+        .ok_or(HydentityError::ArithmeticOverflow)?;
+    policy.updated_at = now;
+
+    let history = &mut ctx.accounts.policy_history;
+    history.initialize_if_needed(policy.vault, ctx.bumps.policy_history);
+    history.record(policy, beneficiary, PolicyActor::Beneficiary, now)?;
+
+    emit!(InheritanceClaimed {
+        version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.vault.key(),
+        previous_owner,
+        beneficiary,
+        domain_transferred,
+        timestamp: now,
+    });
+
+    log_sensitive!("Vault inherited by beneficiary: {}", beneficiary);
+    log_sensitive!("Previous owner: {}", previous_owner);
+
+    Ok(())
+}
@@ -1,14 +1,24 @@
 use anchor_lang::prelude::*;
-use crate::constants::{VAULT_SEED, POLICY_SEED, SNS_NAME_PROGRAM_ID};
+use anchor_spl::token::TokenAccount;
+use crate::constants::{
+    VAULT_SEED, POLICY_SEED, POLICY_HISTORY_SEED, OWNER_SET_SEED, WITHDRAWAL_CONFIG_SEED, RECOVERY_CONFIG_SEED,
+    SNS_NAME_PROGRAM_ID,
+};
 use crate::errors::HydentityError;
-use crate::events::VaultClaimed;
-use crate::state::{NameVault, PrivacyPolicy};
+use crate::events::{VaultClaimed, EVENT_SCHEMA_VERSION};
+use crate::state::{
+    NameVault, PrivacyPolicy, PolicyHistory, PolicyActor, VaultOwnerSet, DirectWithdrawalConfig,
+    verify_subdomain_claim, move_owner_index_entry, close_program_account,
+};
 
 /// Claim an existing vault after domain transfer/sale
 ///
 /// The new SNS domain owner takes over an existing vault.
 /// Verifies the signer is the current SNS domain owner,
 /// updates vault ownership, and resets the privacy policy.
+/// The vault starts over like one re-keyed by `buy_domain`: the previous
+/// owner's owner set, withdrawal timelock and recovery configs are closed,
+/// and their delegate sessions and outflow limits stop applying.
 #[derive(Accounts)]
 pub struct ClaimVault<'info> {
     /// The new domain owner (must be signer)
@@ -16,7 +26,7 @@ pub struct ClaimVault<'info> {
     pub new_owner: Signer<'info>,
 
     /// The SNS name account
-    /// CHECK: Validated by constraint and in handler via verify_sns_ownership
+    /// CHECK: Validated by constraint and in handler via verify_subdomain_claim
     #[account(
         constraint = sns_name_account.owner == &SNS_NAME_PROGRAM_ID @ HydentityError::InvalidSnsName
     )]
@@ -40,46 +50,114 @@ pub struct ClaimVault<'info> {
         constraint = policy.vault == vault.key() @ HydentityError::InvalidPolicyConfig,
     )]
     pub policy: Account<'info, PrivacyPolicy>,
-}
 
-/// Verify that the signer owns the SNS name account
-fn verify_sns_ownership(sns_account: &AccountInfo, expected_owner: &Pubkey) -> Result<()> {
-    let data = sns_account.try_borrow_data()?;
+    /// Audit trail of policy changes (created on first use)
+    #[account(
+        init_if_needed,
+        payer = new_owner,
+        space = PolicyHistory::LEN,
+        seeds = [POLICY_HISTORY_SEED, sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub policy_history: Box<Account<'info, PolicyHistory>>,
 
-    if data.len() < 64 {
-        return Err(HydentityError::InvalidSnsName.into());
-    }
+    /// The previous owner's vault index (vault removed, if the index exists)
+    /// CHECK: Address and contents validated by remove_from_owner_index
+    #[account(mut)]
+    pub previous_owner_index: UncheckedAccount<'info>,
 
-    let owner_bytes: [u8; 32] = data[32..64]
-        .try_into()
-        .map_err(|_| HydentityError::InvalidSnsName)?;
+    /// The new owner's vault index (created or grown as needed)
+    /// CHECK: Address and contents validated by add_to_owner_index
+    #[account(mut)]
+    pub new_owner_index: UncheckedAccount<'info>,
 
-    let sns_owner = Pubkey::new_from_array(owner_bytes);
+    /// System program (for creating the history and index accounts)
+    pub system_program: Program<'info, System>,
 
-    require!(
-        sns_owner == *expected_owner,
-        HydentityError::SnsOwnershipVerificationFailed
-    );
+    /// Optional parent domain name account, for a parent owner claiming a subdomain vault
+    /// CHECK: Must match the subdomain's parent_name, checked in verify_subdomain_claim
+    pub parent_name_account: Option<UncheckedAccount<'info>>,
 
-    msg!("SNS ownership verified for domain owner: {}", sns_owner);
-    Ok(())
+    /// Optional parent domain vault (must have parent_controls_subdomains enabled)
+    pub parent_vault: Option<Account<'info, NameVault>>,
+
+    /// Token account holding the domain's NFT (tokenized domains only)
+    pub nft_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// The previous owner (receives rent from their closed accounts)
+    /// CHECK: Validated against vault.owner
+    #[account(
+        mut,
+        address = vault.owner @ HydentityError::Unauthorized
+    )]
+    pub previous_owner: UncheckedAccount<'info>,
+
+    /// The previous owner's owner set (required if configured, closed on claim)
+    #[account(
+        mut,
+        close = previous_owner,
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+
+    /// The previous owner's withdrawal timelock config (required if configured, closed on claim)
+    #[account(
+        mut,
+        close = previous_owner,
+        seeds = [WITHDRAWAL_CONFIG_SEED, sns_name_account.key().as_ref()],
+        bump = withdrawal_config.bump,
+        constraint = withdrawal_config.vault == vault.key() @ HydentityError::InvalidVault
+    )]
+    pub withdrawal_config: Option<Account<'info, DirectWithdrawalConfig>>,
+
+    /// The previous owner's recovery config PDA (may be uninitialized, closed on claim)
+    /// CHECK: Address checked by seeds; closed by close_program_account if it exists
+    #[account(
+        mut,
+        seeds = [RECOVERY_CONFIG_SEED, sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub recovery_config: UncheckedAccount<'info>,
 }
 
 pub fn handler(ctx: Context<ClaimVault>) -> Result<()> {
     let clock = Clock::get()?;
     let now = clock.unix_timestamp;
 
-    // Verify the signer is the current SNS domain owner
-    verify_sns_ownership(
-        &ctx.accounts.sns_name_account.to_account_info(),
+    // Verify the signer is the current SNS domain owner (or controls it via the parent domain)
+    let managed_by_parent = verify_subdomain_claim(
+        &ctx.accounts.sns_name_account,
+        ctx.accounts.nft_token_account.as_deref(),
+        ctx.accounts.parent_name_account.as_deref(),
+        ctx.accounts.parent_vault.as_ref(),
         &ctx.accounts.new_owner.key(),
     )?;
 
     let previous_owner = ctx.accounts.vault.owner;
 
-    // Update vault owner
+    // The previous owner's owner set would otherwise still pass verify_owner_authority
+    require!(
+        !ctx.accounts.vault.has_owner_set || ctx.accounts.owner_set.is_some(),
+        HydentityError::OwnerSetRequired
+    );
+    require!(
+        !ctx.accounts.vault.has_withdrawal_timelock || ctx.accounts.withdrawal_config.is_some(),
+        HydentityError::WithdrawalConfigRequired
+    );
+
+    // The previous owner's guardians have no say over the new owner's vault
+    close_program_account(
+        &ctx.accounts.recovery_config.to_account_info(),
+        &ctx.accounts.previous_owner.to_account_info(),
+    )?;
+
+    // Hand the vault over like a sale: beneficiary and owner set dropped,
+    // earlier delegate sessions and outflow limits voided
     let vault = &mut ctx.accounts.vault;
-    vault.owner = ctx.accounts.new_owner.key();
+    vault.rekey_for_sale(ctx.accounts.new_owner.key(), now);
+    vault.managed_by_parent = managed_by_parent;
 
     // Reset policy for new owner
     let policy = &mut ctx.accounts.policy;
@@ -89,15 +167,31 @@ pub fn handler(ctx: Context<ClaimVault>) -> Result<()> {
         .ok_or(HydentityError::ArithmeticOverflow)?;
     policy.updated_at = now;
 
+    let history = &mut ctx.accounts.policy_history;
+    history.initialize_if_needed(policy.vault, ctx.bumps.policy_history);
+    history.record(policy, ctx.accounts.new_owner.key(), PolicyActor::Claimant, now)?;
+
+    // Move the vault between the owners' indexes
+    move_owner_index_entry(
+        &ctx.accounts.previous_owner_index,
+        &previous_owner,
+        &ctx.accounts.new_owner_index,
+        &ctx.accounts.new_owner.key(),
+        &ctx.accounts.vault.key(),
+        &ctx.accounts.new_owner.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
     emit!(VaultClaimed {
+        version: EVENT_SCHEMA_VERSION,
         vault: ctx.accounts.vault.key(),
         previous_owner,
         new_owner: ctx.accounts.new_owner.key(),
         timestamp: now,
     });
 
-    msg!("Vault claimed by new owner: {}", ctx.accounts.new_owner.key());
-    msg!("Previous owner: {}", previous_owner);
+    log_sensitive!("Vault claimed by new owner: {}", ctx.accounts.new_owner.key());
+    log_sensitive!("Previous owner: {}", previous_owner);
 
     Ok(())
 }
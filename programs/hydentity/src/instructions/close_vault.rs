@@ -1,18 +1,23 @@
 use anchor_lang::prelude::*;
-use crate::constants::{VAULT_SEED, VAULT_AUTH_SEED, POLICY_SEED};
+use crate::constants::{VAULT_SEED, VAULT_AUTH_SEED, POLICY_SEED, OWNER_SET_SEED, WITHDRAWAL_CONFIG_SEED, RECOVERY_CONFIG_SEED, PROFILE_REGISTRY_SEED};
 use crate::errors::HydentityError;
-use crate::events::VaultClosed;
-use crate::state::{NameVault, VaultAuthority, PrivacyPolicy};
+use crate::events::{VaultClosed, EVENT_SCHEMA_VERSION};
+use crate::state::{
+    NameVault, VaultAuthority, PrivacyPolicy, VaultOwnerSet, DirectWithdrawalConfig, RecoveryConfig,
+    verify_owner_authority, remove_from_owner_index, enforce_outflow_limit, has_guardian_cosign,
+    verify_no_open_profiles,
+};
 
 /// Close a vault and reclaim rent
 ///
-/// The owner closes their vault. No balance checks - user is responsible
-/// for clearing funds first. Anchor's `close = owner` transfers all lamports
-/// (rent + any deposited SOL) from each PDA back to the owner.
+/// The owner (or an owner set quorum) closes their vault. Anchor's `close = owner`
+/// transfers all lamports (rent + any deposited SOL) from each PDA back to the
+/// owner, so deposited SOL is treated like a direct withdrawal: it must stay
+/// under the withdrawal timelock threshold and the SOL outflow limit.
 /// SPL token ATAs associated with the vault authority will become orphaned.
 #[derive(Accounts)]
 pub struct CloseVault<'info> {
-    /// The vault owner (must be signer, receives closed account lamports)
+    /// The vault owner or an owner set member (must be signer, receives closed account lamports)
     #[account(mut)]
     pub owner: Signer<'info>,
 
@@ -25,7 +30,6 @@ pub struct CloseVault<'info> {
         mut,
         seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
         bump = vault.bump,
-        constraint = vault.owner == owner.key() @ HydentityError::Unauthorized,
         constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName,
         constraint = !vault.domain_transferred @ HydentityError::DomainAlreadyTransferred,
         close = owner,
@@ -49,18 +53,132 @@ pub struct CloseVault<'info> {
         close = owner,
     )]
     pub policy: Account<'info, PrivacyPolicy>,
+
+    /// The vault owner's vault index (vault removed, if the index exists)
+    /// CHECK: Address and contents validated by remove_from_owner_index
+    #[account(mut)]
+    pub owner_index: UncheckedAccount<'info>,
+
+    /// Optional owner set to close along with the vault
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        mut,
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet,
+        close = owner,
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+
+    /// Direct withdrawal timelock config (required once configured)
+    #[account(
+        seeds = [WITHDRAWAL_CONFIG_SEED, sns_name_account.key().as_ref()],
+        bump = withdrawal_config.bump,
+        constraint = withdrawal_config.vault == vault.key() @ HydentityError::WithdrawalConfigRequired
+    )]
+    pub withdrawal_config: Option<Account<'info, DirectWithdrawalConfig>>,
+
+    /// SOL outflow limit PDA (may be uninitialized)
+    /// CHECK: Address and contents validated by enforce_outflow_limit
+    #[account(mut)]
+    pub outflow_limit: UncheckedAccount<'info>,
+
+    /// Optional recovery config, for a guardian co-sign above the outflow limit
+    #[account(
+        seeds = [RECOVERY_CONFIG_SEED, sns_name_account.key().as_ref()],
+        bump = recovery_config.bump,
+        constraint = recovery_config.vault == vault.key() @ HydentityError::InvalidRecoveryConfig
+    )]
+    pub recovery_config: Option<Account<'info, RecoveryConfig>>,
+
+    /// Optional guardian co-signing an outflow above the limit
+    pub guardian: Option<Signer<'info>>,
+
+    /// Profile registry PDA (may be uninitialized; must list no profiles)
+    /// CHECK: Address checked by seeds; contents validated by verify_no_open_profiles
+    #[account(
+        seeds = [PROFILE_REGISTRY_SEED, sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub profile_registry: UncheckedAccount<'info>,
 }
 
 pub fn handler(ctx: Context<CloseVault>) -> Result<()> {
     let clock = Clock::get()?;
 
+    // A sold domain freezes the previous owner until the buyer claims the vault
+    ctx.accounts.vault.verify_domain_control(&ctx.accounts.sns_name_account)?;
+
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    ctx.accounts.vault.touch_owner_activity(clock.unix_timestamp);
+
+    // A configured owner set must be closed with the vault, otherwise it
+    // would carry over to a vault re-created at the same address
+    require!(
+        !ctx.accounts.vault.has_owner_set || ctx.accounts.owner_set.is_some(),
+        HydentityError::OwnerSetRequired
+    );
+
+    // Profiles are authorized against this vault, so they go first
+    verify_no_open_profiles(&ctx.accounts.profile_registry)?;
+
+    // SOL left in the closed accounts goes to the owner, so it is an outflow
+    let rent = Rent::get()?;
+    let mut outflow = 0u64;
+    for info in [
+        ctx.accounts.vault.to_account_info(),
+        ctx.accounts.vault_authority.to_account_info(),
+        ctx.accounts.policy.to_account_info(),
+    ] {
+        outflow = outflow
+            .checked_add(info.lamports().saturating_sub(rent.minimum_balance(info.data_len())))
+            .ok_or(HydentityError::ArithmeticOverflow)?;
+    }
+
+    if outflow > 0 {
+        // Balances above the timelock threshold must be withdrawn through the queue
+        if ctx.accounts.vault.has_withdrawal_timelock {
+            let config = ctx.accounts.withdrawal_config
+                .as_deref()
+                .ok_or(HydentityError::WithdrawalConfigRequired)?;
+            require!(
+                !config.requires_queue(outflow, clock.unix_timestamp),
+                HydentityError::DirectWithdrawalRequiresQueue
+            );
+        }
+
+        enforce_outflow_limit(
+            &ctx.accounts.outflow_limit.to_account_info(),
+            &ctx.accounts.vault.key(),
+            ctx.accounts.vault.rekeyed_at,
+            &Pubkey::default(),
+            outflow,
+            clock.unix_timestamp,
+            has_guardian_cosign(ctx.accounts.recovery_config.as_deref(), ctx.accounts.guardian.as_deref()),
+        )?;
+    }
+
+    // Drop the vault from the owner's index
+    remove_from_owner_index(
+        &ctx.accounts.owner_index,
+        &ctx.accounts.vault.owner,
+        &ctx.accounts.vault.key(),
+    )?;
+
     emit!(VaultClosed {
+        version: EVENT_SCHEMA_VERSION,
         vault: ctx.accounts.vault.key(),
         owner: ctx.accounts.owner.key(),
         timestamp: clock.unix_timestamp,
     });
 
-    msg!("Vault closed by owner: {}", ctx.accounts.owner.key());
+    log_sensitive!("Vault closed by owner: {}", ctx.accounts.owner.key());
 
     Ok(())
 }
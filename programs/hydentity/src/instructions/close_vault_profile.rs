@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, VAULT_AUTH_SEED, POLICY_SEED, OWNER_SET_SEED, VAULT_PROFILE_SEED, PROFILE_REGISTRY_SEED};
+use crate::errors::HydentityError;
+use crate::events::{VaultProfileClosed, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, VaultAuthority, PrivacyPolicy, VaultOwnerSet, VaultProfile, ProfileRegistry, verify_owner_authority};
+
+/// Close a vault profile and reclaim rent
+///
+/// Like `close_vault`, there are no balance checks: SOL held by the
+/// profile's vault authority goes to the owner with the rent, and SPL
+/// token accounts must be emptied first. The id is not reused.
+#[derive(Accounts)]
+#[instruction(profile_id: u8)]
+pub struct CloseVaultProfile<'info> {
+    /// The vault owner or an owner set member (must be signer)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The domain's vault
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The domain's profile registry
+    #[account(
+        mut,
+        seeds = [PROFILE_REGISTRY_SEED, sns_name_account.key().as_ref()],
+        bump = registry.bump,
+        constraint = registry.vault == vault.key() @ HydentityError::InvalidVault
+    )]
+    pub registry: Box<Account<'info, ProfileRegistry>>,
+
+    /// The profile to close
+    #[account(
+        mut,
+        close = owner,
+        seeds = [VAULT_PROFILE_SEED, sns_name_account.key().as_ref(), &[profile_id]],
+        bump = profile.bump,
+        constraint = profile.vault == vault.key() @ HydentityError::ProfileNotFound
+    )]
+    pub profile: Account<'info, VaultProfile>,
+
+    /// The profile's vault authority
+    #[account(
+        mut,
+        close = owner,
+        seeds = [VAULT_AUTH_SEED, sns_name_account.key().as_ref(), &[profile_id]],
+        bump = profile_vault_authority.bump,
+        constraint = profile_vault_authority.vault == profile.key() @ HydentityError::InvalidPolicyConfig
+    )]
+    pub profile_vault_authority: Account<'info, VaultAuthority>,
+
+    /// The profile's privacy policy
+    #[account(
+        mut,
+        close = owner,
+        seeds = [POLICY_SEED, sns_name_account.key().as_ref(), &[profile_id]],
+        bump = profile_policy.bump,
+        constraint = profile_policy.vault == profile.key() @ HydentityError::InvalidPolicyConfig
+    )]
+    pub profile_policy: Account<'info, PrivacyPolicy>,
+
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+}
+
+pub fn handler(ctx: Context<CloseVaultProfile>, profile_id: u8) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // A sold domain freezes the previous owner until the buyer claims the vault
+    ctx.accounts.vault.verify_domain_control(&ctx.accounts.sns_name_account)?;
+
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    ctx.accounts.vault.touch_owner_activity(clock.unix_timestamp);
+
+    ctx.accounts.registry.remove(profile_id)?;
+
+    emit!(VaultProfileClosed {
+        version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.vault.key(),
+        profile: ctx.accounts.profile.key(),
+        profile_id,
+        timestamp: clock.unix_timestamp,
+    });
+
+    log_info!("Vault profile {} closed", profile_id);
+
+    Ok(())
+}
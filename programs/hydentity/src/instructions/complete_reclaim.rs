@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use crate::constants::{VAULT_SEED, VAULT_AUTH_SEED, PENDING_RECLAIM_SEED, SNS_NAME_PROGRAM_ID};
+use crate::errors::HydentityError;
+use crate::events::{DomainReclaimed, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, VaultAuthority, PendingReclaim, read_sns_owner, validate_reclaim_destination};
+use super::reclaim_domain::build_sns_transfer_instruction;
+
+/// Complete a pending domain reclaim after its delay
+///
+/// Permissionless: the destination was fixed when the owner initiated the
+/// reclaim, so anyone can crank it once unlocked. The destination is
+/// validated again in case the account changed in the meantime.
+#[derive(Accounts)]
+pub struct CompleteReclaim<'info> {
+    /// Any caller (pays transaction fees only)
+    pub caller: Signer<'info>,
+
+    /// The SNS name account to transfer
+    /// CHECK: Validated by the SNS Name Program during CPI
+    #[account(mut)]
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault account (updates the domain_transferred flag)
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.domain_transferred @ HydentityError::DomainNotTransferred,
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The vault authority PDA (current owner of the SNS domain)
+    #[account(
+        seeds = [VAULT_AUTH_SEED, sns_name_account.key().as_ref()],
+        bump = vault_authority.bump,
+    )]
+    pub vault_authority: Account<'info, VaultAuthority>,
+
+    /// The pending reclaim
+    #[account(
+        mut,
+        close = initiated_by,
+        seeds = [PENDING_RECLAIM_SEED, sns_name_account.key().as_ref()],
+        bump = pending_reclaim.bump,
+        constraint = pending_reclaim.vault == vault.key() @ HydentityError::InvalidVault
+    )]
+    pub pending_reclaim: Account<'info, PendingReclaim>,
+
+    /// The account that paid for the request (receives rent)
+    /// CHECK: Validated against pending_reclaim.initiated_by
+    #[account(
+        mut,
+        address = pending_reclaim.initiated_by @ HydentityError::InvalidVault
+    )]
+    pub initiated_by: UncheckedAccount<'info>,
+
+    /// The destination recorded in the request
+    /// CHECK: Validated against pending_reclaim.destination
+    #[account(
+        address = pending_reclaim.destination @ HydentityError::InvalidReclaimDestination
+    )]
+    pub destination: UncheckedAccount<'info>,
+
+    /// SNS Name Program
+    /// CHECK: Validated by constraint
+    #[account(
+        constraint = sns_name_program.key() == SNS_NAME_PROGRAM_ID @ HydentityError::InvalidSnsName
+    )]
+    pub sns_name_program: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<CompleteReclaim>) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    let pending = &ctx.accounts.pending_reclaim;
+
+    // A change of ownership voids reclaims initiated by the previous owner
+    pending.verify_completable(&ctx.accounts.vault.owner, now)?;
+
+    validate_reclaim_destination(
+        &ctx.accounts.destination,
+        &ctx.accounts.vault.key(),
+        &ctx.accounts.vault_authority.key(),
+        pending.allow_program_owned,
+    )?;
+
+    let sns_name_account = &ctx.accounts.sns_name_account;
+    let vault_authority = &ctx.accounts.vault_authority;
+    let destination = pending.destination;
+
+    require_keys_eq!(
+        read_sns_owner(sns_name_account)?,
+        vault_authority.key(),
+        HydentityError::DomainNotTransferred
+    );
+
+    let transfer_ix = build_sns_transfer_instruction(
+        sns_name_account.key(),
+        vault_authority.key(),
+        destination,
+    );
+
+    let sns_name_key = sns_name_account.key();
+    let bump = vault_authority.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        VAULT_AUTH_SEED,
+        sns_name_key.as_ref(),
+        &[bump],
+    ]];
+
+    invoke_signed(
+        &transfer_ix,
+        &[
+            sns_name_account.to_account_info(),
+            vault_authority.to_account_info(),
+        ],
+        signer_seeds,
+    ).map_err(|_| HydentityError::SnsTransferFailed)?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.set_domain_transferred(false);
+
+    emit!(DomainReclaimed {
+        version: EVENT_SCHEMA_VERSION,
+        vault: vault.key(),
+        sns_name: sns_name_key,
+        destination,
+        timestamp: now,
+    });
+
+    log_info!("Pending domain reclaim completed");
+    log_sensitive!("New owner: {}", destination);
+
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, VAULT_AUTH_SEED, POLICY_SEED, OWNER_SET_SEED, VAULT_PROFILE_SEED, PROFILE_REGISTRY_SEED};
+use crate::errors::HydentityError;
+use crate::events::{VaultProfileCreated, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, VaultAuthority, PrivacyPolicy, VaultOwnerSet, VaultProfile, ProfileRegistry, verify_owner_authority};
+
+/// Create a named receiving profile for a domain
+///
+/// `profile_id` must be the registry's `next_profile_id` (1 for the first
+/// profile). Creates the profile with its own vault authority and a
+/// default privacy policy, and lists it in the domain's profile registry.
+#[derive(Accounts)]
+#[instruction(label: String, profile_id: u8)]
+pub struct CreateVaultProfile<'info> {
+    /// The vault owner or an owner set member (must be signer, pays for the accounts)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The domain's vault
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The domain's profile registry (created with the first profile)
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = ProfileRegistry::LEN,
+        seeds = [PROFILE_REGISTRY_SEED, sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub registry: Box<Account<'info, ProfileRegistry>>,
+
+    /// The profile PDA to be created
+    #[account(
+        init,
+        payer = owner,
+        space = VaultProfile::LEN,
+        seeds = [VAULT_PROFILE_SEED, sns_name_account.key().as_ref(), &[profile_id]],
+        bump
+    )]
+    pub profile: Account<'info, VaultProfile>,
+
+    /// The profile's vault authority (receives the profile's deposits)
+    #[account(
+        init,
+        payer = owner,
+        space = VaultAuthority::LEN,
+        seeds = [VAULT_AUTH_SEED, sns_name_account.key().as_ref(), &[profile_id]],
+        bump
+    )]
+    pub profile_vault_authority: Account<'info, VaultAuthority>,
+
+    /// The profile's privacy policy
+    #[account(
+        init,
+        payer = owner,
+        space = PrivacyPolicy::LEN,
+        seeds = [POLICY_SEED, sns_name_account.key().as_ref(), &[profile_id]],
+        bump
+    )]
+    pub profile_policy: Box<Account<'info, PrivacyPolicy>>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+}
+
+pub fn handler(ctx: Context<CreateVaultProfile>, label: String, profile_id: u8) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    // A sold domain freezes the previous owner until the buyer claims the vault
+    ctx.accounts.vault.verify_domain_control(&ctx.accounts.sns_name_account)?;
+
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    ctx.accounts.vault.touch_owner_activity(now);
+
+    let vault_key = ctx.accounts.vault.key();
+    let sns_name = ctx.accounts.sns_name_account.key();
+
+    let registry = &mut ctx.accounts.registry;
+    registry.initialize_if_needed(vault_key, ctx.bumps.registry);
+    require!(registry.next_profile_id == profile_id, HydentityError::ProfileIdMismatch);
+    registry.add(&label)?;
+
+    let profile_key = ctx.accounts.profile.key();
+    let profile = &mut ctx.accounts.profile;
+    profile.vault = vault_key;
+    profile.sns_name = sns_name;
+    profile.profile_id = profile_id;
+    profile.label = label;
+    profile.created_at = now;
+    profile.bump = ctx.bumps.profile;
+
+    ctx.accounts.profile_vault_authority.initialize(
+        profile_key,
+        sns_name,
+        ctx.bumps.profile_vault_authority,
+    );
+
+    ctx.accounts.profile_policy.initialize(
+        profile_key,
+        sns_name,
+        ctx.accounts.vault.owner,
+        ctx.bumps.profile_policy,
+        now,
+    );
+
+    emit!(VaultProfileCreated {
+        version: EVENT_SCHEMA_VERSION,
+        vault: vault_key,
+        profile: profile_key,
+        profile_id,
+        vault_authority: ctx.accounts.profile_vault_authority.key(),
+        timestamp: now,
+    });
+
+    log_info!("Vault profile {} created", profile_id);
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, OWNER_SET_SEED, DOMAIN_LISTING_SEED};
+use crate::errors::HydentityError;
+use crate::events::{DomainDelisted, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, VaultOwnerSet, DomainListing, verify_owner_authority};
+
+/// Withdraw a domain listing
+///
+/// The domain stays with the vault authority. Rent goes back to whoever
+/// paid for the listing.
+#[derive(Accounts)]
+pub struct DelistDomain<'info> {
+    /// The vault owner or an owner set member (must be signer)
+    pub owner: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault holding the listed domain
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The listing to close
+    #[account(
+        mut,
+        close = listed_by,
+        seeds = [DOMAIN_LISTING_SEED, sns_name_account.key().as_ref()],
+        bump = listing.bump,
+        constraint = listing.vault == vault.key() @ HydentityError::InvalidVault
+    )]
+    pub listing: Account<'info, DomainListing>,
+
+    /// The account that paid for the listing (receives rent)
+    /// CHECK: Validated against listing.listed_by
+    #[account(
+        mut,
+        address = listing.listed_by @ HydentityError::InvalidVault
+    )]
+    pub listed_by: UncheckedAccount<'info>,
+
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+}
+
+pub fn handler(ctx: Context<DelistDomain>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    ctx.accounts.vault.touch_owner_activity(clock.unix_timestamp);
+
+    emit!(DomainDelisted {
+        version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.vault.key(),
+        sns_name: ctx.accounts.sns_name_account.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    log_info!("Domain listing withdrawn");
+
+    Ok(())
+}
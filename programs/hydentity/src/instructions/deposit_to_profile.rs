@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Token, TokenAccount};
+use crate::constants::{VAULT_AUTH_SEED, VAULT_PROFILE_SEED, PROFILE_REGISTRY_SEED};
+use crate::errors::HydentityError;
+use crate::events::{ProfileDeposit, EVENT_SCHEMA_VERSION};
+use crate::state::{VaultAuthority, VaultProfile, ProfileRegistry};
+
+/// Send funds to a domain's profile by label
+///
+/// Resolves the label through the domain's profile registry, so a sender
+/// who only knows "alice.sol" and "business" cannot pay the wrong profile.
+/// Sending SOL (or tokens to an account owned by) the profile's vault
+/// authority directly works too.
+#[derive(Accounts)]
+pub struct DepositToProfile<'info> {
+    /// The sender (must be signer)
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    /// The SNS name account of the recipient domain
+    /// CHECK: Only used for PDA derivation
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The domain's profile registry
+    #[account(
+        seeds = [PROFILE_REGISTRY_SEED, sns_name_account.key().as_ref()],
+        bump = registry.bump,
+    )]
+    pub registry: Box<Account<'info, ProfileRegistry>>,
+
+    /// The profile being paid
+    #[account(
+        seeds = [VAULT_PROFILE_SEED, sns_name_account.key().as_ref(), &[profile.profile_id]],
+        bump = profile.bump,
+        constraint = profile.vault == registry.vault @ HydentityError::ProfileNotFound
+    )]
+    pub profile: Account<'info, VaultProfile>,
+
+    /// The profile's vault authority (receives SOL)
+    #[account(
+        mut,
+        seeds = [VAULT_AUTH_SEED, sns_name_account.key().as_ref(), &[profile.profile_id]],
+        bump = profile_vault_authority.bump,
+        constraint = profile_vault_authority.vault == profile.key() @ HydentityError::InvalidPolicyConfig
+    )]
+    pub profile_vault_authority: Account<'info, VaultAuthority>,
+
+    /// Optional: Sender's token account for SPL deposits
+    #[account(
+        mut,
+        token::authority = sender
+    )]
+    pub sender_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Optional: Profile's token account for SPL deposits
+    #[account(
+        mut,
+        token::authority = profile_vault_authority
+    )]
+    pub profile_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Token program for SPL deposits
+    pub token_program: Program<'info, Token>,
+
+    /// System program for SOL deposits
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<DepositToProfile>, label: String, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(
+        ctx.accounts.registry.find(&label) == Some(ctx.accounts.profile.profile_id),
+        HydentityError::ProfileNotFound
+    );
+
+    let mint = match (&ctx.accounts.sender_token_account, &ctx.accounts.profile_token_account) {
+        (Some(sender_token), Some(profile_token)) => {
+            require_keys_eq!(sender_token.mint, profile_token.mint, HydentityError::InvalidMint);
+
+            let cpi_accounts = token::Transfer {
+                from: sender_token.to_account_info(),
+                to: profile_token.to_account_info(),
+                authority: ctx.accounts.sender.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+            token::transfer(cpi_ctx, amount)?;
+            sender_token.mint
+        }
+        (None, None) => {
+            let cpi_accounts = system_program::Transfer {
+                from: ctx.accounts.sender.to_account_info(),
+                to: ctx.accounts.profile_vault_authority.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+            system_program::transfer(cpi_ctx, amount)?;
+            Pubkey::default()
+        }
+        _ => return Err(HydentityError::InvalidMint.into()),
+    };
+
+    emit!(ProfileDeposit {
+        version: EVENT_SCHEMA_VERSION,
+        profile: ctx.accounts.profile.key(),
+        mint,
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    log_sensitive!("Deposited {} to profile {}", amount, ctx.accounts.profile.key());
+
+    Ok(())
+}
@@ -1,8 +1,12 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
-use crate::constants::{VAULT_SEED, VAULT_AUTH_SEED, POLICY_SEED, DELEGATE_SEED, PERMISSION_DEPOSIT_UMBRA, DUST_THRESHOLD_LAMPORTS};
+use crate::constants::{VAULT_SEED, VAULT_AUTH_SEED, POLICY_SEED, DELEGATE_SEED, OWNER_SET_SEED, RECOVERY_CONFIG_SEED, PERMISSION_DEPOSIT_UMBRA, DUST_THRESHOLD_LAMPORTS};
 use crate::errors::HydentityError;
-use crate::state::{NameVault, VaultAuthority, PrivacyPolicy, DelegateSession, PrivacyMode};
+use crate::events::{UmbraDeposited, EVENT_SCHEMA_VERSION};
+use crate::state::{
+    NameVault, VaultAuthority, PrivacyPolicy, DelegateSession, VaultOwnerSet, RecoveryConfig, PrivacyMode,
+    enforce_outflow_limit, has_guardian_cosign,
+};
 
 /// Deposit vault funds into Umbra mixer pool
 /// 
@@ -10,7 +14,7 @@ use crate::state::{NameVault, VaultAuthority, PrivacyPolicy, DelegateSession, Pr
 /// for private withdrawal. It can handle both SOL and SPL tokens.
 #[derive(Accounts)]
 pub struct DepositToUmbra<'info> {
-    /// The caller (owner or delegate)
+    /// The caller (owner, owner set member or delegate)
     #[account(mut)]
     pub authority: Signer<'info>,
     
@@ -44,6 +48,7 @@ pub struct DepositToUmbra<'info> {
     pub policy: Account<'info, PrivacyPolicy>,
     
     /// Optional delegate session
+    /// Child sessions must pass their parent sessions as remaining accounts
     #[account(
         seeds = [DELEGATE_SEED, sns_name_account.key().as_ref(), authority.key().as_ref()],
         bump = delegate_session.bump,
@@ -77,6 +82,31 @@ pub struct DepositToUmbra<'info> {
     
     /// System program for SOL transfers
     pub system_program: Program<'info, System>,
+    
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+    
+    /// Outflow limit PDA for the asset being moved (may be uninitialized)
+    /// CHECK: Address and contents validated by enforce_outflow_limit
+    #[account(mut)]
+    pub outflow_limit: UncheckedAccount<'info>,
+    
+    /// Optional recovery config, for a guardian co-sign above the outflow limit
+    #[account(
+        seeds = [RECOVERY_CONFIG_SEED, sns_name_account.key().as_ref()],
+        bump = recovery_config.bump,
+        constraint = recovery_config.vault == vault.key() @ HydentityError::InvalidRecoveryConfig
+    )]
+    pub recovery_config: Option<Account<'info, RecoveryConfig>>,
+    
+    /// Optional guardian co-signing an outflow above the limit
+    pub guardian: Option<Signer<'info>>,
 }
 
 pub fn handler(
@@ -87,6 +117,9 @@ pub fn handler(
     let clock = Clock::get()?;
     let now = clock.unix_timestamp;
     
+    // A sold domain freezes the previous owner until the buyer claims the vault
+    ctx.accounts.vault.verify_domain_control(&ctx.accounts.sns_name_account)?;
+    
     let authority = ctx.accounts.authority.key();
     let vault = &ctx.accounts.vault;
     let policy = &ctx.accounts.policy;
@@ -102,12 +135,23 @@ pub fn handler(
     }
     
     // Check authorization
-    if vault.is_owner(&authority) {
+    let owner_signed = if vault.is_owner(&authority) {
         // Owner has full access
+        true
+    } else if let Some(owner_set) = ctx.accounts.owner_set.as_deref().filter(|s| vault.has_owner_set && s.is_member(&authority)) {
+        // Owner set members act as owner once the threshold is met
+        owner_set.verify_quorum(&ctx.accounts.authority.to_account_info(), ctx.remaining_accounts)?;
+        true
     } else if let Some(delegate) = &ctx.accounts.delegate_session {
-        delegate.validate_permission(PERMISSION_DEPOSIT_UMBRA, now)?;
+        delegate.validate_permission(PERMISSION_DEPOSIT_UMBRA, now, vault, ctx.remaining_accounts)?;
+        false
     } else {
         return Err(HydentityError::Unauthorized.into());
+    };
+    
+    // Delegate actions do not count as owner activity
+    if owner_signed {
+        ctx.accounts.vault.touch_owner_activity(now);
     }
     
     // Validate amount
@@ -115,6 +159,18 @@ pub fn handler(
         return Err(HydentityError::AmountBelowDust.into());
     }
     
+    // Count against the rolling 24h outflow limit for this asset
+    let outflow_mint = ctx.accounts.vault_token_account.as_ref().map(|account| account.mint).unwrap_or_default();
+    enforce_outflow_limit(
+        &ctx.accounts.outflow_limit.to_account_info(),
+        &ctx.accounts.vault.key(),
+        ctx.accounts.vault.rekeyed_at,
+        &outflow_mint,
+        amount,
+        now,
+        has_guardian_cosign(ctx.accounts.recovery_config.as_deref(), ctx.accounts.guardian.as_deref()),
+    )?;
+    
     // Check if this is an SPL token transfer
     if let Some(vault_token_account) = &ctx.accounts.vault_token_account {
         // SPL token deposit
@@ -148,7 +204,7 @@ pub fn handler(
         );
         transfer(cpi_ctx, amount)?;
         
-        msg!("Deposited {} SPL tokens to Umbra", amount);
+        log_sensitive!("Deposited {} SPL tokens to Umbra", amount);
     } else {
         // SOL deposit
         let vault_lamports = ctx.accounts.vault.to_account_info().lamports();
@@ -168,13 +224,22 @@ pub fn handler(
         **vault_info.try_borrow_mut_lamports()? -= amount;
         **umbra_pool_info.try_borrow_mut_lamports()? += amount;
         
-        msg!("Deposited {} lamports to Umbra", amount);
+        log_sensitive!("Deposited {} lamports to Umbra", amount);
     }
     
     // TODO: Actual CPI to Umbra deposit instruction would go here
     // This requires the Umbra program ID and instruction format
     
-    msg!("Umbra deposit initiated for vault: {}", vault.key());
+    emit!(UmbraDeposited {
+        version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.vault.key(),
+        authority,
+        mint: outflow_mint,
+        amount,
+        timestamp: now,
+    });
+    
+    log_sensitive!("Umbra deposit initiated for vault: {}", ctx.accounts.vault.key());
     
     Ok(())
 }
@@ -0,0 +1,193 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
+use crate::constants::{
+    VAULT_SEED, VAULT_AUTH_SEED, POLICY_SEED, DIRECT_WITHDRAWAL_SEED, VAULT_PROFILE_SEED, MIN_OUTFLOW_OVERRIDE_DELAY_SECONDS,
+};
+use crate::errors::HydentityError;
+use crate::events::{DirectWithdrawalExecuted, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, VaultAuthority, PrivacyPolicy, PendingDirectWithdrawal, VaultProfile, enforce_outflow_limit};
+
+/// Execute a queued profile withdrawal after its unlock time
+///
+/// Permissionless, like `execute_direct_withdrawal`. Funds come from the
+/// profile's vault authority and count against the domain's outflow limit.
+#[derive(Accounts)]
+#[instruction(profile_id: u8)]
+pub struct ExecuteProfileWithdrawal<'info> {
+    /// Any caller (pays transaction fees only)
+    pub caller: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The domain's vault
+    #[account(
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The profile holding the funds
+    #[account(
+        seeds = [VAULT_PROFILE_SEED, sns_name_account.key().as_ref(), &[profile_id]],
+        bump = profile.bump,
+        constraint = profile.vault == vault.key() @ HydentityError::ProfileNotFound
+    )]
+    pub profile: Account<'info, VaultProfile>,
+
+    /// The profile's vault authority (holds SOL deposits, signs SPL transfers)
+    #[account(
+        mut,
+        seeds = [VAULT_AUTH_SEED, sns_name_account.key().as_ref(), &[profile_id]],
+        bump = profile_vault_authority.bump,
+        constraint = profile_vault_authority.vault == profile.key() @ HydentityError::InvalidPolicyConfig
+    )]
+    pub profile_vault_authority: Account<'info, VaultAuthority>,
+
+    /// The profile's privacy policy (destination allowlist)
+    #[account(
+        seeds = [POLICY_SEED, sns_name_account.key().as_ref(), &[profile_id]],
+        bump = profile_policy.bump,
+        constraint = profile_policy.vault == profile.key() @ HydentityError::InvalidPolicyConfig
+    )]
+    pub profile_policy: Account<'info, PrivacyPolicy>,
+
+    /// The queued withdrawal
+    #[account(
+        mut,
+        close = requested_by,
+        seeds = [
+            DIRECT_WITHDRAWAL_SEED,
+            profile.key().as_ref(),
+            &pending_withdrawal.withdrawal_id.to_le_bytes(),
+        ],
+        bump = pending_withdrawal.bump,
+        constraint = pending_withdrawal.vault == profile.key() @ HydentityError::InvalidVault
+    )]
+    pub pending_withdrawal: Account<'info, PendingDirectWithdrawal>,
+
+    /// The account that paid for the request (receives rent)
+    /// CHECK: Validated against pending_withdrawal.requested_by
+    #[account(
+        mut,
+        address = pending_withdrawal.requested_by @ HydentityError::InvalidVault
+    )]
+    pub requested_by: UncheckedAccount<'info>,
+
+    /// The recipient recorded in the request
+    /// CHECK: Validated against pending_withdrawal.destination
+    #[account(
+        mut,
+        address = pending_withdrawal.destination @ HydentityError::InvalidVault
+    )]
+    pub destination: UncheckedAccount<'info>,
+
+    /// Optional: Profile's token account for SPL transfers
+    #[account(
+        mut,
+        token::authority = profile_vault_authority,
+        constraint = profile_token_account.mint == pending_withdrawal.mint @ HydentityError::InvalidMint
+    )]
+    pub profile_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Token program for SPL transfers
+    pub token_program: Program<'info, Token>,
+
+    /// The domain's outflow limit PDA for the asset being moved (may be uninitialized)
+    /// CHECK: Address and contents validated by enforce_outflow_limit
+    #[account(mut)]
+    pub outflow_limit: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<ExecuteProfileWithdrawal>, profile_id: u8) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    // A sold domain freezes the previous owner until the buyer claims the vault
+    ctx.accounts.vault.verify_domain_control(&ctx.accounts.sns_name_account)?;
+
+    let pending = &ctx.accounts.pending_withdrawal;
+
+    // A change of ownership voids requests queued by the previous owner
+    require_keys_eq!(ctx.accounts.vault.owner, pending.owner, HydentityError::WithdrawalStale);
+    require!(now >= pending.unlock_at, HydentityError::WithdrawalLocked);
+
+    // The allowlist may have changed since the request was queued
+    let recipient = pending.recipient(&ctx.accounts.destination.to_account_info())?;
+    ctx.accounts.profile_policy.verify_direct_destination(&recipient, now)?;
+
+    let amount = pending.amount;
+
+    // A long enough queue delay is itself the second factor for exceeding the outflow limit
+    let timelocked = pending.unlock_at.saturating_sub(pending.requested_at) >= MIN_OUTFLOW_OVERRIDE_DELAY_SECONDS;
+    enforce_outflow_limit(
+        &ctx.accounts.outflow_limit.to_account_info(),
+        &ctx.accounts.vault.key(),
+        ctx.accounts.vault.rekeyed_at,
+        &pending.mint,
+        amount,
+        now,
+        timelocked,
+    )?;
+
+    if pending.is_sol() {
+        // SOL is held by the profile's vault authority
+        let vault_auth_info = ctx.accounts.profile_vault_authority.to_account_info();
+        let rent = Rent::get()?.minimum_balance(VaultAuthority::LEN);
+        let available = vault_auth_info.lamports().saturating_sub(rent);
+
+        if available < amount {
+            return Err(HydentityError::InsufficientBalance.into());
+        }
+
+        let destination_info = ctx.accounts.destination.to_account_info();
+
+        **vault_auth_info.try_borrow_mut_lamports()? -= amount;
+        **destination_info.try_borrow_mut_lamports()? += amount;
+    } else {
+        let profile_token_account = ctx.accounts.profile_token_account
+            .as_ref()
+            .ok_or(HydentityError::InvalidMint)?;
+        if profile_token_account.amount < amount {
+            return Err(HydentityError::InsufficientBalance.into());
+        }
+
+        let sns_name_key = ctx.accounts.sns_name_account.key();
+        let vault_auth_bump = ctx.accounts.profile_vault_authority.bump;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            VAULT_AUTH_SEED,
+            sns_name_key.as_ref(),
+            &[profile_id],
+            &[vault_auth_bump],
+        ]];
+
+        let cpi_accounts = Transfer {
+            from: profile_token_account.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: ctx.accounts.profile_vault_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        transfer(cpi_ctx, amount)?;
+    }
+
+    emit!(DirectWithdrawalExecuted {
+        version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.profile.key(),
+        withdrawal_id: pending.withdrawal_id,
+        mint: pending.mint,
+        destination: pending.destination,
+        amount,
+        timestamp: now,
+    });
+
+    log_sensitive!("Profile {} withdrawal #{} executed: {} to {}", profile_id, pending.withdrawal_id, amount, pending.destination);
+    log_info!("WARNING: This withdrawal bypasses privacy protections");
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::constants::{VAULT_SEED, RECOVERY_CONFIG_SEED, RECOVERY_REQUEST_SEED};
+use crate::errors::HydentityError;
+use crate::events::{RecoveryExecuted, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, RecoveryConfig, RecoveryRequest, move_owner_index_entry, is_domain_holder_signer};
+
+/// Execute a guardian-approved recovery after its timelock
+///
+/// Permissionless: anyone can crank an approved request once the veto
+/// window has passed. Rotates `NameVault.owner` to the proposed key,
+/// which then holds every owner privilege, including `reclaim_domain`
+/// when the domain sits with the vault authority. Policy and encrypted
+/// config are left untouched.
+#[derive(Accounts)]
+pub struct ExecuteRecovery<'info> {
+    /// Any caller (pays for growing the new owner's index)
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault being recovered
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The vault's recovery config
+    #[account(
+        seeds = [RECOVERY_CONFIG_SEED, sns_name_account.key().as_ref()],
+        bump = recovery_config.bump,
+        constraint = recovery_config.vault == vault.key() @ HydentityError::InvalidRecoveryConfig
+    )]
+    pub recovery_config: Account<'info, RecoveryConfig>,
+
+    /// The approved recovery request
+    #[account(
+        mut,
+        close = initiator,
+        seeds = [RECOVERY_REQUEST_SEED, sns_name_account.key().as_ref()],
+        bump = recovery_request.bump,
+        constraint = recovery_request.vault == vault.key() @ HydentityError::InvalidVault
+    )]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+
+    /// The guardian who opened the request (receives rent)
+    /// CHECK: Validated against recovery_request.initiator
+    #[account(
+        mut,
+        address = recovery_request.initiator @ HydentityError::InvalidVault
+    )]
+    pub initiator: UncheckedAccount<'info>,
+
+    /// The previous owner's vault index (vault removed, if the index exists)
+    /// CHECK: Address and contents validated by remove_from_owner_index
+    #[account(mut)]
+    pub previous_owner_index: UncheckedAccount<'info>,
+
+    /// The new owner's vault index (created or grown as needed)
+    /// CHECK: Address and contents validated by add_to_owner_index
+    #[account(mut)]
+    pub new_owner_index: UncheckedAccount<'info>,
+
+    /// System program (for creating or growing the new owner's index)
+    pub system_program: Program<'info, System>,
+
+    /// Optional: the domain's current holder, co-signing a rotation that
+    /// leaves the domain with the previous key
+    pub domain_holder: Option<Signer<'info>>,
+
+    /// Token account holding the domain's NFT (tokenized domains only)
+    pub nft_token_account: Option<Account<'info, TokenAccount>>,
+}
+
+pub fn handler(ctx: Context<ExecuteRecovery>) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    let request = &ctx.accounts.recovery_request;
+    let previous_owner = ctx.accounts.vault.owner;
+    request.verify_executable(&ctx.accounts.recovery_config, &previous_owner, now)?;
+
+    let new_owner = request.proposed_owner;
+    let domain_holder_signed = is_domain_holder_signer(
+        &ctx.accounts.sns_name_account,
+        ctx.accounts.nft_token_account.as_deref(),
+        ctx.accounts.domain_holder.as_ref().map(|holder| holder.as_ref()),
+    )?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.rotate_owner(new_owner, domain_holder_signed);
+    vault.touch_owner_activity(now);
+
+    // Move the vault between the owners' indexes
+    move_owner_index_entry(
+        &ctx.accounts.previous_owner_index,
+        &previous_owner,
+        &ctx.accounts.new_owner_index,
+        &new_owner,
+        &vault.key(),
+        &ctx.accounts.caller.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    emit!(RecoveryExecuted {
+        version: EVENT_SCHEMA_VERSION,
+        vault: vault.key(),
+        previous_owner,
+        new_owner,
+        timestamp: now,
+    });
+
+    log_sensitive!("Vault recovered to new owner: {}", new_owner);
+    log_sensitive!("Previous owner: {}", previous_owner);
+
+    Ok(())
+}
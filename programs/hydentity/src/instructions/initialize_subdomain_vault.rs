@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::constants::{VAULT_SEED, VAULT_AUTH_SEED, POLICY_SEED, SUBDOMAIN_LINK_SEED, SNS_NAME_PROGRAM_ID};
+use crate::state::{
+    NameVault, VaultAuthority, PrivacyPolicy, SubdomainLink,
+    read_sns_parent, is_subdomain_parent, verify_subdomain_claim, add_to_owner_index,
+};
+use crate::errors::HydentityError;
+use crate::events::{VaultInitialized, SubdomainVaultInitialized, EVENT_SCHEMA_VERSION};
+
+/// Initialize a vault for an SNS subdomain (e.g. `pay.alice.sol`)
+///
+/// Creates the same accounts as `initialize_vault` plus a `SubdomainLink`
+/// to the parent domain. The signer must own the subdomain, or own the
+/// parent domain's vault with `parent_controls_subdomains` enabled (pass
+/// `parent_vault` in that case).
+#[derive(Accounts)]
+pub struct InitializeSubdomainVault<'info> {
+    /// The subdomain owner, or the parent domain owner (must be signer)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The subdomain's SNS name account
+    /// CHECK: Owned by the SNS Name Program; parent and owner fields read in handler
+    #[account(
+        constraint = sns_name_account.owner == &SNS_NAME_PROGRAM_ID @ HydentityError::InvalidSnsName
+    )]
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The parent domain's SNS name account
+    /// CHECK: Must match the subdomain's parent_name, checked in handler
+    #[account(
+        constraint = parent_name_account.owner == &SNS_NAME_PROGRAM_ID @ HydentityError::InvalidSnsName
+    )]
+    pub parent_name_account: UncheckedAccount<'info>,
+
+    /// The parent domain's vault (required when acting as the parent owner)
+    pub parent_vault: Option<Account<'info, NameVault>>,
+
+    /// Token account holding the subdomain's NFT (tokenized subdomains only)
+    pub nft_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// The vault PDA to be created
+    #[account(
+        init,
+        payer = owner,
+        space = NameVault::LEN,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The vault authority PDA to be created
+    #[account(
+        init,
+        payer = owner,
+        space = VaultAuthority::LEN,
+        seeds = [VAULT_AUTH_SEED, sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: Account<'info, VaultAuthority>,
+
+    /// The privacy policy PDA to be created
+    #[account(
+        init,
+        payer = owner,
+        space = PrivacyPolicy::LEN,
+        seeds = [POLICY_SEED, sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub policy: Account<'info, PrivacyPolicy>,
+
+    /// Link from the parent domain to this vault
+    #[account(
+        init,
+        payer = owner,
+        space = SubdomainLink::LEN,
+        seeds = [SUBDOMAIN_LINK_SEED, parent_name_account.key().as_ref(), sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub subdomain_link: Account<'info, SubdomainLink>,
+
+    /// The owner's vault index (created or grown as needed)
+    /// CHECK: Address and contents validated by add_to_owner_index
+    #[account(mut)]
+    pub owner_index: UncheckedAccount<'info>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeSubdomainVault>) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    let owner = ctx.accounts.owner.key();
+    let sns_name = ctx.accounts.sns_name_account.key();
+    let parent_name = ctx.accounts.parent_name_account.key();
+
+    let actual_parent = read_sns_parent(&ctx.accounts.sns_name_account)?;
+    require!(is_subdomain_parent(&actual_parent), HydentityError::NotASubdomain);
+    require_keys_eq!(parent_name, actual_parent, HydentityError::InvalidParentDomain);
+
+    let managed_by_parent = verify_subdomain_claim(
+        &ctx.accounts.sns_name_account,
+        ctx.accounts.nft_token_account.as_deref(),
+        Some(&ctx.accounts.parent_name_account),
+        ctx.accounts.parent_vault.as_ref(),
+        &owner,
+    )?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.initialize(owner, sns_name, ctx.bumps.vault, now);
+    vault.managed_by_parent = managed_by_parent;
+
+    let vault_authority = &mut ctx.accounts.vault_authority;
+    vault_authority.initialize(vault.key(), sns_name, ctx.bumps.vault_authority);
+
+    let policy = &mut ctx.accounts.policy;
+    policy.initialize(vault.key(), sns_name, owner, ctx.bumps.policy, now);
+
+    let link = &mut ctx.accounts.subdomain_link;
+    link.parent_name = parent_name;
+    link.subdomain_name = sns_name;
+    link.vault = vault.key();
+    link.created_at = now;
+    link.bump = ctx.bumps.subdomain_link;
+
+    // List the vault in the owner's index
+    add_to_owner_index(
+        &ctx.accounts.owner_index,
+        &owner,
+        &vault.key(),
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    emit!(VaultInitialized {
+        version: EVENT_SCHEMA_VERSION,
+        vault: vault.key(),
+        owner,
+        sns_name,
+        timestamp: now,
+    });
+
+    emit!(SubdomainVaultInitialized {
+        version: EVENT_SCHEMA_VERSION,
+        vault: vault.key(),
+        owner,
+        parent_name,
+        subdomain_name: sns_name,
+        managed_by_parent,
+        timestamp: now,
+    });
+
+    log_info!("Subdomain vault initialized (managed by parent: {})", managed_by_parent);
+
+    Ok(())
+}
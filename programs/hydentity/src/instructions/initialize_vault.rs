@@ -1,7 +1,12 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
 use crate::constants::{VAULT_SEED, VAULT_AUTH_SEED, POLICY_SEED, SNS_NAME_PROGRAM_ID};
-use crate::state::{NameVault, VaultAuthority, PrivacyPolicy};
+use crate::state::{
+    NameVault, VaultAuthority, PrivacyPolicy,
+    read_sns_parent, is_subdomain_parent, resolve_domain_holder, add_to_owner_index,
+};
 use crate::errors::HydentityError;
+use crate::events::{VaultInitialized, EVENT_SCHEMA_VERSION};
 
 /// Initialize a new vault for an SNS name
 /// 
@@ -13,7 +18,8 @@ use crate::errors::HydentityError;
 /// The instruction verifies SNS ownership by:
 /// 1. Checking the sns_name_account is owned by the SNS Name Program
 /// 2. Parsing the owner from the SNS account data structure
-/// 3. Verifying the signer matches the SNS domain owner
+/// 3. Verifying the signer matches the SNS domain owner, or holds the
+///    domain's NFT in `nft_token_account` if the domain is tokenized
 #[derive(Accounts)]
 pub struct InitializeVault<'info> {
     /// The owner of the SNS name (must be signer)
@@ -59,39 +65,42 @@ pub struct InitializeVault<'info> {
     )]
     pub policy: Account<'info, PrivacyPolicy>,
     
+    /// The owner's vault index (created or grown as needed)
+    /// CHECK: Address and contents validated by add_to_owner_index
+    #[account(mut)]
+    pub owner_index: UncheckedAccount<'info>,
+    
     /// System program for account creation
     pub system_program: Program<'info, System>,
+    
+    /// Token account holding the domain's NFT (tokenized domains only)
+    pub nft_token_account: Option<Account<'info, TokenAccount>>,
 }
 
-/// Verify that the signer owns the SNS name account
+/// Verify that the signer holds the SNS name account
 /// 
 /// SNS Name Account Data Layout:
 /// - bytes 0-32: parent_name (Pubkey)
 /// - bytes 32-64: owner (Pubkey) <- This is what we verify
 /// - bytes 64-96: class (Pubkey)
 /// - bytes 96+: data (variable)
-fn verify_sns_ownership(sns_account: &AccountInfo, expected_owner: &Pubkey) -> Result<()> {
-    let data = sns_account.try_borrow_data()?;
-    
-    // Ensure account has enough data for the owner field
-    if data.len() < 64 {
-        return Err(HydentityError::InvalidSnsName.into());
-    }
-    
-    // Extract the owner pubkey from offset 32
-    let owner_bytes: [u8; 32] = data[32..64]
-        .try_into()
-        .map_err(|_| HydentityError::InvalidSnsName)?;
-    
-    let sns_owner = Pubkey::new_from_array(owner_bytes);
-    
-    // Verify the signer is the SNS domain owner
+/// 
+/// A tokenized domain is owned by the tokenizer, so its holder is the
+/// owner of the token account holding the domain's NFT instead.
+fn verify_sns_ownership(
+    sns_account: &AccountInfo,
+    nft_token_account: Option<&TokenAccount>,
+    expected_owner: &Pubkey,
+) -> Result<()> {
+    let domain_holder = resolve_domain_holder(sns_account, nft_token_account)?;
+    
+    // Verify the signer is the SNS domain holder
     require!(
-        sns_owner == *expected_owner,
+        domain_holder == *expected_owner,
         HydentityError::SnsOwnershipVerificationFailed
     );
     
-    msg!("SNS ownership verified for domain owner: {}", sns_owner);
+    log_sensitive!("SNS ownership verified for domain owner: {}", domain_holder);
     Ok(())
 }
 
@@ -105,9 +114,16 @@ pub fn handler(ctx: Context<InitializeVault>) -> Result<()> {
     // Verify SNS ownership - the signer must own the SNS domain
     verify_sns_ownership(
         &ctx.accounts.sns_name_account.to_account_info(),
+        ctx.accounts.nft_token_account.as_deref(),
         &owner
     )?;
     
+    // Subdomains go through initialize_subdomain_vault so they get linked to their parent
+    require!(
+        !is_subdomain_parent(&read_sns_parent(&ctx.accounts.sns_name_account)?),
+        HydentityError::SubdomainRequiresParent
+    );
+    
     // Initialize the vault
     let vault = &mut ctx.accounts.vault;
     vault.initialize(
@@ -135,9 +151,26 @@ pub fn handler(ctx: Context<InitializeVault>) -> Result<()> {
         now,
     );
     
-    msg!("Vault initialized for SNS name: {}", sns_name);
-    msg!("Vault address: {}", vault.key());
-    msg!("Vault authority: {}", vault_authority.key());
+    // List the vault in the owner's index
+    add_to_owner_index(
+        &ctx.accounts.owner_index.to_account_info(),
+        &owner,
+        &vault.key(),
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    
+    emit!(VaultInitialized {
+        version: EVENT_SCHEMA_VERSION,
+        vault: vault.key(),
+        owner,
+        sns_name,
+        timestamp: now,
+    });
+    
+    log_sensitive!("Vault initialized for SNS name: {}", sns_name);
+    log_sensitive!("Vault address: {}", vault.key());
+    log_sensitive!("Vault authority: {}", vault_authority.key());
     
     Ok(())
 }
@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, VAULT_AUTH_SEED, OWNER_SET_SEED, RECLAIM_CONFIG_SEED, PENDING_RECLAIM_SEED};
+use crate::errors::HydentityError;
+use crate::events::{ReclaimInitiated, EVENT_SCHEMA_VERSION};
+use crate::state::{
+    NameVault, VaultAuthority, VaultOwnerSet, ReclaimConfig, PendingReclaim,
+    verify_owner_authority, validate_reclaim_destination,
+};
+
+/// Start a timelocked domain reclaim
+///
+/// First step of the two-step alternative to `reclaim_domain`. The
+/// destination is validated and fixed now; `complete_reclaim` transfers
+/// the domain once the configured reclaim delay has passed. Only one
+/// reclaim can be pending.
+#[derive(Accounts)]
+pub struct InitiateReclaim<'info> {
+    /// The vault owner or an owner set member (must be signer, pays for the request)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault holding the domain
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName,
+        constraint = vault.domain_transferred @ HydentityError::DomainNotTransferred,
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The vault authority PDA (current owner of the SNS domain)
+    #[account(
+        seeds = [VAULT_AUTH_SEED, sns_name_account.key().as_ref()],
+        bump = vault_authority.bump,
+    )]
+    pub vault_authority: Account<'info, VaultAuthority>,
+
+    /// The destination address for the domain
+    /// CHECK: Validated by validate_reclaim_destination
+    pub destination: UncheckedAccount<'info>,
+
+    /// The pending reclaim PDA to be created
+    #[account(
+        init,
+        payer = owner,
+        space = PendingReclaim::LEN,
+        seeds = [PENDING_RECLAIM_SEED, sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub pending_reclaim: Account<'info, PendingReclaim>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+
+    /// Reclaim timelock config (required once configured)
+    #[account(
+        seeds = [RECLAIM_CONFIG_SEED, sns_name_account.key().as_ref()],
+        bump = reclaim_config.bump,
+        constraint = reclaim_config.vault == vault.key() @ HydentityError::ReclaimConfigRequired
+    )]
+    pub reclaim_config: Option<Account<'info, ReclaimConfig>>,
+}
+
+pub fn handler(ctx: Context<InitiateReclaim>, allow_program_owned: bool) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    ctx.accounts.vault.touch_owner_activity(now);
+
+    validate_reclaim_destination(
+        &ctx.accounts.destination,
+        &ctx.accounts.vault.key(),
+        &ctx.accounts.vault_authority.key(),
+        allow_program_owned,
+    )?;
+
+    let delay_seconds = if ctx.accounts.vault.has_reclaim_timelock {
+        ctx.accounts.reclaim_config
+            .as_deref()
+            .ok_or(HydentityError::ReclaimConfigRequired)?
+            .active_delay(now)
+    } else {
+        0
+    };
+    let executable_at = now
+        .checked_add(delay_seconds)
+        .ok_or(HydentityError::ArithmeticOverflow)?;
+
+    let vault = &ctx.accounts.vault;
+    let destination = ctx.accounts.destination.key();
+    let pending = &mut ctx.accounts.pending_reclaim;
+    pending.vault = vault.key();
+    pending.owner = vault.owner;
+    pending.destination = destination;
+    pending.allow_program_owned = allow_program_owned;
+    pending.initiated_by = ctx.accounts.owner.key();
+    pending.initiated_at = now;
+    pending.executable_at = executable_at;
+    pending.bump = ctx.bumps.pending_reclaim;
+
+    emit!(ReclaimInitiated {
+        version: EVENT_SCHEMA_VERSION,
+        vault: vault.key(),
+        destination,
+        executable_at,
+        timestamp: now,
+    });
+
+    log_info!("Domain reclaim initiated, completable at: {}", executable_at);
+    log_sensitive!("Reclaim destination: {}", destination);
+
+    Ok(())
+}
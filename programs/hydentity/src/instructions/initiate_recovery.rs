@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::constants::{VAULT_SEED, RECOVERY_CONFIG_SEED, RECOVERY_REQUEST_SEED};
+use crate::errors::HydentityError;
+use crate::events::{RecoveryInitiated, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, RecoveryConfig, RecoveryRequest};
+
+/// Open a recovery request proposing a new owner key
+///
+/// Any registered guardian can open the request; it counts as that
+/// guardian's approval. Only one request can be pending per vault. A
+/// stale request (the owner changed since it was opened) is replaced, and
+/// the new guardian refunds its rent to the guardian who opened it.
+#[derive(Accounts)]
+pub struct InitiateRecovery<'info> {
+    /// A registered guardian (must be signer, pays for the request)
+    #[account(mut)]
+    pub guardian: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault being recovered
+    #[account(
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The vault's recovery config
+    #[account(
+        seeds = [RECOVERY_CONFIG_SEED, sns_name_account.key().as_ref()],
+        bump = recovery_config.bump,
+        constraint = recovery_config.vault == vault.key() @ HydentityError::InvalidRecoveryConfig
+    )]
+    pub recovery_config: Account<'info, RecoveryConfig>,
+
+    /// The recovery request PDA (created, or replaced if stale)
+    #[account(
+        init_if_needed,
+        payer = guardian,
+        space = RecoveryRequest::LEN,
+        seeds = [RECOVERY_REQUEST_SEED, sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub recovery_request: Account<'info, RecoveryRequest>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+
+    /// Guardian who opened the stale request being replaced (receives its rent)
+    /// Only required when replacing another guardian's request
+    /// CHECK: Validated against recovery_request.initiator
+    #[account(mut)]
+    pub previous_initiator: Option<UncheckedAccount<'info>>,
+}
+
+pub fn handler(ctx: Context<InitiateRecovery>, new_owner: Pubkey) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    let vault = &ctx.accounts.vault;
+    let guardian = ctx.accounts.guardian.key();
+
+    require!(new_owner != Pubkey::default(), HydentityError::InvalidRecoveryConfig);
+    require!(!vault.is_owner(&new_owner), HydentityError::VaultOwnerUnchanged);
+
+    // Replace a request left stale by an owner change, refunding its opener
+    let existing = &ctx.accounts.recovery_request;
+    if existing.vault != Pubkey::default() {
+        require!(
+            existing.is_stale(&vault.key(), &vault.owner),
+            HydentityError::RecoveryPending
+        );
+
+        if existing.initiator != guardian {
+            let previous_initiator = ctx.accounts.previous_initiator
+                .as_ref()
+                .ok_or(HydentityError::InvalidRecoveryConfig)?;
+            require_keys_eq!(previous_initiator.key(), existing.initiator, HydentityError::InvalidRecoveryConfig);
+
+            let cpi_accounts = system_program::Transfer {
+                from: ctx.accounts.guardian.to_account_info(),
+                to: previous_initiator.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+            system_program::transfer(cpi_ctx, existing.to_account_info().lamports())?;
+        }
+    }
+
+    let request = &mut ctx.accounts.recovery_request;
+    request.vault = vault.key();
+    request.previous_owner = vault.owner;
+    request.proposed_owner = new_owner;
+    request.initiator = guardian;
+    request.approvals = Vec::new();
+    request.initiated_at = now;
+    request.executable_at = 0;
+    request.bump = ctx.bumps.recovery_request;
+
+    request.approve(guardian, &ctx.accounts.recovery_config, now)?;
+
+    emit!(RecoveryInitiated {
+        version: EVENT_SCHEMA_VERSION,
+        vault: vault.key(),
+        guardian,
+        proposed_owner: new_owner,
+        timestamp: now,
+    });
+
+    log_sensitive!("Recovery initiated for vault: {}", vault.key());
+    log_sensitive!("Proposed owner: {}", new_owner);
+
+    Ok(())
+}
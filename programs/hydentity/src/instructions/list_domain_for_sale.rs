@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, VAULT_AUTH_SEED, OWNER_SET_SEED, DOMAIN_LISTING_SEED, SNS_NAME_PROGRAM_ID};
+use crate::errors::HydentityError;
+use crate::events::{DomainListed, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, VaultAuthority, VaultOwnerSet, DomainListing, read_sns_owner, verify_owner_authority};
+
+/// List a vault-held domain for sale
+///
+/// The vault authority must own the domain, which keeps it in escrow
+/// until `buy_domain` settles the sale or the owner calls `delist_domain`.
+/// Only one listing per domain can be open.
+#[derive(Accounts)]
+pub struct ListDomainForSale<'info> {
+    /// The vault owner or an owner set member (must be signer, pays for the listing)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The SNS name account being sold
+    /// CHECK: Must be owned by the SNS Name Program; owner field read manually
+    #[account(
+        constraint = sns_name_account.owner == &SNS_NAME_PROGRAM_ID @ HydentityError::InvalidSnsName
+    )]
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault holding the domain
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName,
+        constraint = vault.domain_transferred @ HydentityError::DomainNotTransferred,
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The vault authority PDA (current SNS owner of the domain)
+    #[account(
+        seeds = [VAULT_AUTH_SEED, sns_name_account.key().as_ref()],
+        bump = vault_authority.bump,
+    )]
+    pub vault_authority: Account<'info, VaultAuthority>,
+
+    /// The listing PDA to be created
+    #[account(
+        init,
+        payer = owner,
+        space = DomainListing::LEN,
+        seeds = [DOMAIN_LISTING_SEED, sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub listing: Account<'info, DomainListing>,
+
+    /// Where the sale proceeds go (a wallet for SOL, a token account of the mint otherwise)
+    /// CHECK: Validated by DomainListing::validate_proceeds_destination
+    pub proceeds_destination: UncheckedAccount<'info>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+}
+
+pub fn handler(
+    ctx: Context<ListDomainForSale>,
+    price: u64,
+    mint: Option<Pubkey>,
+    buyer: Option<Pubkey>,
+    release_domain: bool,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    // A sold domain freezes the previous owner until the buyer claims the vault
+    ctx.accounts.vault.verify_domain_control(&ctx.accounts.sns_name_account)?;
+
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    ctx.accounts.vault.touch_owner_activity(now);
+
+    require!(price > 0, HydentityError::InvalidListingPrice);
+
+    // The domain must actually sit with the vault authority to be in escrow
+    require_keys_eq!(
+        read_sns_owner(&ctx.accounts.sns_name_account)?,
+        ctx.accounts.vault_authority.key(),
+        HydentityError::DomainNotTransferred
+    );
+
+    let vault = &ctx.accounts.vault;
+    let listing = &mut ctx.accounts.listing;
+    listing.vault = vault.key();
+    listing.seller = vault.owner;
+    listing.listed_by = ctx.accounts.owner.key();
+    listing.proceeds_destination = ctx.accounts.proceeds_destination.key();
+    listing.price = price;
+    listing.mint = mint.unwrap_or_default();
+    listing.buyer = buyer.unwrap_or_default();
+    listing.release_domain = release_domain;
+    listing.listed_at = now;
+    listing.bump = ctx.bumps.listing;
+
+    listing.validate_proceeds_destination(&ctx.accounts.proceeds_destination)?;
+
+    emit!(DomainListed {
+        version: EVENT_SCHEMA_VERSION,
+        vault: vault.key(),
+        sns_name: vault.sns_name,
+        seller: listing.seller,
+        price,
+        mint: listing.mint,
+        buyer: listing.buyer,
+        release_domain,
+        timestamp: now,
+    });
+
+    log_info!("Domain listed for sale (release domain: {})", release_domain);
+    log_sensitive!("Listing price: {}", price);
+
+    Ok(())
+}
@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::constants::{VAULT_SEED, VAULT_AUTH_SEED, SNS_NAME_PROGRAM_ID};
-use crate::state::{NameVault, VaultAuthority};
+use crate::constants::{VAULT_SEED, VAULT_AUTH_SEED, OWNER_SET_SEED, SNS_NAME_PROGRAM_ID};
+use crate::state::{NameVault, VaultAuthority, VaultOwnerSet, verify_owner_authority};
 use crate::errors::HydentityError;
+use crate::events::{DomainTransferred, EVENT_SCHEMA_VERSION};
 
 /// Mark domain as transferred to the vault authority
 /// 
@@ -12,7 +13,7 @@ use crate::errors::HydentityError;
 /// The verification checks that the SNS name account's owner field matches the vault authority.
 #[derive(Accounts)]
 pub struct MarkDomainTransferred<'info> {
-    /// The vault owner (must be signer)
+    /// The vault owner or an owner set member (must be signer)
     pub owner: Signer<'info>,
     
     /// The SNS name account that was transferred
@@ -27,7 +28,6 @@ pub struct MarkDomainTransferred<'info> {
         mut,
         seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
         bump = vault.bump,
-        constraint = vault.owner == owner.key() @ HydentityError::Unauthorized,
         constraint = !vault.domain_transferred @ HydentityError::DomainAlreadyTransferred,
     )]
     pub vault: Account<'info, NameVault>,
@@ -38,16 +38,36 @@ pub struct MarkDomainTransferred<'info> {
         bump = vault_authority.bump,
     )]
     pub vault_authority: Account<'info, VaultAuthority>,
+    
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
 }
 
 pub fn handler(ctx: Context<MarkDomainTransferred>) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+    
+    ctx.accounts.vault.touch_owner_activity(clock.unix_timestamp);
+    
     let vault = &mut ctx.accounts.vault;
     let vault_authority = &ctx.accounts.vault_authority;
     let sns_name_account = &ctx.accounts.sns_name_account;
     
-    msg!("Verifying domain transfer to vault authority");
-    msg!("SNS name account: {}", sns_name_account.key());
-    msg!("Expected owner (vault authority): {}", vault_authority.key());
+    log_info!("Verifying domain transfer to vault authority");
+    log_sensitive!("SNS name account: {}", sns_name_account.key());
+    log_sensitive!("Expected owner (vault authority): {}", vault_authority.key());
     
     // Verify the SNS name account is now owned by the vault authority
     // by checking the owner field in the name registry state
@@ -56,7 +76,7 @@ pub fn handler(ctx: Context<MarkDomainTransferred>) -> Result<()> {
         .map_err(|_| HydentityError::InvalidSnsName)?;
     let current_owner = Pubkey::new_from_array(current_owner_bytes);
     
-    msg!("Current SNS owner: {}", current_owner);
+    log_sensitive!("Current SNS owner: {}", current_owner);
     
     require!(
         current_owner == vault_authority.key(),
@@ -66,7 +86,15 @@ pub fn handler(ctx: Context<MarkDomainTransferred>) -> Result<()> {
     // Update vault state to mark domain as transferred
     vault.set_domain_transferred(true);
     
-    msg!("Domain ownership verified and marked as transferred to vault");
+    emit!(DomainTransferred {
+        version: EVENT_SCHEMA_VERSION,
+        vault: vault.key(),
+        sns_name: sns_name_account.key(),
+        vault_authority: vault_authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    log_info!("Domain ownership verified and marked as transferred to vault");
     
     Ok(())
 }
@@ -1,25 +1,111 @@
 pub mod initialize_vault;
 pub mod update_policy;
+pub mod queue_policy_update;
+pub mod apply_policy_update;
+pub mod cancel_policy_update;
+pub mod apply_policy_preset;
 pub mod deposit_to_umbra;
 pub mod withdraw_direct;
 pub mod add_delegate;
 pub mod revoke_delegate;
+pub mod add_sub_delegate;
+pub mod set_owner_set;
+pub mod remove_owner_set;
+pub mod set_recovery_config;
+pub mod initiate_recovery;
+pub mod approve_recovery;
+pub mod cancel_recovery;
+pub mod execute_recovery;
+pub mod propose_owner_transfer;
+pub mod accept_owner_transfer;
+pub mod cancel_owner_transfer;
+pub mod set_beneficiary;
+pub mod claim_inheritance;
+pub mod set_withdrawal_timelock;
+pub mod queue_direct_withdrawal;
+pub mod execute_direct_withdrawal;
+pub mod cancel_direct_withdrawal;
+pub mod set_outflow_limit;
 pub mod mark_domain_transferred;
+pub mod transfer_domain_to_vault;
+pub mod sync_domain_state;
+pub mod set_subdomain_policy;
+pub mod initialize_subdomain_vault;
+pub mod set_vault_sol_record;
+pub mod list_domain_for_sale;
+pub mod buy_domain;
+pub mod delist_domain;
+pub mod set_reclaim_timelock;
+pub mod initiate_reclaim;
+pub mod complete_reclaim;
+pub mod cancel_reclaim;
 pub mod reclaim_domain;
 #[cfg(feature = "arcium")]
 pub mod store_private_config;
 pub mod close_vault;
 pub mod claim_vault;
+pub mod rebind_vault;
+pub mod create_vault_profile;
+pub mod update_profile_policy;
+pub mod withdraw_profile_direct;
+pub mod close_vault_profile;
+pub mod deposit_to_profile;
+pub mod queue_profile_withdrawal;
+pub mod execute_profile_withdrawal;
+pub mod sync_owner_index;
 // pub mod request_withdrawal; // TODO: Fix callback resolution for Arcium macros
 
 pub use initialize_vault::{InitializeVault, handler as init_vault_handler};
 pub use update_policy::{UpdatePolicy, UpdatePolicyParams, handler as update_policy_handler};
+pub use queue_policy_update::{QueuePolicyUpdate, handler as queue_policy_update_handler};
+pub use apply_policy_update::{ApplyPolicyUpdate, handler as apply_policy_update_handler};
+pub use cancel_policy_update::{CancelPolicyUpdate, handler as cancel_policy_update_handler};
+pub use apply_policy_preset::{ApplyPolicyPreset, handler as apply_policy_preset_handler};
 pub use deposit_to_umbra::{DepositToUmbra, handler as deposit_handler};
 pub use withdraw_direct::{WithdrawDirect, handler as withdraw_handler};
 pub use add_delegate::{AddDelegate, handler as add_delegate_handler};
 pub use revoke_delegate::{RevokeDelegate, handler as revoke_delegate_handler};
+pub use add_sub_delegate::{AddSubDelegate, handler as add_sub_delegate_handler};
+pub use set_owner_set::{SetOwnerSet, handler as set_owner_set_handler};
+pub use remove_owner_set::{RemoveOwnerSet, handler as remove_owner_set_handler};
+pub use set_recovery_config::{SetRecoveryConfig, handler as set_recovery_config_handler};
+pub use initiate_recovery::{InitiateRecovery, handler as initiate_recovery_handler};
+pub use approve_recovery::{ApproveRecovery, handler as approve_recovery_handler};
+pub use cancel_recovery::{CancelRecovery, handler as cancel_recovery_handler};
+pub use execute_recovery::{ExecuteRecovery, handler as execute_recovery_handler};
+pub use propose_owner_transfer::{ProposeOwnerTransfer, handler as propose_owner_transfer_handler};
+pub use accept_owner_transfer::{AcceptOwnerTransfer, handler as accept_owner_transfer_handler};
+pub use cancel_owner_transfer::{CancelOwnerTransfer, handler as cancel_owner_transfer_handler};
+pub use set_beneficiary::{SetBeneficiary, handler as set_beneficiary_handler};
+pub use claim_inheritance::{ClaimInheritance, handler as claim_inheritance_handler};
+pub use set_withdrawal_timelock::{SetWithdrawalTimelock, handler as set_withdrawal_timelock_handler};
+pub use queue_direct_withdrawal::{QueueDirectWithdrawal, handler as queue_direct_withdrawal_handler};
+pub use execute_direct_withdrawal::{ExecuteDirectWithdrawal, handler as execute_direct_withdrawal_handler};
+pub use cancel_direct_withdrawal::{CancelDirectWithdrawal, handler as cancel_direct_withdrawal_handler};
+pub use set_outflow_limit::{SetOutflowLimit, handler as set_outflow_limit_handler};
 pub use mark_domain_transferred::{MarkDomainTransferred, handler as mark_domain_handler};
+pub use transfer_domain_to_vault::{TransferDomainToVault, handler as transfer_domain_handler};
+pub use sync_domain_state::{SyncDomainState, handler as sync_domain_state_handler};
+pub use set_subdomain_policy::{SetSubdomainPolicy, handler as set_subdomain_policy_handler};
+pub use initialize_subdomain_vault::{InitializeSubdomainVault, handler as initialize_subdomain_vault_handler};
+pub use set_vault_sol_record::{SetVaultSolRecord, handler as set_vault_sol_record_handler};
+pub use list_domain_for_sale::{ListDomainForSale, handler as list_domain_for_sale_handler};
+pub use buy_domain::{BuyDomain, handler as buy_domain_handler};
+pub use delist_domain::{DelistDomain, handler as delist_domain_handler};
+pub use set_reclaim_timelock::{SetReclaimTimelock, handler as set_reclaim_timelock_handler};
+pub use initiate_reclaim::{InitiateReclaim, handler as initiate_reclaim_handler};
+pub use complete_reclaim::{CompleteReclaim, handler as complete_reclaim_handler};
+pub use cancel_reclaim::{CancelReclaim, handler as cancel_reclaim_handler};
 pub use reclaim_domain::{ReclaimDomain, handler as reclaim_domain_handler};
 pub use close_vault::{CloseVault, handler as close_vault_handler};
 pub use claim_vault::{ClaimVault, handler as claim_vault_handler};
+pub use rebind_vault::{RebindVault, handler as rebind_vault_handler};
+pub use create_vault_profile::{CreateVaultProfile, handler as create_vault_profile_handler};
+pub use update_profile_policy::{UpdateProfilePolicy, handler as update_profile_policy_handler};
+pub use withdraw_profile_direct::{WithdrawProfileDirect, handler as withdraw_profile_direct_handler};
+pub use close_vault_profile::{CloseVaultProfile, handler as close_vault_profile_handler};
+pub use deposit_to_profile::{DepositToProfile, handler as deposit_to_profile_handler};
+pub use queue_profile_withdrawal::{QueueProfileWithdrawal, handler as queue_profile_withdrawal_handler};
+pub use execute_profile_withdrawal::{ExecuteProfileWithdrawal, handler as execute_profile_withdrawal_handler};
+pub use sync_owner_index::{SyncOwnerIndex, handler as sync_owner_index_handler};
 
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, OWNER_SET_SEED, OWNER_TRANSFER_SEED};
+use crate::errors::HydentityError;
+use crate::events::{OwnerTransferProposed, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, PendingOwnerTransfer, VaultOwnerSet, verify_owner_authority};
+
+/// Propose rotating the vault owner to a new key
+///
+/// First step of a two-step key rotation that does not touch the SNS
+/// domain. The new key accepts with `accept_owner_transfer` once
+/// `delay_seconds` have passed. Only one proposal can be pending.
+#[derive(Accounts)]
+pub struct ProposeOwnerTransfer<'info> {
+    /// The vault owner or an owner set member (must be signer, pays for the proposal)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault whose owner is being rotated
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The pending transfer PDA to be created
+    #[account(
+        init,
+        payer = owner,
+        space = PendingOwnerTransfer::LEN,
+        seeds = [OWNER_TRANSFER_SEED, sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub pending_transfer: Account<'info, PendingOwnerTransfer>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+}
+
+pub fn handler(
+    ctx: Context<ProposeOwnerTransfer>,
+    new_owner: Pubkey,
+    delay_seconds: i64,
+    reset_destinations: bool,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    // A sold domain freezes the previous owner until the buyer claims the vault
+    ctx.accounts.vault.verify_domain_control(&ctx.accounts.sns_name_account)?;
+
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    ctx.accounts.vault.touch_owner_activity(now);
+
+    let vault = &ctx.accounts.vault;
+    require!(new_owner != Pubkey::default(), HydentityError::InvalidVault);
+    require!(!vault.is_owner(&new_owner), HydentityError::VaultOwnerUnchanged);
+    PendingOwnerTransfer::validate_delay(delay_seconds)?;
+
+    let executable_at = now
+        .checked_add(delay_seconds)
+        .ok_or(HydentityError::ArithmeticOverflow)?;
+
+    let pending = &mut ctx.accounts.pending_transfer;
+    pending.vault = vault.key();
+    pending.current_owner = vault.owner;
+    pending.new_owner = new_owner;
+    pending.proposed_by = ctx.accounts.owner.key();
+    pending.reset_destinations = reset_destinations;
+    pending.proposed_at = now;
+    pending.executable_at = executable_at;
+    pending.bump = ctx.bumps.pending_transfer;
+
+    emit!(OwnerTransferProposed {
+        version: EVENT_SCHEMA_VERSION,
+        vault: vault.key(),
+        current_owner: vault.owner,
+        new_owner,
+        executable_at,
+        reset_destinations,
+        timestamp: now,
+    });
+
+    log_sensitive!("Owner transfer proposed to: {}", new_owner);
+    log_info!("Acceptable at: {}", executable_at);
+
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, POLICY_SEED, DELEGATE_SEED, OWNER_SET_SEED, PENDING_POLICY_SEED};
+use crate::errors::HydentityError;
+use crate::events::{PolicyUpdateQueued, EVENT_SCHEMA_VERSION};
+use crate::instructions::update_policy::{UpdatePolicyParams, authorize_policy_change};
+use crate::state::{NameVault, PrivacyPolicy, PendingPolicyUpdate, DelegateSession, VaultOwnerSet};
+
+/// Queue a policy update behind the policy timelock
+///
+/// Accepts the same parameters and callers as `update_policy`. The update
+/// is validated now but only written to the policy by `apply_policy_update`
+/// once `policy_update_delay_seconds` has elapsed.
+#[derive(Accounts)]
+pub struct QueuePolicyUpdate<'info> {
+    /// The caller (owner, owner set member or delegate; pays for the request)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault (for ownership verification)
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The timelocked privacy policy
+    #[account(
+        seeds = [POLICY_SEED, sns_name_account.key().as_ref()],
+        bump = policy.bump,
+        constraint = policy.vault == vault.key() @ HydentityError::InvalidPolicyConfig
+    )]
+    pub policy: Account<'info, PrivacyPolicy>,
+
+    /// The pending update PDA to be created
+    #[account(
+        init,
+        payer = authority,
+        space = PendingPolicyUpdate::LEN,
+        seeds = [PENDING_POLICY_SEED, sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub pending_update: Account<'info, PendingPolicyUpdate>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+
+    /// Optional delegate session (if caller is not the owner)
+    /// Child sessions must pass their parent sessions as remaining accounts
+    #[account(
+        seeds = [DELEGATE_SEED, sns_name_account.key().as_ref(), authority.key().as_ref()],
+        bump = delegate_session.bump,
+        constraint = delegate_session.vault == vault.key() @ HydentityError::UnauthorizedDelegate
+    )]
+    pub delegate_session: Option<Account<'info, DelegateSession>>,
+
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+}
+
+pub fn handler(ctx: Context<QueuePolicyUpdate>, params: UpdatePolicyParams) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    // A sold domain freezes the previous owner until the buyer claims the vault
+    ctx.accounts.vault.verify_domain_control(&ctx.accounts.sns_name_account)?;
+
+    let actor = authorize_policy_change(
+        &ctx.accounts.vault,
+        &ctx.accounts.authority.to_account_info(),
+        ctx.accounts.delegate_session.as_deref(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+        now,
+    )?;
+    let owner_signed = actor.is_owner();
+    params.verify_allowlist_access(&ctx.accounts.policy, owner_signed)?;
+
+    // Reject updates that would fail when applied
+    let policy = &ctx.accounts.policy;
+    let mut preview = (**policy).clone();
+    params.apply(&mut preview, now);
+    preview.validate_update(policy)?;
+
+    if owner_signed {
+        ctx.accounts.vault.touch_owner_activity(now);
+    }
+
+    let effective_at = now
+        .checked_add(policy.policy_update_delay_seconds as i64)
+        .ok_or(HydentityError::ArithmeticOverflow)?;
+    let base_nonce = policy.policy_nonce;
+    let authority = ctx.accounts.authority.key();
+
+    let pending = &mut ctx.accounts.pending_update;
+    pending.vault = ctx.accounts.vault.key();
+    pending.owner = ctx.accounts.vault.owner;
+    pending.queued_by = authority;
+    pending.actor_type = actor;
+    pending.payer = authority;
+    pending.base_nonce = base_nonce;
+    pending.params = params;
+    pending.queued_at = now;
+    pending.effective_at = effective_at;
+    pending.bump = ctx.bumps.pending_update;
+
+    emit!(PolicyUpdateQueued {
+        version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.vault.key(),
+        queued_by: authority,
+        base_nonce,
+        effective_at,
+        timestamp: now,
+    });
+
+    log_info!("Policy update queued against nonce {}", base_nonce);
+    log_info!("Applies at: {}", effective_at);
+
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, POLICY_SEED, OWNER_SET_SEED, WITHDRAWAL_CONFIG_SEED, DIRECT_WITHDRAWAL_SEED, VAULT_PROFILE_SEED};
+use crate::errors::HydentityError;
+use crate::events::{DirectWithdrawalQueued, EVENT_SCHEMA_VERSION};
+use crate::state::{
+    NameVault, PrivacyPolicy, DirectWithdrawalConfig, PendingDirectWithdrawal, VaultOwnerSet, VaultProfile,
+    verify_owner_authority,
+};
+
+/// Queue a direct withdrawal from a vault profile behind the domain's timelock
+///
+/// Same as `queue_direct_withdrawal`, for amounts `withdraw_profile_direct`
+/// refuses. The request records the profile as its vault and shares the
+/// domain's withdrawal ids, delay and security key.
+#[derive(Accounts)]
+#[instruction(profile_id: u8)]
+pub struct QueueProfileWithdrawal<'info> {
+    /// The vault owner or an owner set member (must be signer, pays for the request)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The domain's vault
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The profile holding the funds
+    #[account(
+        seeds = [VAULT_PROFILE_SEED, sns_name_account.key().as_ref(), &[profile_id]],
+        bump = profile.bump,
+        constraint = profile.vault == vault.key() @ HydentityError::ProfileNotFound
+    )]
+    pub profile: Account<'info, VaultProfile>,
+
+    /// The domain's timelock config
+    #[account(
+        mut,
+        seeds = [WITHDRAWAL_CONFIG_SEED, sns_name_account.key().as_ref()],
+        bump = withdrawal_config.bump,
+        constraint = withdrawal_config.vault == vault.key() @ HydentityError::WithdrawalConfigRequired
+    )]
+    pub withdrawal_config: Account<'info, DirectWithdrawalConfig>,
+
+    /// The profile's privacy policy (destination allowlist)
+    #[account(
+        seeds = [POLICY_SEED, sns_name_account.key().as_ref(), &[profile_id]],
+        bump = profile_policy.bump,
+        constraint = profile_policy.vault == profile.key() @ HydentityError::InvalidPolicyConfig
+    )]
+    pub profile_policy: Account<'info, PrivacyPolicy>,
+
+    /// The queued withdrawal PDA to be created
+    #[account(
+        init,
+        payer = owner,
+        space = PendingDirectWithdrawal::LEN,
+        seeds = [
+            DIRECT_WITHDRAWAL_SEED,
+            profile.key().as_ref(),
+            &withdrawal_config.next_withdrawal_id.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub pending_withdrawal: Account<'info, PendingDirectWithdrawal>,
+
+    /// The recipient (wallet for SOL, token account for SPL)
+    /// CHECK: Any valid account can receive funds
+    pub destination: UncheckedAccount<'info>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+}
+
+pub fn handler(
+    ctx: Context<QueueProfileWithdrawal>,
+    profile_id: u8,
+    amount: u64,
+    mint: Option<Pubkey>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    // A sold domain freezes the previous owner until the buyer claims the vault
+    ctx.accounts.vault.verify_domain_control(&ctx.accounts.sns_name_account)?;
+
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    if amount == 0 {
+        return Err(HydentityError::InsufficientBalance.into());
+    }
+
+    ctx.accounts.vault.touch_owner_activity(now);
+
+    let config = &mut ctx.accounts.withdrawal_config;
+    config.apply_matured(now);
+
+    let withdrawal_id = config.next_withdrawal_id;
+    config.next_withdrawal_id = withdrawal_id
+        .checked_add(1)
+        .ok_or(HydentityError::ArithmeticOverflow)?;
+    let unlock_at = now
+        .checked_add(config.delay_seconds)
+        .ok_or(HydentityError::ArithmeticOverflow)?;
+
+    let profile = ctx.accounts.profile.key();
+    let destination = ctx.accounts.destination.key();
+    let mint = mint.unwrap_or_default();
+
+    let pending = &mut ctx.accounts.pending_withdrawal;
+    pending.vault = profile;
+    pending.withdrawal_id = withdrawal_id;
+    pending.owner = ctx.accounts.vault.owner;
+    pending.mint = mint;
+    pending.destination = destination;
+    pending.amount = amount;
+    pending.requested_by = ctx.accounts.owner.key();
+    pending.requested_at = now;
+    pending.unlock_at = unlock_at;
+    pending.bump = ctx.bumps.pending_withdrawal;
+
+    // Only allowlisted recipients when the profile's policy enforces it
+    let recipient = pending.recipient(&ctx.accounts.destination.to_account_info())?;
+    ctx.accounts.profile_policy.verify_direct_destination(&recipient, now)?;
+
+    emit!(DirectWithdrawalQueued {
+        version: EVENT_SCHEMA_VERSION,
+        vault: profile,
+        withdrawal_id,
+        mint,
+        destination,
+        amount,
+        unlock_at,
+        timestamp: now,
+    });
+
+    log_sensitive!("Profile {} withdrawal #{} queued: {} to {}", profile_id, withdrawal_id, amount, destination);
+    log_info!("Unlocks at: {}", unlock_at);
+
+    Ok(())
+}
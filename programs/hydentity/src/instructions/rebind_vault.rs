@@ -0,0 +1,524 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+use crate::constants::{
+    VAULT_SEED, VAULT_AUTH_SEED, POLICY_SEED, OWNER_SET_SEED, WITHDRAWAL_CONFIG_SEED, RECLAIM_CONFIG_SEED,
+    RECOVERY_CONFIG_SEED, POLICY_HISTORY_SEED, PROFILE_REGISTRY_SEED, SNS_NAME_PROGRAM_ID,
+};
+use crate::errors::HydentityError;
+use crate::events::{VaultRebound, EVENT_SCHEMA_VERSION};
+use crate::state::{
+    NameVault, VaultAuthority, PrivacyPolicy, VaultOwnerSet, EncryptedVaultConfig, ENCRYPTED_CONFIG_SEED,
+    DirectWithdrawalConfig, ReclaimConfig, RecoveryConfig, PolicyHistory,
+    read_sns_parent, is_subdomain_parent, resolve_domain_holder, add_to_owner_index, remove_from_owner_index,
+    migrate_outflow_limit, verify_no_open_profiles, is_program_account, close_program_account,
+};
+
+/// Move a vault to a different SNS domain (e.g. after a rebrand)
+///
+/// The vault owner must hold both domains, and the old domain must not be
+/// held by the vault authority (reclaim it first). Creates the vault,
+/// vault authority and policy under the new name, moves the SOL balance,
+/// copies the policy and (if passed) the encrypted config, then closes
+/// the old accounts, including a configured owner set so it cannot carry
+/// over to a vault re-created for the old name.
+///
+/// Protections move with the vault so rebinding cannot shed them: the
+/// withdrawal and reclaim timelocks, the recovery config, the policy
+/// history and the outflow limit of every asset moved are copied under
+/// the new name, and the old accounts are closed. The domain's profiles
+/// must be closed first. Delegate sessions stay behind and no longer
+/// apply (see `NameVault::accepts_delegation_from`).
+///
+/// SPL balances move through remaining accounts, passed as groups of four:
+/// (old vault authority token account, new vault authority token account,
+/// old vault's outflow limit PDA for the mint, new vault's outflow limit
+/// PDA for the mint). Each old token account is emptied and closed.
+#[derive(Accounts)]
+pub struct RebindVault<'info> {
+    /// The vault owner, holding both domains (must be signer, pays for the new accounts)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The SNS name account the vault is bound to today
+    /// CHECK: Must be owned by the SNS Name Program; owner field read manually
+    #[account(
+        constraint = old_sns_name_account.owner == &SNS_NAME_PROGRAM_ID @ HydentityError::InvalidSnsName
+    )]
+    pub old_sns_name_account: UncheckedAccount<'info>,
+
+    /// The SNS name account to bind the vault to
+    /// CHECK: Must be owned by the SNS Name Program; owner field read manually
+    #[account(
+        constraint = new_sns_name_account.owner == &SNS_NAME_PROGRAM_ID @ HydentityError::InvalidSnsName
+    )]
+    pub new_sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault to migrate (closed)
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, old_sns_name_account.key().as_ref()],
+        bump = old_vault.bump,
+        constraint = old_vault.owner == owner.key() @ HydentityError::Unauthorized,
+        constraint = !old_vault.domain_transferred @ HydentityError::DomainAlreadyTransferred,
+        close = owner,
+    )]
+    pub old_vault: Box<Account<'info, NameVault>>,
+
+    /// The old vault authority (SOL moved to the new one, then closed)
+    #[account(
+        mut,
+        seeds = [VAULT_AUTH_SEED, old_sns_name_account.key().as_ref()],
+        bump = old_vault_authority.bump,
+        close = owner,
+    )]
+    pub old_vault_authority: Box<Account<'info, VaultAuthority>>,
+
+    /// The old privacy policy (copied, then closed)
+    #[account(
+        mut,
+        seeds = [POLICY_SEED, old_sns_name_account.key().as_ref()],
+        bump = old_policy.bump,
+        constraint = old_policy.vault == old_vault.key() @ HydentityError::InvalidPolicyConfig,
+        close = owner,
+    )]
+    pub old_policy: Box<Account<'info, PrivacyPolicy>>,
+
+    /// The vault PDA under the new name
+    #[account(
+        init,
+        payer = owner,
+        space = NameVault::LEN,
+        seeds = [VAULT_SEED, new_sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub new_vault: Box<Account<'info, NameVault>>,
+
+    /// The vault authority PDA under the new name
+    #[account(
+        init,
+        payer = owner,
+        space = VaultAuthority::LEN,
+        seeds = [VAULT_AUTH_SEED, new_sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub new_vault_authority: Box<Account<'info, VaultAuthority>>,
+
+    /// The privacy policy PDA under the new name
+    ///
+    /// Sized like the old policy, which may hold more destinations than
+    /// `MAX_DESTINATIONS` if it was saved under the old limits.
+    #[account(
+        init,
+        payer = owner,
+        space = old_policy.to_account_info().data_len().max(PrivacyPolicy::LEN),
+        seeds = [POLICY_SEED, new_sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub new_policy: Box<Account<'info, PrivacyPolicy>>,
+
+    /// The owner's vault index (old vault replaced by the new one)
+    /// CHECK: Address and contents validated by add_to_owner_index
+    #[account(mut)]
+    pub owner_index: UncheckedAccount<'info>,
+
+    /// Token program for moving SPL balances
+    pub token_program: Program<'info, Token>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+
+    /// Owner set of the old vault (required if configured, closed)
+    #[account(
+        mut,
+        seeds = [OWNER_SET_SEED, old_sns_name_account.key().as_ref()],
+        bump = old_owner_set.bump,
+        constraint = old_owner_set.vault == old_vault.key() @ HydentityError::InvalidOwnerSet,
+        close = owner,
+    )]
+    pub old_owner_set: Option<Box<Account<'info, VaultOwnerSet>>>,
+
+    /// Encrypted config of the old vault (copied, then closed)
+    #[account(
+        mut,
+        seeds = [ENCRYPTED_CONFIG_SEED, old_vault.key().as_ref()],
+        bump = old_encrypted_config.bump,
+        constraint = old_encrypted_config.vault == old_vault.key() @ HydentityError::InvalidVault,
+        close = owner,
+    )]
+    pub old_encrypted_config: Option<Box<Account<'info, EncryptedVaultConfig>>>,
+
+    /// Encrypted config PDA under the new vault (pass with old_encrypted_config)
+    #[account(
+        init,
+        payer = owner,
+        space = EncryptedVaultConfig::SPACE,
+        seeds = [ENCRYPTED_CONFIG_SEED, new_vault.key().as_ref()],
+        bump
+    )]
+    pub new_encrypted_config: Option<Box<Account<'info, EncryptedVaultConfig>>>,
+
+    /// Token account holding the new domain's NFT (tokenized domains only)
+    pub nft_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// SOL outflow limit PDA of the old vault (may be uninitialized, closed)
+    /// CHECK: Address and contents validated by migrate_outflow_limit
+    #[account(mut)]
+    pub old_outflow_limit: UncheckedAccount<'info>,
+
+    /// SOL outflow limit PDA of the new vault (created if the old vault has a limit)
+    /// CHECK: Address and contents validated by migrate_outflow_limit
+    #[account(mut)]
+    pub new_outflow_limit: UncheckedAccount<'info>,
+
+    /// Withdrawal timelock config of the old vault (required if configured, closed)
+    #[account(
+        mut,
+        seeds = [WITHDRAWAL_CONFIG_SEED, old_sns_name_account.key().as_ref()],
+        bump = old_withdrawal_config.bump,
+        constraint = old_withdrawal_config.vault == old_vault.key() @ HydentityError::InvalidVault,
+        close = owner,
+    )]
+    pub old_withdrawal_config: Option<Box<Account<'info, DirectWithdrawalConfig>>>,
+
+    /// Withdrawal timelock config PDA under the new name (pass with old_withdrawal_config)
+    #[account(
+        init,
+        payer = owner,
+        space = DirectWithdrawalConfig::LEN,
+        seeds = [WITHDRAWAL_CONFIG_SEED, new_sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub new_withdrawal_config: Option<Box<Account<'info, DirectWithdrawalConfig>>>,
+
+    /// Reclaim timelock config of the old vault (required if configured, closed)
+    #[account(
+        mut,
+        seeds = [RECLAIM_CONFIG_SEED, old_sns_name_account.key().as_ref()],
+        bump = old_reclaim_config.bump,
+        constraint = old_reclaim_config.vault == old_vault.key() @ HydentityError::InvalidVault,
+        close = owner,
+    )]
+    pub old_reclaim_config: Option<Box<Account<'info, ReclaimConfig>>>,
+
+    /// Reclaim timelock config PDA under the new name (pass with old_reclaim_config)
+    #[account(
+        init,
+        payer = owner,
+        space = ReclaimConfig::LEN,
+        seeds = [RECLAIM_CONFIG_SEED, new_sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub new_reclaim_config: Option<Box<Account<'info, ReclaimConfig>>>,
+
+    /// Recovery config PDA of the old vault (may be uninitialized, closed)
+    /// CHECK: Address checked by seeds; contents deserialized in the handler
+    #[account(
+        mut,
+        seeds = [RECOVERY_CONFIG_SEED, old_sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub old_recovery_config: UncheckedAccount<'info>,
+
+    /// Recovery config PDA under the new name (required if the old vault has one)
+    #[account(
+        init,
+        payer = owner,
+        space = RecoveryConfig::LEN,
+        seeds = [RECOVERY_CONFIG_SEED, new_sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub new_recovery_config: Option<Box<Account<'info, RecoveryConfig>>>,
+
+    /// Policy history PDA of the old vault (may be uninitialized, closed)
+    /// CHECK: Address checked by seeds; contents deserialized in the handler
+    #[account(
+        mut,
+        seeds = [POLICY_HISTORY_SEED, old_sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub old_policy_history: UncheckedAccount<'info>,
+
+    /// Policy history PDA under the new name (required if the old vault has one)
+    #[account(
+        init,
+        payer = owner,
+        space = PolicyHistory::LEN,
+        seeds = [POLICY_HISTORY_SEED, new_sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub new_policy_history: Option<Box<Account<'info, PolicyHistory>>>,
+
+    /// Profile registry PDA of the old name (may be uninitialized; must list no profiles)
+    /// CHECK: Address checked by seeds; contents validated by verify_no_open_profiles
+    #[account(
+        seeds = [PROFILE_REGISTRY_SEED, old_sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub old_profile_registry: UncheckedAccount<'info>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, RebindVault<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    // A sold domain freezes the previous owner until the buyer claims the vault
+    ctx.accounts.old_vault.verify_domain_control(&ctx.accounts.old_sns_name_account)?;
+
+    let owner = ctx.accounts.owner.key();
+    let old_sns_name = ctx.accounts.old_sns_name_account.key();
+    let new_sns_name = ctx.accounts.new_sns_name_account.key();
+    require_keys_neq!(old_sns_name, new_sns_name, HydentityError::InvalidSnsName);
+
+    // The owner must hold both domains
+    require_keys_eq!(
+        resolve_domain_holder(&ctx.accounts.old_sns_name_account, None)?,
+        owner,
+        HydentityError::SnsOwnershipVerificationFailed
+    );
+    require_keys_eq!(
+        resolve_domain_holder(&ctx.accounts.new_sns_name_account, ctx.accounts.nft_token_account.as_deref())?,
+        owner,
+        HydentityError::SnsOwnershipVerificationFailed
+    );
+
+    // Subdomains go through initialize_subdomain_vault so they get linked to their parent
+    require!(
+        !is_subdomain_parent(&read_sns_parent(&ctx.accounts.new_sns_name_account)?),
+        HydentityError::SubdomainRequiresParent
+    );
+
+    // A configured owner set must be closed with the old vault, otherwise it
+    // would carry over to a vault re-created for the old name
+    require!(
+        !ctx.accounts.old_vault.has_owner_set || ctx.accounts.old_owner_set.is_some(),
+        HydentityError::OwnerSetRequired
+    );
+    require!(
+        ctx.accounts.old_encrypted_config.is_some() == ctx.accounts.new_encrypted_config.is_some(),
+        HydentityError::InvalidVault
+    );
+
+    // Timelocks move with the vault, so both halves are needed when one is configured
+    require!(
+        ctx.accounts.old_withdrawal_config.is_some() == ctx.accounts.new_withdrawal_config.is_some(),
+        HydentityError::InvalidVault
+    );
+    require!(
+        !ctx.accounts.old_vault.has_withdrawal_timelock || ctx.accounts.old_withdrawal_config.is_some(),
+        HydentityError::WithdrawalConfigRequired
+    );
+    require!(
+        ctx.accounts.old_reclaim_config.is_some() == ctx.accounts.new_reclaim_config.is_some(),
+        HydentityError::InvalidVault
+    );
+    require!(
+        !ctx.accounts.old_vault.has_reclaim_timelock || ctx.accounts.old_reclaim_config.is_some(),
+        HydentityError::ReclaimConfigRequired
+    );
+
+    // Profiles are authorized against this vault and would be left without one
+    verify_no_open_profiles(&ctx.accounts.old_profile_registry)?;
+
+    let old_vault_key = ctx.accounts.old_vault.key();
+    let new_vault_key = ctx.accounts.new_vault.key();
+
+    // Copy the vault; the owner set starts over, and delegate sessions and
+    // outflow limits left under the new name by an earlier vault stop applying
+    let mut vault = (**ctx.accounts.old_vault).clone();
+    vault.sns_name = new_sns_name;
+    vault.bump = ctx.bumps.new_vault;
+    vault.domain_transferred = false;
+    vault.has_owner_set = false;
+    vault.has_withdrawal_timelock = ctx.accounts.old_withdrawal_config.is_some();
+    vault.has_reclaim_timelock = ctx.accounts.old_reclaim_config.is_some();
+    vault.rekeyed_at = now;
+    vault.owner_lost_domain = false;
+    vault.owner_rotated = false;
+    vault.parent_controls_subdomains = false;
+    vault.managed_by_parent = false;
+    vault.touch_owner_activity(now);
+    ctx.accounts.new_vault.set_inner(vault);
+
+    remove_from_owner_index(&ctx.accounts.owner_index, &owner, &old_vault_key)?;
+    add_to_owner_index(
+        &ctx.accounts.owner_index,
+        &owner,
+        &new_vault_key,
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    ctx.accounts.new_vault_authority.initialize(
+        new_vault_key,
+        new_sns_name,
+        ctx.bumps.new_vault_authority,
+    );
+
+    let mut policy = (**ctx.accounts.old_policy).clone();
+    policy.vault = new_vault_key;
+    policy.sns_name = new_sns_name;
+    policy.bump = ctx.bumps.new_policy;
+    policy.updated_at = now;
+    ctx.accounts.new_policy.set_inner(policy);
+
+    if let (Some(old_config), Some(new_config)) = (
+        ctx.accounts.old_encrypted_config.as_ref(),
+        ctx.accounts.new_encrypted_config.as_mut(),
+    ) {
+        let mut config = (***old_config).clone();
+        config.vault = new_vault_key;
+        config.bump = ctx.bumps.new_encrypted_config.unwrap_or_default();
+        new_config.set_inner(config);
+    }
+
+    if let (Some(old_config), Some(new_config)) = (
+        ctx.accounts.old_withdrawal_config.as_ref(),
+        ctx.accounts.new_withdrawal_config.as_mut(),
+    ) {
+        let mut config = (***old_config).clone();
+        config.vault = new_vault_key;
+        config.bump = ctx.bumps.new_withdrawal_config.unwrap_or_default();
+        new_config.set_inner(config);
+    }
+
+    if let (Some(old_config), Some(new_config)) = (
+        ctx.accounts.old_reclaim_config.as_ref(),
+        ctx.accounts.new_reclaim_config.as_mut(),
+    ) {
+        let mut config = (***old_config).clone();
+        config.vault = new_vault_key;
+        config.bump = ctx.bumps.new_reclaim_config.unwrap_or_default();
+        new_config.set_inner(config);
+    }
+
+    // Recovery guardians and the policy audit trail have no vault flag, so
+    // an existing account must always be carried over
+    let old_recovery_info = ctx.accounts.old_recovery_config.to_account_info();
+    if is_program_account(&old_recovery_info) {
+        let mut config = {
+            let data = old_recovery_info.try_borrow_data()?;
+            RecoveryConfig::try_deserialize(&mut &data[..])?
+        };
+        require_keys_eq!(config.vault, old_vault_key, HydentityError::InvalidRecoveryConfig);
+        let new_config = ctx.accounts.new_recovery_config
+            .as_mut()
+            .ok_or(HydentityError::InvalidRecoveryConfig)?;
+        config.vault = new_vault_key;
+        config.sns_name = new_sns_name;
+        config.bump = ctx.bumps.new_recovery_config.unwrap_or_default();
+        new_config.set_inner(config);
+        close_program_account(&old_recovery_info, &ctx.accounts.owner.to_account_info())?;
+    }
+
+    let old_history_info = ctx.accounts.old_policy_history.to_account_info();
+    if is_program_account(&old_history_info) {
+        let mut history = {
+            let data = old_history_info.try_borrow_data()?;
+            PolicyHistory::try_deserialize(&mut &data[..])?
+        };
+        require_keys_eq!(history.vault, old_vault_key, HydentityError::InvalidPolicyConfig);
+        let new_history = ctx.accounts.new_policy_history
+            .as_mut()
+            .ok_or(HydentityError::InvalidPolicyConfig)?;
+        history.vault = new_vault_key;
+        history.bump = ctx.bumps.new_policy_history.unwrap_or_default();
+        new_history.set_inner(history);
+        close_program_account(&old_history_info, &ctx.accounts.owner.to_account_info())?;
+    }
+
+    migrate_outflow_limit(
+        &ctx.accounts.old_outflow_limit.to_account_info(),
+        &ctx.accounts.new_outflow_limit.to_account_info(),
+        &ctx.accounts.old_vault,
+        &new_vault_key,
+        &Pubkey::default(),
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    // Move SPL balances, signed by the old vault authority
+    let old_authority_bump = ctx.accounts.old_vault_authority.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        VAULT_AUTH_SEED,
+        old_sns_name.as_ref(),
+        &[old_authority_bump],
+    ]];
+
+    let token_groups = ctx.remaining_accounts.chunks_exact(4);
+    require!(token_groups.remainder().is_empty(), HydentityError::InvalidMint);
+    let mut token_accounts_moved: u8 = 0;
+    for group in token_groups {
+        let old_token_account = Account::<TokenAccount>::try_from(&group[0])?;
+        let new_token_account = Account::<TokenAccount>::try_from(&group[1])?;
+        require_keys_eq!(old_token_account.owner, ctx.accounts.old_vault_authority.key(), HydentityError::InvalidMint);
+        require_keys_eq!(new_token_account.owner, ctx.accounts.new_vault_authority.key(), HydentityError::InvalidMint);
+        require_keys_eq!(old_token_account.mint, new_token_account.mint, HydentityError::InvalidMint);
+
+        migrate_outflow_limit(
+            &group[2],
+            &group[3],
+            &ctx.accounts.old_vault,
+            &new_vault_key,
+            &old_token_account.mint,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        if old_token_account.amount > 0 {
+            let cpi_accounts = token::Transfer {
+                from: group[0].clone(),
+                to: group[1].clone(),
+                authority: ctx.accounts.old_vault_authority.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            token::transfer(cpi_ctx, old_token_account.amount)?;
+        }
+
+        let cpi_accounts = token::CloseAccount {
+            account: group[0].clone(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: ctx.accounts.old_vault_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::close_account(cpi_ctx)?;
+
+        token_accounts_moved = token_accounts_moved
+            .checked_add(1)
+            .ok_or(HydentityError::ArithmeticOverflow)?;
+    }
+
+    // Move deposited SOL; the old authority's rent goes back to the owner on close
+    let old_authority_info = ctx.accounts.old_vault_authority.to_account_info();
+    let rent = Rent::get()?.minimum_balance(VaultAuthority::LEN);
+    let sol_moved = old_authority_info.lamports().saturating_sub(rent);
+    if sol_moved > 0 {
+        let new_authority_info = ctx.accounts.new_vault_authority.to_account_info();
+        **old_authority_info.try_borrow_mut_lamports()? -= sol_moved;
+        **new_authority_info.try_borrow_mut_lamports()? += sol_moved;
+    }
+
+    emit!(VaultRebound {
+        version: EVENT_SCHEMA_VERSION,
+        old_vault: old_vault_key,
+        new_vault: new_vault_key,
+        old_sns_name,
+        new_sns_name,
+        owner,
+        sol_moved,
+        token_accounts_moved,
+        timestamp: now,
+    });
+
+    log_info!("Vault rebound to a new domain ({} token accounts moved)", token_accounts_moved);
+    log_sensitive!("Vault rebound from {} to {}", old_sns_name, new_sns_name);
+
+    Ok(())
+}
@@ -1,18 +1,23 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{program::invoke_signed, instruction::Instruction};
-use crate::constants::{VAULT_SEED, VAULT_AUTH_SEED, SNS_NAME_PROGRAM_ID};
-use crate::state::{NameVault, VaultAuthority};
+use crate::constants::{VAULT_SEED, VAULT_AUTH_SEED, OWNER_SET_SEED, RECLAIM_CONFIG_SEED, SNS_NAME_PROGRAM_ID};
+use crate::state::{NameVault, VaultAuthority, VaultOwnerSet, ReclaimConfig, verify_owner_authority, validate_reclaim_destination};
 use crate::errors::HydentityError;
+use crate::events::{DomainReclaimed, EVENT_SCHEMA_VERSION};
 
 /// Reclaim domain ownership from the vault authority
 /// 
 /// This instruction transfers SNS domain ownership from the vault authority PDA
-/// back to a specified destination address. Only the vault owner can execute this.
+/// back to a specified destination address. Only the vault owner (or an owner set
+/// quorum) can execute this.
 /// 
 /// The vault authority PDA signs the SNS transfer instruction via CPI.
+/// Destinations that would strand the domain are rejected (see
+/// `validate_reclaim_destination`). Once a reclaim timelock is configured
+/// the domain must leave through `initiate_reclaim` instead.
 #[derive(Accounts)]
 pub struct ReclaimDomain<'info> {
-    /// The vault owner (must be signer)
+    /// The vault owner or an owner set member (must be signer)
     #[account(mut)]
     pub owner: Signer<'info>,
     
@@ -26,7 +31,6 @@ pub struct ReclaimDomain<'info> {
         mut,
         seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
         bump = vault.bump,
-        constraint = vault.owner == owner.key() @ HydentityError::Unauthorized,
         constraint = vault.domain_transferred @ HydentityError::DomainNotTransferred,
     )]
     pub vault: Account<'info, NameVault>,
@@ -39,7 +43,7 @@ pub struct ReclaimDomain<'info> {
     pub vault_authority: Account<'info, VaultAuthority>,
     
     /// The destination address for the domain (where ownership will be transferred)
-    /// CHECK: Can be any valid pubkey - the vault owner decides where to send
+    /// CHECK: Validated by validate_reclaim_destination
     pub destination: UncheckedAccount<'info>,
     
     /// SNS Name Program
@@ -48,17 +52,63 @@ pub struct ReclaimDomain<'info> {
         constraint = sns_name_program.key() == SNS_NAME_PROGRAM_ID @ HydentityError::InvalidSnsName
     )]
     pub sns_name_program: UncheckedAccount<'info>,
+    
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+    
+    /// Reclaim timelock config (required once configured)
+    #[account(
+        seeds = [RECLAIM_CONFIG_SEED, sns_name_account.key().as_ref()],
+        bump = reclaim_config.bump,
+        constraint = reclaim_config.vault == vault.key() @ HydentityError::ReclaimConfigRequired
+    )]
+    pub reclaim_config: Option<Account<'info, ReclaimConfig>>,
 }
 
-pub fn handler(ctx: Context<ReclaimDomain>) -> Result<()> {
+pub fn handler(ctx: Context<ReclaimDomain>, allow_program_owned: bool) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+    
+    ctx.accounts.vault.touch_owner_activity(clock.unix_timestamp);
+    
+    // A configured reclaim delay forces the two-step flow
+    if ctx.accounts.vault.has_reclaim_timelock {
+        let config = ctx.accounts.reclaim_config
+            .as_deref()
+            .ok_or(HydentityError::ReclaimConfigRequired)?;
+        require!(
+            config.active_delay(clock.unix_timestamp) == 0,
+            HydentityError::ReclaimRequiresQueue
+        );
+    }
+    
+    validate_reclaim_destination(
+        &ctx.accounts.destination,
+        &ctx.accounts.vault.key(),
+        &ctx.accounts.vault_authority.key(),
+        allow_program_owned,
+    )?;
+    
     let vault = &mut ctx.accounts.vault;
     let vault_authority = &ctx.accounts.vault_authority;
     let sns_name_account = &ctx.accounts.sns_name_account;
     let destination = &ctx.accounts.destination;
     
-    msg!("Reclaiming domain ownership from vault");
-    msg!("SNS name account: {}", sns_name_account.key());
-    msg!("Destination: {}", destination.key());
+    log_info!("Reclaiming domain ownership from vault");
+    log_sensitive!("SNS name account: {}", sns_name_account.key());
+    log_sensitive!("Destination: {}", destination.key());
     
     // Verify the SNS name account is currently owned by the vault authority
     // by checking the owner field in the name registry state
@@ -106,8 +156,16 @@ pub fn handler(ctx: Context<ReclaimDomain>) -> Result<()> {
     // Update vault state
     vault.set_domain_transferred(false);
     
-    msg!("Domain ownership successfully reclaimed");
-    msg!("New owner: {}", destination.key());
+    emit!(DomainReclaimed {
+        version: EVENT_SCHEMA_VERSION,
+        vault: vault.key(),
+        sns_name: sns_name_account.key(),
+        destination: destination.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    log_info!("Domain ownership successfully reclaimed");
+    log_sensitive!("New owner: {}", destination.key());
     
     Ok(())
 }
@@ -121,7 +179,7 @@ pub fn handler(ctx: Context<ReclaimDomain>) -> Result<()> {
 ///   1. Current owner (signer)
 /// 
 /// Note: The new owner is passed in instruction data, NOT as an account
-fn build_sns_transfer_instruction(
+pub(crate) fn build_sns_transfer_instruction(
     name_account: Pubkey,
     current_owner: Pubkey,
     new_owner: Pubkey,
@@ -141,3 +199,23 @@ fn build_sns_transfer_instruction(
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sns_transfer_instruction_layout() {
+        let name = Pubkey::new_unique();
+        let current_owner = Pubkey::new_unique();
+        let new_owner = Pubkey::new_unique();
+        let ix = build_sns_transfer_instruction(name, current_owner, new_owner);
+
+        assert_eq!(ix.program_id, SNS_NAME_PROGRAM_ID);
+        assert_eq!(ix.data[0], 2);
+        assert_eq!(&ix.data[1..], new_owner.as_ref());
+
+        // The name account is written, the current owner signs
+        assert!(ix.accounts[0].pubkey == name && ix.accounts[0].is_writable && !ix.accounts[0].is_signer);
+        assert!(ix.accounts[1].pubkey == current_owner && ix.accounts[1].is_signer);
+    }
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, OWNER_SET_SEED};
+use crate::errors::HydentityError;
+use crate::events::{OwnerSetRemoved, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, VaultOwnerSet, verify_owner_authority};
+
+/// Remove the vault's owner set, returning to single-owner control
+///
+/// Requires owner authority (single owner or owner set quorum).
+/// Closes the owner set account and returns rent to the caller.
+#[derive(Accounts)]
+pub struct RemoveOwnerSet<'info> {
+    /// The vault owner or an owner set member (must be signer)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault the owner set controls
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The owner set to close
+    #[account(
+        mut,
+        close = owner,
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Account<'info, VaultOwnerSet>,
+}
+
+pub fn handler(ctx: Context<RemoveOwnerSet>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        Some(&ctx.accounts.owner_set),
+        ctx.remaining_accounts,
+    )?;
+
+    ctx.accounts.vault.touch_owner_activity(clock.unix_timestamp);
+
+    ctx.accounts.vault.has_owner_set = false;
+
+    emit!(OwnerSetRemoved {
+        version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.vault.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    log_info!("Owner set removed, vault returned to single-owner control");
+
+    Ok(())
+}
@@ -13,10 +13,11 @@ pub enum ErrorCode {
     ClusterNotSet,
 }
 use crate::state::{
-    EncryptedVaultConfig, NameVault, PendingWithdrawal, WithdrawalRequest,
+    EncryptedVaultConfig, NameVault, PendingWithdrawal, PrivacyPolicy, RecoveryConfig, WithdrawalRequest,
     ENCRYPTED_CONFIG_SEED, PENDING_WITHDRAWAL_SEED, WITHDRAWAL_REQUEST_SEED,
+    enforce_outflow_limit, has_guardian_cosign,
 };
-use crate::events::{WithdrawalRequested, WithdrawalPlanGenerated};
+use crate::events::{WithdrawalRequested, WithdrawalPlanGenerated, EVENT_SCHEMA_VERSION};
 
 /// Computation definition offset for generate_withdrawal_plan
 /// Using offset 2 (fixed) - must match the uploadCircuit SDK offset
@@ -72,6 +73,8 @@ pub fn handler(
         HydentityError::Unauthorized
     );
 
+    ctx.accounts.vault.touch_owner_activity(clock.unix_timestamp);
+
     // Verify config is initialized
     require!(
         ctx.accounts.encrypted_config.is_initialized,
@@ -81,6 +84,21 @@ pub fn handler(
     // Verify sufficient balance
     require!(amount > 0, HydentityError::InvalidAmount);
 
+    // Every split must stay above the dust threshold
+    ctx.accounts.policy.verify_split_feasible(amount)?;
+
+    // The whole plan counts against the rolling 24h SOL outflow limit,
+    // since its splits are executed off-chain by the MPC cluster
+    enforce_outflow_limit(
+        &ctx.accounts.outflow_limit.to_account_info(),
+        &vault_key,
+        ctx.accounts.vault.rekeyed_at,
+        &Pubkey::default(),
+        amount,
+        clock.unix_timestamp,
+        has_guardian_cosign(ctx.accounts.recovery_config.as_deref(), ctx.accounts.guardian.as_deref()),
+    )?;
+
     // Initialize withdrawal request
     {
         let request = &mut ctx.accounts.withdrawal_request;
@@ -153,11 +171,12 @@ pub fn handler(
         0,
     )?;
     
-    msg!("Withdrawal requested from vault: {}", vault_key);
-    msg!("Amount: {} lamports", amount);
-    msg!("Computation offset: {}", computation_offset);
+    log_sensitive!("Withdrawal requested from vault: {}", vault_key);
+    log_sensitive!("Amount: {} lamports", amount);
+    log_info!("Computation offset: {}", computation_offset);
 
     emit!(WithdrawalRequested {
+        version: EVENT_SCHEMA_VERSION,
         vault: vault_key,
         amount,
         computation_offset,
@@ -178,6 +197,7 @@ pub struct RequestWithdrawal<'info> {
     
     /// The vault to withdraw from
     #[account(
+        mut,
         seeds = [VAULT_SEED, vault.sns_name.as_ref()],
         bump = vault.bump,
         constraint = vault.owner == owner.key() @ HydentityError::Unauthorized,
@@ -192,6 +212,14 @@ pub struct RequestWithdrawal<'info> {
     )]
     pub encrypted_config: Account<'info, EncryptedVaultConfig>,
     
+    /// The privacy policy (split feasibility check)
+    #[account(
+        seeds = [POLICY_SEED, vault.sns_name.as_ref()],
+        bump = policy.bump,
+        constraint = policy.vault == vault.key() @ HydentityError::InvalidPolicyConfig,
+    )]
+    pub policy: Account<'info, PrivacyPolicy>,
+    
     /// Withdrawal request account (created)
     #[account(
         init,
@@ -271,6 +299,22 @@ pub struct RequestWithdrawal<'info> {
     
     pub system_program: Program<'info, System>,
     pub arcium_program: Program<'info, Arcium>,
+    
+    /// SOL outflow limit PDA (may be uninitialized)
+    /// CHECK: Address and contents validated by enforce_outflow_limit
+    #[account(mut)]
+    pub outflow_limit: UncheckedAccount<'info>,
+    
+    /// Optional recovery config, for a guardian co-sign above the outflow limit
+    #[account(
+        seeds = [RECOVERY_CONFIG_SEED, vault.sns_name.as_ref()],
+        bump = recovery_config.bump,
+        constraint = recovery_config.vault == vault.key() @ HydentityError::InvalidRecoveryConfig
+    )]
+    pub recovery_config: Option<Account<'info, RecoveryConfig>>,
+    
+    /// Optional guardian co-signing an outflow above the limit
+    pub guardian: Option<Signer<'info>>,
 }
 
 /// Callback from Arcium after MPC generates withdrawal plan
@@ -310,11 +354,12 @@ pub fn generate_withdrawal_plan_callback(
     // Placeholder values for now - the actual encrypted plan is in the MPC output
     let total_splits = 2u8;
 
-    msg!("Withdrawal plan generated");
-    msg!("Plan ID: {:?}", plan_id);
-    msg!("Total splits: {}", total_splits);
+    log_info!("Withdrawal plan generated");
+    log_info!("Plan ID: {:?}", plan_id);
+    log_info!("Total splits: {}", total_splits);
 
     emit!(WithdrawalPlanGenerated {
+        version: EVENT_SCHEMA_VERSION,
         vault: request.vault,
         plan_id,
         total_splits,
@@ -1,15 +1,16 @@
 use anchor_lang::prelude::*;
-use crate::constants::{VAULT_SEED, DELEGATE_SEED};
+use crate::constants::{VAULT_SEED, DELEGATE_SEED, OWNER_SET_SEED};
 use crate::errors::HydentityError;
-use crate::state::{NameVault, DelegateSession};
+use crate::events::{DelegateRevoked, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, DelegateSession, VaultOwnerSet, verify_owner_authority};
 
 /// Revoke delegate permissions
 /// 
-/// Only the vault owner can revoke delegates. This closes the
-/// delegate session account and returns rent to the owner.
+/// Only the vault owner (or an owner set quorum) can revoke delegates.
+/// This closes the delegate session account and returns rent to the owner.
 #[derive(Accounts)]
 pub struct RevokeDelegate<'info> {
-    /// The vault owner (must be signer)
+    /// The vault owner or an owner set member (must be signer)
     #[account(mut)]
     pub owner: Signer<'info>,
     
@@ -19,10 +20,10 @@ pub struct RevokeDelegate<'info> {
     
     /// The vault (for ownership verification)
     #[account(
+        mut,
         seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
         bump = vault.bump,
-        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName,
-        constraint = vault.owner == owner.key() @ HydentityError::Unauthorized
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
     )]
     pub vault: Account<'info, NameVault>,
     
@@ -39,13 +40,41 @@ pub struct RevokeDelegate<'info> {
         constraint = delegate_session.vault == vault.key() @ HydentityError::DelegateNotFound
     )]
     pub delegate_session: Account<'info, DelegateSession>,
+    
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
 }
 
 pub fn handler(ctx: Context<RevokeDelegate>) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+    
+    ctx.accounts.vault.touch_owner_activity(clock.unix_timestamp);
+    
     let delegate = ctx.accounts.delegate.key();
     
-    msg!("Delegate revoked: {}", delegate);
-    msg!("Session closed, rent returned to owner");
+    emit!(DelegateRevoked {
+        version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.vault.key(),
+        delegate,
+        revoked_by: ctx.accounts.owner.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    log_sensitive!("Delegate revoked: {}", delegate);
+    log_info!("Session closed, rent returned to owner");
     
     Ok(())
 }
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, OWNER_SET_SEED, MIN_INACTIVITY_WINDOW_SECONDS};
+use crate::errors::HydentityError;
+use crate::events::{BeneficiaryUpdated, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, VaultOwnerSet, verify_owner_authority};
+
+/// Configure the vault's inactivity beneficiary
+///
+/// If the owner signs nothing for `inactivity_window_seconds`, the
+/// beneficiary can take over the vault with `claim_inheritance`.
+/// Passing the default pubkey removes the beneficiary.
+#[derive(Accounts)]
+pub struct SetBeneficiary<'info> {
+    /// The vault owner or an owner set member (must be signer)
+    pub owner: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault to configure
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+}
+
+pub fn handler(
+    ctx: Context<SetBeneficiary>,
+    beneficiary: Pubkey,
+    inactivity_window_seconds: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    // A sold domain freezes the previous owner until the buyer claims the vault
+    ctx.accounts.vault.verify_domain_control(&ctx.accounts.sns_name_account)?;
+
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.touch_owner_activity(now);
+
+    if beneficiary == Pubkey::default() {
+        vault.clear_beneficiary();
+    } else {
+        require!(!vault.is_owner(&beneficiary), HydentityError::InvalidInheritanceConfig);
+        require!(
+            inactivity_window_seconds >= MIN_INACTIVITY_WINDOW_SECONDS,
+            HydentityError::InvalidInheritanceConfig
+        );
+        vault.beneficiary = beneficiary;
+        vault.inactivity_window_seconds = inactivity_window_seconds;
+    }
+
+    emit!(BeneficiaryUpdated {
+        version: EVENT_SCHEMA_VERSION,
+        vault: vault.key(),
+        beneficiary: vault.beneficiary,
+        inactivity_window_seconds: vault.inactivity_window_seconds,
+        timestamp: now,
+    });
+
+    log_sensitive!("Beneficiary updated: {}", vault.beneficiary);
+    log_info!("Inactivity window: {}s", vault.inactivity_window_seconds);
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, OWNER_SET_SEED, OUTFLOW_LIMIT_SEED, OUTFLOW_WINDOW_SECONDS};
+use crate::errors::HydentityError;
+use crate::events::{OutflowLimitUpdated, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, OutflowLimit, VaultOwnerSet, verify_owner_authority};
+
+/// Set the rolling 24h outflow limit for one mint
+///
+/// `mint` is the default pubkey for SOL. A `daily_limit` of 0 removes
+/// the cap. Lowering the limit applies immediately; raising or removing
+/// it takes effect after a full 24h window. Limits set before the vault
+/// was sold no longer apply and can be replaced right away.
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct SetOutflowLimit<'info> {
+    /// The vault owner or an owner set member (must be signer, pays for the account)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault to limit
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The outflow limit PDA for this mint (created on first use)
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = OutflowLimit::LEN,
+        seeds = [OUTFLOW_LIMIT_SEED, vault.key().as_ref(), mint.as_ref()],
+        bump
+    )]
+    pub outflow_limit: Account<'info, OutflowLimit>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+}
+
+pub fn handler(ctx: Context<SetOutflowLimit>, mint: Pubkey, daily_limit: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    ctx.accounts.vault.touch_owner_activity(now);
+
+    let vault_key = ctx.accounts.vault.key();
+    let rekeyed_at = ctx.accounts.vault.rekeyed_at;
+    let limit = &mut ctx.accounts.outflow_limit;
+    // A limit left by a previous owner does not hold back the buyer
+    let first_time = limit.vault == Pubkey::default() || limit.is_stale(rekeyed_at);
+    limit.apply_matured(now);
+
+    let effective_at = if first_time || limit.is_tightening(daily_limit) {
+        limit.daily_limit = daily_limit;
+        limit.pending_daily_limit = 0;
+        limit.pending_effective_at = 0;
+        now
+    } else {
+        // Raising the cap waits out a full window
+        let effective_at = now
+            .checked_add(OUTFLOW_WINDOW_SECONDS)
+            .ok_or(HydentityError::ArithmeticOverflow)?;
+        limit.pending_daily_limit = daily_limit;
+        limit.pending_effective_at = effective_at;
+        effective_at
+    };
+
+    limit.vault = vault_key;
+    limit.mint = mint;
+    limit.updated_at = now;
+    limit.bump = ctx.bumps.outflow_limit;
+
+    emit!(OutflowLimitUpdated {
+        version: EVENT_SCHEMA_VERSION,
+        vault: vault_key,
+        mint,
+        daily_limit,
+        effective_at,
+        timestamp: now,
+    });
+
+    log_sensitive!("Outflow limit for mint {}: {} per 24h", mint, daily_limit);
+    log_info!("Effective at: {}", effective_at);
+
+    Ok(())
+}
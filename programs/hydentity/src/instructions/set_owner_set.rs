@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, OWNER_SET_SEED};
+use crate::errors::HydentityError;
+use crate::events::{OwnerSetUpdated, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, VaultOwnerSet, verify_owner_authority};
+
+/// Create or replace the vault's M-of-N owner set
+///
+/// Requires owner authority: the single vault owner, or a quorum of the
+/// current owner set (co-signers passed as remaining accounts). Once set,
+/// owner-gated instructions accept `threshold` signatures from the listed
+/// keys in place of the single owner.
+#[derive(Accounts)]
+pub struct SetOwnerSet<'info> {
+    /// The vault owner or an owner set member (must be signer, pays for the account)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault the owner set controls
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The owner set PDA (created on first use)
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = VaultOwnerSet::LEN,
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub owner_set: Account<'info, VaultOwnerSet>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<SetOwnerSet>,
+    signers: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    // A sold domain freezes the previous owner until the buyer claims the vault
+    ctx.accounts.vault.verify_domain_control(&ctx.accounts.sns_name_account)?;
+
+    // A freshly created set is not yet enabled on the vault,
+    // so only the single owner can create it
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        Some(&ctx.accounts.owner_set),
+        ctx.remaining_accounts,
+    )?;
+
+    ctx.accounts.vault.touch_owner_activity(now);
+
+    VaultOwnerSet::validate_config(&signers, threshold)?;
+
+    let vault_key = ctx.accounts.vault.key();
+    ctx.accounts.vault.has_owner_set = true;
+
+    let owner_set = &mut ctx.accounts.owner_set;
+    owner_set.vault = vault_key;
+    owner_set.sns_name = ctx.accounts.sns_name_account.key();
+    owner_set.signers = signers;
+    owner_set.threshold = threshold;
+    owner_set.updated_at = now;
+    owner_set.bump = ctx.bumps.owner_set;
+
+    emit!(OwnerSetUpdated {
+        version: EVENT_SCHEMA_VERSION,
+        vault: vault_key,
+        signer_count: owner_set.signers.len() as u8,
+        threshold,
+        timestamp: now,
+    });
+
+    log_info!("Owner set updated: {} of {}", threshold, owner_set.signers.len());
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, OWNER_SET_SEED, RECLAIM_CONFIG_SEED};
+use crate::errors::HydentityError;
+use crate::events::{ReclaimTimelockUpdated, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, ReclaimConfig, VaultOwnerSet, verify_owner_authority};
+
+/// Configure the domain reclaim timelock
+///
+/// A longer delay applies immediately. A shorter one, including disabling
+/// with `delay_seconds = 0`, only takes effect once the current delay has
+/// elapsed.
+#[derive(Accounts)]
+pub struct SetReclaimTimelock<'info> {
+    /// The vault owner or an owner set member (must be signer, pays for the account)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault to protect
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The timelock config PDA (created on first use)
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = ReclaimConfig::LEN,
+        seeds = [RECLAIM_CONFIG_SEED, sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub reclaim_config: Account<'info, ReclaimConfig>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+}
+
+pub fn handler(ctx: Context<SetReclaimTimelock>, delay_seconds: i64) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    ReclaimConfig::validate_delay(delay_seconds)?;
+
+    let vault_key = ctx.accounts.vault.key();
+    let first_time = !ctx.accounts.vault.has_reclaim_timelock;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.touch_owner_activity(now);
+    vault.has_reclaim_timelock = true;
+
+    let config = &mut ctx.accounts.reclaim_config;
+    config.apply_matured(now);
+
+    let effective_at = if first_time || delay_seconds >= config.delay_seconds {
+        config.delay_seconds = delay_seconds;
+        config.clear_pending();
+        now
+    } else {
+        // Shortening waits out the current delay
+        let effective_at = now
+            .checked_add(config.delay_seconds)
+            .ok_or(HydentityError::ArithmeticOverflow)?;
+        config.pending_delay_seconds = delay_seconds;
+        config.pending_effective_at = effective_at;
+        effective_at
+    };
+
+    config.vault = vault_key;
+    config.updated_at = now;
+    config.bump = ctx.bumps.reclaim_config;
+
+    emit!(ReclaimTimelockUpdated {
+        version: EVENT_SCHEMA_VERSION,
+        vault: vault_key,
+        delay_seconds,
+        effective_at,
+        timestamp: now,
+    });
+
+    log_info!("Reclaim timelock: delay {}s, effective at {}", delay_seconds, effective_at);
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, OWNER_SET_SEED, RECOVERY_CONFIG_SEED};
+use crate::errors::HydentityError;
+use crate::events::{RecoveryConfigUpdated, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, RecoveryConfig, VaultOwnerSet, verify_owner_authority};
+
+/// Register or replace the vault's recovery guardians
+///
+/// Guardians can jointly rotate `NameVault.owner` to a new key if the
+/// owner loses theirs. Only the owner (or an owner set quorum) can
+/// change the guardian list, threshold and veto timelock.
+#[derive(Accounts)]
+pub struct SetRecoveryConfig<'info> {
+    /// The vault owner or an owner set member (must be signer, pays for the account)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault being protected
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The recovery config PDA (created on first use)
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = RecoveryConfig::LEN,
+        seeds = [RECOVERY_CONFIG_SEED, sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub recovery_config: Account<'info, RecoveryConfig>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+}
+
+pub fn handler(
+    ctx: Context<SetRecoveryConfig>,
+    guardians: Vec<Pubkey>,
+    threshold: u8,
+    timelock_seconds: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    // A sold domain freezes the previous owner until the buyer claims the vault
+    ctx.accounts.vault.verify_domain_control(&ctx.accounts.sns_name_account)?;
+
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    ctx.accounts.vault.touch_owner_activity(now);
+
+    RecoveryConfig::validate_config(&guardians, threshold, timelock_seconds)?;
+
+    let vault_key = ctx.accounts.vault.key();
+    let recovery_config = &mut ctx.accounts.recovery_config;
+    recovery_config.vault = vault_key;
+    recovery_config.sns_name = ctx.accounts.sns_name_account.key();
+    recovery_config.guardians = guardians;
+    recovery_config.threshold = threshold;
+    recovery_config.timelock_seconds = timelock_seconds;
+    recovery_config.updated_at = now;
+    recovery_config.bump = ctx.bumps.recovery_config;
+
+    emit!(RecoveryConfigUpdated {
+        version: EVENT_SCHEMA_VERSION,
+        vault: vault_key,
+        guardian_count: recovery_config.guardians.len() as u8,
+        threshold,
+        timelock_seconds,
+        timestamp: now,
+    });
+
+    log_info!(
+        "Recovery guardians updated: {} of {}, timelock {}s",
+        threshold,
+        recovery_config.guardians.len(),
+        timelock_seconds
+    );
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, OWNER_SET_SEED};
+use crate::errors::HydentityError;
+use crate::events::{SubdomainPolicyUpdated, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, VaultOwnerSet, verify_owner_authority};
+
+/// Allow or forbid the parent domain owner to manage subdomain vaults
+///
+/// When enabled on a parent domain's vault, its owner can create vaults for
+/// subdomains with `initialize_subdomain_vault` and take over existing ones
+/// with `claim_vault`, even when a subdomain is owned by another key.
+#[derive(Accounts)]
+pub struct SetSubdomainPolicy<'info> {
+    /// The vault owner or an owner set member (must be signer)
+    pub owner: Signer<'info>,
+
+    /// The parent domain's SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The parent domain's vault
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+}
+
+pub fn handler(ctx: Context<SetSubdomainPolicy>, parent_controls_subdomains: bool) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    // A sold domain freezes the previous owner until the buyer claims the vault
+    ctx.accounts.vault.verify_domain_control(&ctx.accounts.sns_name_account)?;
+
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.touch_owner_activity(now);
+    vault.parent_controls_subdomains = parent_controls_subdomains;
+
+    emit!(SubdomainPolicyUpdated {
+        version: EVENT_SCHEMA_VERSION,
+        vault: vault.key(),
+        parent_controls_subdomains,
+        timestamp: now,
+    });
+
+    log_info!("Parent control over subdomain vaults: {}", parent_controls_subdomains);
+
+    Ok(())
+}
@@ -0,0 +1,399 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use solana_sha256_hasher::hashv;
+use crate::constants::{
+    VAULT_SEED, VAULT_AUTH_SEED, OWNER_SET_SEED,
+    SNS_NAME_PROGRAM_ID, SNS_RECORDS_PROGRAM_ID, SNS_HASH_PREFIX,
+};
+use crate::state::{NameVault, VaultAuthority, VaultOwnerSet, read_sns_owner, verify_owner_authority};
+use crate::errors::HydentityError;
+use crate::events::{SolRecordSet, EVENT_SCHEMA_VERSION};
+
+/// SNS records program instruction tags
+const RECORDS_IX_ALLOCATE_AND_POST: u8 = 1;
+const RECORDS_IX_EDIT: u8 = 2;
+const RECORDS_IX_VALIDATE_SOLANA_SIGNATURE: u8 = 3;
+
+/// Record V2 validation kind for a Solana signature
+const VALIDATION_SOLANA: u16 = 1;
+
+/// Point the domain's SOL record at the vault authority
+///
+/// Opt-in: wallets resolve `alice.sol` through its SOL record, so until
+/// this is called payments keep going wherever the record points. Writes
+/// the record through the SNS records program, signed by the domain owner
+/// (the signer, or the vault authority once it holds the domain), then has
+/// the domain owner validate staleness and the vault authority validate
+/// right of association so resolvers accept the record.
+#[derive(Accounts)]
+pub struct SetVaultSolRecord<'info> {
+    /// The vault owner or an owner set member (pays for the record)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The SNS name account the vault belongs to
+    /// CHECK: Must be owned by the SNS Name Program; owner field read manually
+    #[account(
+        constraint = sns_name_account.owner == &SNS_NAME_PROGRAM_ID @ HydentityError::InvalidSnsName
+    )]
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault account
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The vault authority PDA (the record's new content)
+    #[account(
+        seeds = [VAULT_AUTH_SEED, sns_name_account.key().as_ref()],
+        bump = vault_authority.bump,
+    )]
+    pub vault_authority: Account<'info, VaultAuthority>,
+
+    /// The domain's SOL record (V2), created if it does not exist yet
+    /// CHECK: Address derived from the domain in the constraint
+    #[account(
+        mut,
+        address = sol_record_key(&sns_name_account.key()) @ HydentityError::InvalidSnsName
+    )]
+    pub sol_record: UncheckedAccount<'info>,
+
+    /// The SNS records program's central state (class of every V2 record)
+    /// CHECK: Address derived in the constraint
+    #[account(
+        address = sns_records_central_state() @ HydentityError::InvalidSnsName
+    )]
+    pub records_central_state: UncheckedAccount<'info>,
+
+    /// SNS records program
+    /// CHECK: Validated by constraint
+    #[account(
+        constraint = sns_records_program.key() == SNS_RECORDS_PROGRAM_ID @ HydentityError::InvalidSnsName
+    )]
+    pub sns_records_program: UncheckedAccount<'info>,
+
+    /// SNS Name Program
+    /// CHECK: Validated by constraint
+    #[account(
+        constraint = sns_name_program.key() == SNS_NAME_PROGRAM_ID @ HydentityError::InvalidSnsName
+    )]
+    pub sns_name_program: UncheckedAccount<'info>,
+
+    /// System program (for allocating the record)
+    pub system_program: Program<'info, System>,
+
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+}
+
+pub fn handler(ctx: Context<SetVaultSolRecord>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // A sold domain freezes the previous owner until the buyer claims the vault
+    ctx.accounts.vault.verify_domain_control(&ctx.accounts.sns_name_account)?;
+
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    ctx.accounts.vault.touch_owner_activity(clock.unix_timestamp);
+
+    let sns_name = ctx.accounts.sns_name_account.key();
+    let vault_authority = ctx.accounts.vault_authority.key();
+
+    // Whoever owns the domain on SNS must sign the record write
+    let sns_owner = read_sns_owner(&ctx.accounts.sns_name_account)?;
+    let domain_owner = if sns_owner == vault_authority {
+        ctx.accounts.vault_authority.to_account_info()
+    } else {
+        require_keys_eq!(sns_owner, ctx.accounts.owner.key(), HydentityError::SnsOwnershipVerificationFailed);
+        ctx.accounts.owner.to_account_info()
+    };
+
+    let bump = ctx.accounts.vault_authority.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        VAULT_AUTH_SEED,
+        sns_name.as_ref(),
+        &[bump],
+    ]];
+
+    let record_accounts = RecordAccounts {
+        fee_payer: ctx.accounts.owner.key(),
+        record: ctx.accounts.sol_record.key(),
+        domain: sns_name,
+        domain_owner: domain_owner.key(),
+    };
+    let account_infos = [
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.sns_name_program.to_account_info(),
+        ctx.accounts.owner.to_account_info(),
+        ctx.accounts.sol_record.to_account_info(),
+        ctx.accounts.sns_name_account.to_account_info(),
+        domain_owner.clone(),
+        ctx.accounts.records_central_state.to_account_info(),
+        ctx.accounts.vault_authority.to_account_info(),
+        ctx.accounts.sns_records_program.to_account_info(),
+    ];
+
+    log_info!("Writing SOL record");
+
+    let tag = if ctx.accounts.sol_record.data_is_empty() {
+        RECORDS_IX_ALLOCATE_AND_POST
+    } else {
+        RECORDS_IX_EDIT
+    };
+    let write_ix = build_write_sol_record_instruction(tag, &record_accounts, &vault_authority);
+    invoke_signed(&write_ix, &account_infos, signer_seeds)
+        .map_err(|_| HydentityError::SolRecordWriteFailed)?;
+
+    // Staleness: the current domain owner vouches for the record
+    let staleness_ix = build_validate_signature_instruction(&record_accounts, &domain_owner.key(), true);
+    invoke_signed(&staleness_ix, &account_infos, signer_seeds)
+        .map_err(|_| HydentityError::SolRecordWriteFailed)?;
+
+    // Right of association: the vault authority accepts being the destination
+    let roa_ix = build_validate_signature_instruction(&record_accounts, &vault_authority, false);
+    invoke_signed(&roa_ix, &account_infos, signer_seeds)
+        .map_err(|_| HydentityError::SolRecordWriteFailed)?;
+
+    verify_sol_record(&ctx.accounts.sol_record, &domain_owner.key(), &vault_authority)?;
+
+    emit!(SolRecordSet {
+        version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.vault.key(),
+        sns_name,
+        record: ctx.accounts.sol_record.key(),
+        target: vault_authority,
+        timestamp: clock.unix_timestamp,
+    });
+
+    log_info!("SOL record now points at the vault authority");
+
+    Ok(())
+}
+
+/// The SNS records program's central state PDA
+///
+/// PDA Seeds (records program): [records_program_id]
+pub fn sns_records_central_state() -> Pubkey {
+    Pubkey::find_program_address(
+        &[SNS_RECORDS_PROGRAM_ID.as_ref()],
+        &SNS_RECORDS_PROGRAM_ID,
+    ).0
+}
+
+/// Address of a domain's SOL record (V2)
+///
+/// A V2 record is an SNS name account named "\x02SOL", with the records
+/// central state as class and the domain as parent.
+pub fn sol_record_key(domain: &Pubkey) -> Pubkey {
+    let hashed_name = hashv(&[SNS_HASH_PREFIX, b"\x02SOL"]).to_bytes();
+    let central_state = sns_records_central_state();
+    Pubkey::find_program_address(
+        &[&hashed_name, central_state.as_ref(), domain.as_ref()],
+        &SNS_NAME_PROGRAM_ID,
+    ).0
+}
+
+/// Accounts shared by every records program instruction used here
+struct RecordAccounts {
+    fee_payer: Pubkey,
+    record: Pubkey,
+    domain: Pubkey,
+    domain_owner: Pubkey,
+}
+
+impl RecordAccounts {
+    fn metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(anchor_lang::solana_program::system_program::ID, false),
+            AccountMeta::new_readonly(SNS_NAME_PROGRAM_ID, false),
+            AccountMeta::new(self.fee_payer, true),
+            AccountMeta::new(self.record, false),
+            AccountMeta::new_readonly(self.domain, false),
+            AccountMeta::new_readonly(self.domain_owner, true),
+            AccountMeta::new_readonly(sns_records_central_state(), false),
+        ]
+    }
+}
+
+/// Build an SNS records `AllocateAndPostRecord` or `EditRecord` instruction
+///
+/// Instruction format:
+/// - Data: [tag, record name (borsh string "SOL"), content (borsh bytes)]
+/// - Accounts: system program, name program, fee payer (writable, signer),
+///   record (writable), domain, domain owner (signer), central state
+fn build_write_sol_record_instruction(
+    tag: u8,
+    accounts: &RecordAccounts,
+    target: &Pubkey,
+) -> Instruction {
+    let record_name = b"SOL";
+    let mut data = Vec::with_capacity(1 + 4 + record_name.len() + 4 + 32);
+    data.push(tag);
+    data.extend_from_slice(&(record_name.len() as u32).to_le_bytes());
+    data.extend_from_slice(record_name);
+    data.extend_from_slice(&32u32.to_le_bytes());
+    data.extend_from_slice(target.as_ref());
+
+    Instruction {
+        program_id: SNS_RECORDS_PROGRAM_ID,
+        accounts: accounts.metas(),
+        data,
+    }
+}
+
+/// Build an SNS records `ValidateSolanaSignature` instruction
+///
+/// Instruction format:
+/// - Data: [3 (tag), staleness (bool)]
+/// - Accounts: as for writing, plus the verifier (signer). With `staleness`
+///   the verifier must be the domain owner, otherwise the record's content.
+fn build_validate_signature_instruction(
+    accounts: &RecordAccounts,
+    verifier: &Pubkey,
+    staleness: bool,
+) -> Instruction {
+    let mut metas = accounts.metas();
+    metas.push(AccountMeta::new_readonly(*verifier, true));
+
+    Instruction {
+        program_id: SNS_RECORDS_PROGRAM_ID,
+        accounts: metas,
+        data: vec![RECORDS_IX_VALIDATE_SOLANA_SIGNATURE, staleness as u8],
+    }
+}
+
+/// Check the written record points at the vault authority and is validated
+///
+/// Record V2 layout after the 96-byte name registry header:
+/// - staleness validation kind (u16), right of association kind (u16),
+///   content length (u32)
+/// - staleness validation id (32 bytes for Solana)
+/// - right of association id (32 bytes for Solana)
+/// - content
+fn verify_sol_record(record: &AccountInfo, domain_owner: &Pubkey, vault_authority: &Pubkey) -> Result<()> {
+    let data = record.try_borrow_data()?;
+    let read_u16 = |offset: usize| data.get(offset..offset + 2).map(|b| u16::from_le_bytes([b[0], b[1]]));
+    let read_key = |offset: usize| {
+        data.get(offset..offset + 32)
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            .map(Pubkey::new_from_array)
+    };
+
+    let header = 96;
+    require!(
+        read_u16(header) == Some(VALIDATION_SOLANA) && read_u16(header + 2) == Some(VALIDATION_SOLANA),
+        HydentityError::SolRecordMismatch
+    );
+
+    let staleness_id = read_key(header + 8);
+    let roa_id = read_key(header + 40);
+    let content = read_key(header + 72);
+    require!(
+        staleness_id == Some(*domain_owner)
+            && roa_id == Some(*vault_authority)
+            && content == Some(*vault_authority),
+        HydentityError::SolRecordMismatch
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record_accounts() -> RecordAccounts {
+        RecordAccounts {
+            fee_payer: Pubkey::new_unique(),
+            record: Pubkey::new_unique(),
+            domain: Pubkey::new_unique(),
+            domain_owner: Pubkey::new_unique(),
+        }
+    }
+
+    /// A V2 record validated by `domain_owner` (staleness) and `target` (right of association)
+    fn record_data(domain_owner: &Pubkey, target: &Pubkey, content: &Pubkey) -> Vec<u8> {
+        let mut data = vec![0u8; 96];
+        data.extend_from_slice(&VALIDATION_SOLANA.to_le_bytes());
+        data.extend_from_slice(&VALIDATION_SOLANA.to_le_bytes());
+        data.extend_from_slice(&32u32.to_le_bytes());
+        data.extend_from_slice(domain_owner.as_ref());
+        data.extend_from_slice(target.as_ref());
+        data.extend_from_slice(content.as_ref());
+        data
+    }
+
+    #[test]
+    fn test_write_instruction_encodes_record_and_content() {
+        let accounts = record_accounts();
+        let target = Pubkey::new_unique();
+        let ix = build_write_sol_record_instruction(RECORDS_IX_ALLOCATE_AND_POST, &accounts, &target);
+
+        assert_eq!(ix.program_id, SNS_RECORDS_PROGRAM_ID);
+        assert_eq!(ix.data[0], RECORDS_IX_ALLOCATE_AND_POST);
+        assert_eq!(&ix.data[1..8], &[3, 0, 0, 0, b'S', b'O', b'L']);
+        assert_eq!(&ix.data[8..12], &32u32.to_le_bytes());
+        assert_eq!(&ix.data[12..], target.as_ref());
+
+        // Fee payer and domain owner sign, the record is written
+        assert!(ix.accounts[2].is_signer && ix.accounts[2].is_writable);
+        assert!(ix.accounts[3].is_writable && ix.accounts[3].pubkey == accounts.record);
+        assert!(ix.accounts[5].is_signer && ix.accounts[5].pubkey == accounts.domain_owner);
+        assert_eq!(ix.accounts[6].pubkey, sns_records_central_state());
+    }
+
+    #[test]
+    fn test_validate_instruction_appends_verifier() {
+        let accounts = record_accounts();
+        let verifier = Pubkey::new_unique();
+        let ix = build_validate_signature_instruction(&accounts, &verifier, true);
+
+        assert_eq!(ix.data, vec![RECORDS_IX_VALIDATE_SOLANA_SIGNATURE, 1]);
+        assert_eq!(ix.accounts.len(), 8);
+        assert!(ix.accounts[7].is_signer && ix.accounts[7].pubkey == verifier);
+    }
+
+    #[test]
+    fn test_verify_sol_record_requires_both_validations() {
+        let key = Pubkey::new_unique();
+        let domain_owner = Pubkey::new_unique();
+        let vault_authority = Pubkey::new_unique();
+        let mut lamports = 0;
+
+        let mut data = record_data(&domain_owner, &vault_authority, &vault_authority);
+        let record = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &SNS_NAME_PROGRAM_ID, false, 0);
+        assert!(verify_sol_record(&record, &domain_owner, &vault_authority).is_ok());
+
+        // Content pointing elsewhere
+        let mut data = record_data(&domain_owner, &vault_authority, &Pubkey::new_unique());
+        let record = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &SNS_NAME_PROGRAM_ID, false, 0);
+        assert_eq!(
+            verify_sol_record(&record, &domain_owner, &vault_authority).unwrap_err(),
+            HydentityError::SolRecordMismatch.into()
+        );
+
+        // Right of association not validated
+        let mut data = record_data(&domain_owner, &vault_authority, &vault_authority);
+        data[98..100].copy_from_slice(&0u16.to_le_bytes());
+        let record = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &SNS_NAME_PROGRAM_ID, false, 0);
+        assert_eq!(
+            verify_sol_record(&record, &domain_owner, &vault_authority).unwrap_err(),
+            HydentityError::SolRecordMismatch.into()
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, OWNER_SET_SEED, WITHDRAWAL_CONFIG_SEED};
+use crate::errors::HydentityError;
+use crate::events::{WithdrawalTimelockUpdated, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, DirectWithdrawalConfig, VaultOwnerSet, verify_owner_authority};
+
+/// Configure the direct withdrawal timelock
+///
+/// Tightening (lower threshold, longer delay, same security key) applies
+/// immediately. Loosening, including disabling with `delay_seconds = 0`,
+/// only takes effect once the current delay has elapsed.
+#[derive(Accounts)]
+pub struct SetWithdrawalTimelock<'info> {
+    /// The vault owner or an owner set member (must be signer, pays for the account)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault to protect
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The timelock config PDA (created on first use)
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = DirectWithdrawalConfig::LEN,
+        seeds = [WITHDRAWAL_CONFIG_SEED, sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub withdrawal_config: Account<'info, DirectWithdrawalConfig>,
+
+    /// System program for account creation
+    pub system_program: Program<'info, System>,
+
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+}
+
+pub fn handler(
+    ctx: Context<SetWithdrawalTimelock>,
+    threshold: u64,
+    delay_seconds: i64,
+    security_key: Pubkey,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    DirectWithdrawalConfig::validate_delay(delay_seconds)?;
+
+    let vault_key = ctx.accounts.vault.key();
+    let first_time = !ctx.accounts.vault.has_withdrawal_timelock;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.touch_owner_activity(now);
+    vault.has_withdrawal_timelock = true;
+
+    let config = &mut ctx.accounts.withdrawal_config;
+    config.apply_matured(now);
+
+    let effective_at = if first_time || config.is_tightening(threshold, delay_seconds, &security_key) {
+        config.threshold = threshold;
+        config.delay_seconds = delay_seconds;
+        config.security_key = security_key;
+        config.clear_pending();
+        now
+    } else {
+        // Loosening waits out the current delay
+        let effective_at = now
+            .checked_add(config.delay_seconds)
+            .ok_or(HydentityError::ArithmeticOverflow)?;
+        config.pending_threshold = threshold;
+        config.pending_delay_seconds = delay_seconds;
+        config.pending_security_key = security_key;
+        config.pending_effective_at = effective_at;
+        effective_at
+    };
+
+    config.vault = vault_key;
+    config.updated_at = now;
+    config.bump = ctx.bumps.withdrawal_config;
+
+    emit!(WithdrawalTimelockUpdated {
+        version: EVENT_SCHEMA_VERSION,
+        vault: vault_key,
+        threshold,
+        delay_seconds,
+        security_key,
+        effective_at,
+        timestamp: now,
+    });
+
+    log_sensitive!("Direct withdrawal timelock: threshold {}, delay {}s", threshold, delay_seconds);
+    log_info!("Effective at: {}", effective_at);
+
+    Ok(())
+}
@@ -42,6 +42,7 @@ pub fn handler(
     ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
 
     let clock = Clock::get()?;
+    ctx.accounts.vault.touch_owner_activity(clock.unix_timestamp);
     let vault_pubkey = ctx.accounts.vault.key().to_bytes();
 
     let args = ArgBuilder::new()
@@ -66,8 +67,8 @@ pub fn handler(
         0,
     )?;
 
-    msg!("Queued Arcium computation for config storage");
-    msg!("Computation offset: {}", computation_offset);
+    log_info!("Queued Arcium computation for config storage");
+    log_info!("Computation offset: {}", computation_offset);
 
     Ok(())
 }
@@ -82,6 +83,7 @@ pub struct StorePrivateConfig<'info> {
 
     /// The vault this config is for
     #[account(
+        mut,
         seeds = [VAULT_SEED, vault.sns_name.as_ref()],
         bump = vault.bump,
         constraint = vault.owner == payer.key() @ HydentityError::Unauthorized
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::constants::{VAULT_SEED, VAULT_AUTH_SEED, SNS_NAME_PROGRAM_ID};
+use crate::state::{NameVault, VaultAuthority, read_sns_owner, resolve_domain_holder};
+use crate::errors::HydentityError;
+use crate::events::{DomainStateSynced, EVENT_SCHEMA_VERSION};
+
+/// Reconcile the vault's domain flags with the actual SNS owner
+///
+/// Permissionless: anyone can call this after the domain moved outside
+/// Hydentity (e.g. a transfer with the Bonfida SDK or a marketplace sale).
+/// Sets `domain_transferred` if the vault authority owns the domain and
+/// `owner_lost_domain` if neither the vault owner nor the vault authority
+/// does. Emits `DomainStateSynced` only when a flag changes.
+///
+/// For a tokenized domain the holder is the owner of the token account
+/// holding the domain's NFT, which must be passed as `nft_token_account`.
+#[derive(Accounts)]
+pub struct SyncDomainState<'info> {
+    /// The SNS name account the vault belongs to
+    /// CHECK: Must be owned by the SNS Name Program; owner field read manually
+    #[account(
+        constraint = sns_name_account.owner == &SNS_NAME_PROGRAM_ID @ HydentityError::InvalidSnsName
+    )]
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault account
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The vault authority PDA
+    #[account(
+        seeds = [VAULT_AUTH_SEED, sns_name_account.key().as_ref()],
+        bump = vault_authority.bump,
+    )]
+    pub vault_authority: Account<'info, VaultAuthority>,
+
+    /// Token account holding the domain's NFT (tokenized domains only)
+    pub nft_token_account: Option<Account<'info, TokenAccount>>,
+}
+
+pub fn handler(ctx: Context<SyncDomainState>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    let sns_owner = read_sns_owner(&ctx.accounts.sns_name_account)?;
+    let domain_holder = resolve_domain_holder(
+        &ctx.accounts.sns_name_account,
+        ctx.accounts.nft_token_account.as_deref(),
+    )?;
+    let vault_authority = ctx.accounts.vault_authority.key();
+
+    let vault = &mut ctx.accounts.vault;
+    if !vault.sync_domain_state(&domain_holder, &vault_authority) {
+        log_info!("Domain state already in sync");
+        return Ok(());
+    }
+
+    emit!(DomainStateSynced {
+        version: EVENT_SCHEMA_VERSION,
+        vault: vault.key(),
+        sns_owner,
+        domain_transferred: vault.domain_transferred,
+        owner_lost_domain: vault.owner_lost_domain,
+        timestamp: now,
+    });
+
+    log_info!(
+        "Domain state synced: transferred {}, owner lost domain {}",
+        vault.domain_transferred,
+        vault.owner_lost_domain
+    );
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::constants::VAULT_SEED;
+use crate::errors::HydentityError;
+use crate::state::{NameVault, add_to_owner_index};
+
+/// Add a vault to its current owner's index
+///
+/// Permissionless: it only records who owns the vault today. Used to list
+/// vaults created before the owner index existed; later ownership changes
+/// keep the index up to date on their own.
+#[derive(Accounts)]
+pub struct SyncOwnerIndex<'info> {
+    /// Any caller (pays for creating or growing the index)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault to list
+    #[account(
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The vault owner's index
+    /// CHECK: Address and contents validated by add_to_owner_index
+    #[account(mut)]
+    pub owner_index: UncheckedAccount<'info>,
+
+    /// System program for creating or growing the index
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SyncOwnerIndex>) -> Result<()> {
+    add_to_owner_index(
+        &ctx.accounts.owner_index,
+        &ctx.accounts.vault.owner,
+        &ctx.accounts.vault.key(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    log_sensitive!("Vault {} listed for owner {}", ctx.accounts.vault.key(), ctx.accounts.vault.owner);
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke;
+use crate::constants::{VAULT_SEED, VAULT_AUTH_SEED, OWNER_SET_SEED, SNS_NAME_PROGRAM_ID};
+use crate::state::{NameVault, VaultAuthority, VaultOwnerSet, read_sns_owner, verify_owner_authority};
+use crate::errors::HydentityError;
+use crate::events::{DomainTransferred, EVENT_SCHEMA_VERSION};
+use super::reclaim_domain::build_sns_transfer_instruction;
+
+/// Transfer the SNS domain to the vault authority and mark it transferred
+///
+/// Single-step alternative to transferring the domain with the Bonfida SDK
+/// and then calling `mark_domain_transferred`. The signer must be the
+/// current SNS owner of the domain and signs the SNS transfer CPI, so the
+/// flag can never disagree with the actual transfer.
+#[derive(Accounts)]
+pub struct TransferDomainToVault<'info> {
+    /// The vault owner or an owner set member who currently owns the domain
+    pub owner: Signer<'info>,
+
+    /// The SNS name account to transfer
+    /// CHECK: Must be owned by the SNS Name Program; owner field read manually
+    #[account(
+        mut,
+        constraint = sns_name_account.owner == &SNS_NAME_PROGRAM_ID @ HydentityError::InvalidSnsName
+    )]
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The vault account (updates the domain_transferred flag)
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = !vault.domain_transferred @ HydentityError::DomainAlreadyTransferred,
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The vault authority PDA (new owner of the SNS domain)
+    #[account(
+        seeds = [VAULT_AUTH_SEED, sns_name_account.key().as_ref()],
+        bump = vault_authority.bump,
+    )]
+    pub vault_authority: Account<'info, VaultAuthority>,
+
+    /// SNS Name Program
+    /// CHECK: Validated by constraint
+    #[account(
+        constraint = sns_name_program.key() == SNS_NAME_PROGRAM_ID @ HydentityError::InvalidSnsName
+    )]
+    pub sns_name_program: UncheckedAccount<'info>,
+
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+}
+
+pub fn handler(ctx: Context<TransferDomainToVault>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    ctx.accounts.vault.touch_owner_activity(clock.unix_timestamp);
+
+    let sns_name_account = &ctx.accounts.sns_name_account;
+    let vault_authority = &ctx.accounts.vault_authority;
+    let owner = &ctx.accounts.owner;
+
+    log_info!("Transferring domain ownership to vault");
+
+    // The signer must currently own the domain to sign the SNS transfer
+    require!(
+        read_sns_owner(sns_name_account)? == owner.key(),
+        HydentityError::SnsOwnershipVerificationFailed
+    );
+
+    let transfer_ix = build_sns_transfer_instruction(
+        sns_name_account.key(),
+        owner.key(),
+        vault_authority.key(),
+    );
+
+    invoke(
+        &transfer_ix,
+        &[
+            sns_name_account.to_account_info(),
+            owner.to_account_info(),
+        ],
+    ).map_err(|_| HydentityError::SnsTransferFailed)?;
+
+    // Confirm the name registry now points at the vault authority
+    require!(
+        read_sns_owner(sns_name_account)? == vault_authority.key(),
+        HydentityError::SnsTransferFailed
+    );
+
+    let vault = &mut ctx.accounts.vault;
+    vault.set_domain_transferred(true);
+
+    emit!(DomainTransferred {
+        version: EVENT_SCHEMA_VERSION,
+        vault: vault.key(),
+        sns_name: sns_name_account.key(),
+        vault_authority: vault_authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    log_info!("Domain ownership transferred to vault");
+
+    Ok(())
+}
@@ -1,10 +1,17 @@
 use anchor_lang::prelude::*;
-use crate::constants::{VAULT_SEED, POLICY_SEED, DELEGATE_SEED, PERMISSION_UPDATE_POLICY};
+use crate::constants::{
+    VAULT_SEED, POLICY_SEED, POLICY_HISTORY_SEED, DELEGATE_SEED, OWNER_SET_SEED, PERMISSION_UPDATE_POLICY,
+    MAX_DESTINATIONS,
+};
 use crate::errors::HydentityError;
-use crate::state::{NameVault, PrivacyPolicy, DelegateSession, Distribution, PrivacyMode, DestinationMode};
+use crate::events::{PolicyUpdated, EVENT_SCHEMA_VERSION};
+use crate::state::{
+    NameVault, PrivacyPolicy, PolicyHistory, PolicyActor, DelegateSession, VaultOwnerSet, Distribution,
+    PrivacyMode, DestinationMode,
+};
 
 /// Parameters for updating the privacy policy
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
 pub struct UpdatePolicyParams {
     /// Whether privacy routing is enabled
     pub enabled: Option<bool>,
@@ -32,12 +39,93 @@ pub struct UpdatePolicyParams {
     
     /// List of destination addresses for claims
     pub destinations: Option<Vec<Pubkey>>,
+    
+    /// Restrict direct withdrawals to the destination list
+    pub enforce_destination_allowlist: Option<bool>,
+    
+    /// Delay before a changed destination list is usable for direct withdrawals
+    pub allowlist_delay_seconds: Option<u32>,
+    
+    /// Activation delay for future policy updates (0 disables the timelock)
+    pub policy_update_delay_seconds: Option<u32>,
+}
+
+impl UpdatePolicyParams {
+    /// Serialized size with a full destination list
+    pub const MAX_LEN: usize = 2 + // enabled
+        2 + // min_splits
+        2 + // max_splits
+        5 + // min_delay_seconds
+        5 + // max_delay_seconds
+        2 + // distribution
+        2 + // privacy_mode
+        2 + // destination_mode
+        1 + 4 + (32 * MAX_DESTINATIONS) + // destinations
+        2 + // enforce_destination_allowlist
+        5 + // allowlist_delay_seconds
+        5;  // policy_update_delay_seconds
+    
+    /// Whether the update changes the destination allowlist
+    pub fn touches_allowlist(&self) -> bool {
+        self.destinations.is_some()
+            || self.enforce_destination_allowlist.is_some()
+            || self.allowlist_delay_seconds.is_some()
+    }
+    
+    /// Once the allowlist is enforced, only the owner can change it
+    pub fn verify_allowlist_access(&self, policy: &PrivacyPolicy, owner_signed: bool) -> Result<()> {
+        if !owner_signed && policy.enforce_destination_allowlist && self.touches_allowlist() {
+            return Err(HydentityError::AllowlistRequiresOwner.into());
+        }
+        Ok(())
+    }
+    
+    /// Apply the provided fields to a policy (without bumping the nonce)
+    pub fn apply(&self, policy: &mut PrivacyPolicy, now: i64) {
+        if let Some(enabled) = self.enabled {
+            policy.enabled = enabled;
+        }
+        if let Some(min_splits) = self.min_splits {
+            policy.min_splits = min_splits;
+        }
+        if let Some(max_splits) = self.max_splits {
+            policy.max_splits = max_splits;
+        }
+        if let Some(min_delay) = self.min_delay_seconds {
+            policy.min_delay_seconds = min_delay;
+        }
+        if let Some(max_delay) = self.max_delay_seconds {
+            policy.max_delay_seconds = max_delay;
+        }
+        if let Some(distribution) = self.distribution {
+            policy.distribution = distribution;
+        }
+        if let Some(privacy_mode) = self.privacy_mode {
+            policy.privacy_mode = privacy_mode;
+        }
+        if let Some(destination_mode) = self.destination_mode {
+            policy.destination_mode = destination_mode;
+        }
+        if let Some(destinations) = &self.destinations {
+            policy.set_destinations(destinations.clone(), now);
+        }
+        if let Some(enforce) = self.enforce_destination_allowlist {
+            policy.enforce_destination_allowlist = enforce;
+        }
+        if let Some(delay) = self.allowlist_delay_seconds {
+            policy.allowlist_delay_seconds = delay;
+        }
+        if let Some(delay) = self.policy_update_delay_seconds {
+            policy.policy_update_delay_seconds = delay;
+        }
+        policy.refresh_preset();
+    }
 }
 
 /// Update the privacy policy for a vault
 #[derive(Accounts)]
 pub struct UpdatePolicy<'info> {
-    /// The caller (owner or delegate)
+    /// The caller (owner, owner set member or delegate)
     #[account(mut)]
     pub authority: Signer<'info>,
     
@@ -47,6 +135,7 @@ pub struct UpdatePolicy<'info> {
     
     /// The vault (for ownership verification)
     #[account(
+        mut,
         seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
         bump = vault.bump,
         constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
@@ -62,67 +151,121 @@ pub struct UpdatePolicy<'info> {
     )]
     pub policy: Account<'info, PrivacyPolicy>,
     
+    /// Audit trail of policy changes (created on first use)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = PolicyHistory::LEN,
+        seeds = [POLICY_HISTORY_SEED, sns_name_account.key().as_ref()],
+        bump
+    )]
+    pub policy_history: Box<Account<'info, PolicyHistory>>,
+    
+    /// System program (for creating the history account)
+    pub system_program: Program<'info, System>,
+    
     /// Optional delegate session (if caller is not the owner)
+    /// Child sessions must pass their parent sessions as remaining accounts
     #[account(
         seeds = [DELEGATE_SEED, sns_name_account.key().as_ref(), authority.key().as_ref()],
         bump = delegate_session.bump,
         constraint = delegate_session.vault == vault.key() @ HydentityError::UnauthorizedDelegate
     )]
     pub delegate_session: Option<Account<'info, DelegateSession>>,
+    
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+}
+
+/// Check that `authority` may change the policy
+///
+/// Returns how the caller was authorized: as the owner, an owner set
+/// quorum, or a delegate with `PERMISSION_UPDATE_POLICY`.
+pub fn authorize_policy_change(
+    vault: &NameVault,
+    authority: &AccountInfo,
+    delegate_session: Option<&DelegateSession>,
+    owner_set: Option<&VaultOwnerSet>,
+    remaining_accounts: &[AccountInfo],
+    now: i64,
+) -> Result<PolicyActor> {
+    let authority_key = authority.key();
+    
+    if vault.is_owner(&authority_key) {
+        // Owner has full access
+        Ok(PolicyActor::Owner)
+    } else if let Some(owner_set) = owner_set.filter(|s| vault.has_owner_set && s.is_member(&authority_key)) {
+        // Owner set members act as owner once the threshold is met
+        owner_set.verify_quorum(authority, remaining_accounts)?;
+        Ok(PolicyActor::OwnerSet)
+    } else if let Some(delegate) = delegate_session {
+        // Validate delegate permission
+        delegate.validate_permission(PERMISSION_UPDATE_POLICY, now, vault, remaining_accounts)?;
+        Ok(PolicyActor::Delegate)
+    } else {
+        Err(HydentityError::Unauthorized.into())
+    }
 }
 
 pub fn handler(ctx: Context<UpdatePolicy>, params: UpdatePolicyParams) -> Result<()> {
     let clock = Clock::get()?;
     let now = clock.unix_timestamp;
     
-    let authority = ctx.accounts.authority.key();
-    let vault = &ctx.accounts.vault;
+    // A sold domain freezes the previous owner until the buyer claims the vault
+    ctx.accounts.vault.verify_domain_control(&ctx.accounts.sns_name_account)?;
     
     // Check authorization
-    if vault.is_owner(&authority) {
-        // Owner has full access
-    } else if let Some(delegate) = &ctx.accounts.delegate_session {
-        // Validate delegate permission
-        delegate.validate_permission(PERMISSION_UPDATE_POLICY, now)?;
-    } else {
-        return Err(HydentityError::Unauthorized.into());
+    let actor = authorize_policy_change(
+        &ctx.accounts.vault,
+        &ctx.accounts.authority.to_account_info(),
+        ctx.accounts.delegate_session.as_deref(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+        now,
+    )?;
+    let owner_signed = actor.is_owner();
+    params.verify_allowlist_access(&ctx.accounts.policy, owner_signed)?;
+    
+    // Timelocked policies only change through queue_policy_update
+    if ctx.accounts.policy.is_timelocked() {
+        return Err(HydentityError::PolicyUpdateRequiresQueue.into());
+    }
+    
+    // Delegate actions do not count as owner activity
+    if owner_signed {
+        ctx.accounts.vault.touch_owner_activity(now);
     }
     
     let policy = &mut ctx.accounts.policy;
+    let previous = (**policy).clone();
     
     // Apply updates
-    if let Some(enabled) = params.enabled {
-        policy.enabled = enabled;
-    }
-    if let Some(min_splits) = params.min_splits {
-        policy.min_splits = min_splits;
-    }
-    if let Some(max_splits) = params.max_splits {
-        policy.max_splits = max_splits;
-    }
-    if let Some(min_delay) = params.min_delay_seconds {
-        policy.min_delay_seconds = min_delay;
-    }
-    if let Some(max_delay) = params.max_delay_seconds {
-        policy.max_delay_seconds = max_delay;
-    }
-    if let Some(distribution) = params.distribution {
-        policy.distribution = distribution;
-    }
-    if let Some(privacy_mode) = params.privacy_mode {
-        policy.privacy_mode = privacy_mode;
-    }
-    if let Some(destination_mode) = params.destination_mode {
-        policy.destination_mode = destination_mode;
-    }
-    if let Some(destinations) = params.destinations {
-        policy.destinations = destinations;
-    }
+    params.apply(policy, now);
     
     // Validate and update nonce
-    policy.update(now)?;
+    policy.update(&previous, now)?;
+    
+    // Record the change
+    let history = &mut ctx.accounts.policy_history;
+    history.initialize_if_needed(policy.vault, ctx.bumps.policy_history);
+    history.record(policy, ctx.accounts.authority.key(), actor, now)?;
+    
+    emit!(PolicyUpdated {
+        version: EVENT_SCHEMA_VERSION,
+        vault: policy.vault,
+        authority: ctx.accounts.authority.key(),
+        actor_type: actor,
+        policy_nonce: policy.policy_nonce,
+        timestamp: now,
+    });
     
-    msg!("Policy updated. New nonce: {}", policy.policy_nonce);
+    log_info!("Policy updated. New nonce: {}", policy.policy_nonce);
     
     Ok(())
 }
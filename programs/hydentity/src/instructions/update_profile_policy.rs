@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use crate::constants::{VAULT_SEED, POLICY_SEED, OWNER_SET_SEED, VAULT_PROFILE_SEED};
+use crate::errors::HydentityError;
+use crate::events::{PolicyUpdated, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, PrivacyPolicy, PolicyActor, VaultOwnerSet, VaultProfile, verify_owner_authority};
+use super::update_policy::UpdatePolicyParams;
+
+/// Update the privacy policy of a vault profile
+///
+/// Owner or owner set only. Profile policies have no update queue, so
+/// `policy_update_delay_seconds` cannot be set on them.
+#[derive(Accounts)]
+#[instruction(profile_id: u8)]
+pub struct UpdateProfilePolicy<'info> {
+    /// The vault owner or an owner set member (must be signer)
+    pub owner: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The domain's vault
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The profile whose policy is updated
+    #[account(
+        seeds = [VAULT_PROFILE_SEED, sns_name_account.key().as_ref(), &[profile_id]],
+        bump = profile.bump,
+        constraint = profile.vault == vault.key() @ HydentityError::ProfileNotFound
+    )]
+    pub profile: Account<'info, VaultProfile>,
+
+    /// The profile's privacy policy
+    #[account(
+        mut,
+        seeds = [POLICY_SEED, sns_name_account.key().as_ref(), &[profile_id]],
+        bump = profile_policy.bump,
+        constraint = profile_policy.vault == profile.key() @ HydentityError::InvalidPolicyConfig
+    )]
+    pub profile_policy: Account<'info, PrivacyPolicy>,
+
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+}
+
+pub fn handler(ctx: Context<UpdateProfilePolicy>, _profile_id: u8, params: UpdatePolicyParams) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+
+    // A sold domain freezes the previous owner until the buyer claims the vault
+    ctx.accounts.vault.verify_domain_control(&ctx.accounts.sns_name_account)?;
+
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    ctx.accounts.vault.touch_owner_activity(now);
+
+    require!(
+        params.policy_update_delay_seconds.unwrap_or(0) == 0,
+        HydentityError::InvalidPolicyConfig
+    );
+
+    let actor = if ctx.accounts.vault.is_owner(&ctx.accounts.owner.key()) {
+        PolicyActor::Owner
+    } else {
+        PolicyActor::OwnerSet
+    };
+
+    let policy = &mut ctx.accounts.profile_policy;
+    let previous = (**policy).clone();
+    params.apply(policy, now);
+    policy.update(&previous, now)?;
+
+    emit!(PolicyUpdated {
+        version: EVENT_SCHEMA_VERSION,
+        vault: policy.vault,
+        authority: ctx.accounts.owner.key(),
+        actor_type: actor,
+        policy_nonce: policy.policy_nonce,
+        timestamp: now,
+    });
+
+    log_info!("Profile policy updated. New nonce: {}", policy.policy_nonce);
+
+    Ok(())
+}
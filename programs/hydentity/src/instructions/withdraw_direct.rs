@@ -1,8 +1,12 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
-use crate::constants::{VAULT_SEED, VAULT_AUTH_SEED};
+use crate::constants::{VAULT_SEED, VAULT_AUTH_SEED, POLICY_SEED, OWNER_SET_SEED, RECOVERY_CONFIG_SEED, WITHDRAWAL_CONFIG_SEED};
 use crate::errors::HydentityError;
-use crate::state::{NameVault, VaultAuthority};
+use crate::events::{DirectWithdrawal, EVENT_SCHEMA_VERSION};
+use crate::state::{
+    NameVault, VaultAuthority, PrivacyPolicy, VaultOwnerSet, DirectWithdrawalConfig, RecoveryConfig,
+    verify_owner_authority, enforce_outflow_limit, has_guardian_cosign,
+};
 
 /// Emergency direct withdrawal (bypasses privacy)
 /// 
@@ -10,10 +14,10 @@ use crate::state::{NameVault, VaultAuthority};
 /// without going through Umbra. This is a fallback mechanism for
 /// recovering funds if off-chain services fail.
 /// 
-/// ONLY the vault owner can execute this - delegates cannot.
+/// ONLY the vault owner (or an owner set quorum) can execute this - delegates cannot.
 #[derive(Accounts)]
 pub struct WithdrawDirect<'info> {
-    /// The vault owner (must be signer)
+    /// The vault owner or an owner set member (must be signer)
     #[account(mut)]
     pub owner: Signer<'info>,
     
@@ -26,8 +30,7 @@ pub struct WithdrawDirect<'info> {
         mut,
         seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
         bump = vault.bump,
-        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName,
-        constraint = vault.owner == owner.key() @ HydentityError::Unauthorized
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
     )]
     pub vault: Account<'info, NameVault>,
     
@@ -40,6 +43,14 @@ pub struct WithdrawDirect<'info> {
     )]
     pub vault_authority: Account<'info, VaultAuthority>,
     
+    /// The privacy policy (destination allowlist)
+    #[account(
+        seeds = [POLICY_SEED, sns_name_account.key().as_ref()],
+        bump = policy.bump,
+        constraint = policy.vault == vault.key() @ HydentityError::InvalidPolicyConfig
+    )]
+    pub policy: Account<'info, PrivacyPolicy>,
+    
     /// The destination for the withdrawal
     /// CHECK: Any valid account can receive funds
     #[account(mut)]
@@ -61,6 +72,39 @@ pub struct WithdrawDirect<'info> {
     
     /// System program for SOL transfers
     pub system_program: Program<'info, System>,
+    
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+    
+    /// Direct withdrawal timelock config (required once configured)
+    #[account(
+        seeds = [WITHDRAWAL_CONFIG_SEED, sns_name_account.key().as_ref()],
+        bump = withdrawal_config.bump,
+        constraint = withdrawal_config.vault == vault.key() @ HydentityError::WithdrawalConfigRequired
+    )]
+    pub withdrawal_config: Option<Account<'info, DirectWithdrawalConfig>>,
+    
+    /// Outflow limit PDA for the asset being moved (may be uninitialized)
+    /// CHECK: Address and contents validated by enforce_outflow_limit
+    #[account(mut)]
+    pub outflow_limit: UncheckedAccount<'info>,
+    
+    /// Optional recovery config, for a guardian co-sign above the outflow limit
+    #[account(
+        seeds = [RECOVERY_CONFIG_SEED, sns_name_account.key().as_ref()],
+        bump = recovery_config.bump,
+        constraint = recovery_config.vault == vault.key() @ HydentityError::InvalidRecoveryConfig
+    )]
+    pub recovery_config: Option<Account<'info, RecoveryConfig>>,
+    
+    /// Optional guardian co-signing an outflow above the limit
+    pub guardian: Option<Signer<'info>>,
 }
 
 pub fn handler(
@@ -68,9 +112,56 @@ pub fn handler(
     amount: u64,
     _mint: Option<Pubkey>,
 ) -> Result<()> {
-    let vault = &ctx.accounts.vault;
+    let clock = Clock::get()?;
+    
+    // A sold domain freezes the previous owner until the buyer claims the vault
+    ctx.accounts.vault.verify_domain_control(&ctx.accounts.sns_name_account)?;
+    
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
     
-    msg!("Emergency direct withdrawal initiated by owner: {}", ctx.accounts.owner.key());
+    ctx.accounts.vault.touch_owner_activity(clock.unix_timestamp);
+    
+    // Large withdrawals must go through the timelock queue once configured
+    if ctx.accounts.vault.has_withdrawal_timelock {
+        let config = ctx.accounts.withdrawal_config
+            .as_deref()
+            .ok_or(HydentityError::WithdrawalConfigRequired)?;
+        require!(
+            !config.requires_queue(amount, clock.unix_timestamp),
+            HydentityError::DirectWithdrawalRequiresQueue
+        );
+    }
+    
+    // Count against the rolling 24h outflow limit for this asset
+    let outflow_mint = ctx.accounts.vault_token_account.as_ref().map(|account| account.mint).unwrap_or_default();
+    enforce_outflow_limit(
+        &ctx.accounts.outflow_limit.to_account_info(),
+        &ctx.accounts.vault.key(),
+        ctx.accounts.vault.rekeyed_at,
+        &outflow_mint,
+        amount,
+        clock.unix_timestamp,
+        has_guardian_cosign(ctx.accounts.recovery_config.as_deref(), ctx.accounts.guardian.as_deref()),
+    )?;
+    
+    // Only allowlisted recipients when the policy enforces it
+    let recipient = match &ctx.accounts.destination_token_account {
+        Some(destination_token) if ctx.accounts.vault_token_account.is_some() => destination_token.owner,
+        _ => ctx.accounts.destination.key(),
+    };
+    ctx.accounts.policy.verify_direct_destination(&recipient, clock.unix_timestamp)?;
+    
+    // SOL amounts the private path could not split above dust are refused here too
+    if ctx.accounts.vault_token_account.is_none() {
+        ctx.accounts.policy.verify_split_feasible(amount)?;
+    }
+    
+    log_sensitive!("Emergency direct withdrawal initiated by owner: {}", ctx.accounts.owner.key());
     
     // Check if this is an SPL token transfer
     if let Some(vault_token_account) = &ctx.accounts.vault_token_account {
@@ -105,7 +196,7 @@ pub fn handler(
         );
         transfer(cpi_ctx, amount)?;
         
-        msg!("Direct withdrawal: {} SPL tokens to {}", amount, ctx.accounts.destination.key());
+        log_sensitive!("Direct withdrawal: {} SPL tokens to {}", amount, ctx.accounts.destination.key());
     } else {
         // SOL withdrawal from vault authority (where deposits are held)
         let vault_auth_info = ctx.accounts.vault_authority.to_account_info();
@@ -123,11 +214,24 @@ pub fn handler(
         **vault_auth_info.try_borrow_mut_lamports()? -= amount;
         **destination_info.try_borrow_mut_lamports()? += amount;
 
-        msg!("Direct withdrawal: {} lamports to {}", amount, ctx.accounts.destination.key());
+        log_sensitive!("Direct withdrawal: {} lamports to {}", amount, ctx.accounts.destination.key());
     }
     
-    msg!("WARNING: This withdrawal bypasses privacy protections");
-    msg!("Vault: {}", vault.key());
+    let (mint, destination) = match (&ctx.accounts.vault_token_account, &ctx.accounts.destination_token_account) {
+        (Some(vault_token), Some(destination_token)) => (vault_token.mint, destination_token.key()),
+        _ => (Pubkey::default(), ctx.accounts.destination.key()),
+    };
+    emit!(DirectWithdrawal {
+        version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.vault.key(),
+        mint,
+        destination,
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    log_info!("WARNING: This withdrawal bypasses privacy protections");
+    log_sensitive!("Vault: {}", ctx.accounts.vault.key());
     
     Ok(())
 }
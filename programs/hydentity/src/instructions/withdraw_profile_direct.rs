@@ -0,0 +1,224 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
+use crate::constants::{VAULT_SEED, VAULT_AUTH_SEED, POLICY_SEED, OWNER_SET_SEED, RECOVERY_CONFIG_SEED, WITHDRAWAL_CONFIG_SEED, VAULT_PROFILE_SEED};
+use crate::errors::HydentityError;
+use crate::events::{DirectWithdrawal, EVENT_SCHEMA_VERSION};
+use crate::state::{
+    NameVault, VaultAuthority, PrivacyPolicy, VaultOwnerSet, DirectWithdrawalConfig, RecoveryConfig, VaultProfile,
+    verify_owner_authority, enforce_outflow_limit, has_guardian_cosign,
+};
+
+/// Direct withdrawal from a vault profile (bypasses privacy)
+///
+/// Same rules as `withdraw_direct`, applied to the profile's balance and
+/// destination allowlist. The domain's withdrawal timelock and outflow
+/// limits cover its profiles too; larger amounts go through
+/// `queue_profile_withdrawal`.
+#[derive(Accounts)]
+#[instruction(profile_id: u8)]
+pub struct WithdrawProfileDirect<'info> {
+    /// The vault owner or an owner set member (must be signer)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The SNS name account
+    /// CHECK: Validated via vault's sns_name field
+    pub sns_name_account: UncheckedAccount<'info>,
+
+    /// The domain's vault
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
+        bump = vault.bump,
+        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName
+    )]
+    pub vault: Account<'info, NameVault>,
+
+    /// The profile holding the funds
+    #[account(
+        seeds = [VAULT_PROFILE_SEED, sns_name_account.key().as_ref(), &[profile_id]],
+        bump = profile.bump,
+        constraint = profile.vault == vault.key() @ HydentityError::ProfileNotFound
+    )]
+    pub profile: Account<'info, VaultProfile>,
+
+    /// The profile's vault authority (holds SOL deposits, signs SPL transfers)
+    #[account(
+        mut,
+        seeds = [VAULT_AUTH_SEED, sns_name_account.key().as_ref(), &[profile_id]],
+        bump = profile_vault_authority.bump,
+        constraint = profile_vault_authority.vault == profile.key() @ HydentityError::InvalidPolicyConfig
+    )]
+    pub profile_vault_authority: Account<'info, VaultAuthority>,
+
+    /// The profile's privacy policy (destination allowlist)
+    #[account(
+        seeds = [POLICY_SEED, sns_name_account.key().as_ref(), &[profile_id]],
+        bump = profile_policy.bump,
+        constraint = profile_policy.vault == profile.key() @ HydentityError::InvalidPolicyConfig
+    )]
+    pub profile_policy: Account<'info, PrivacyPolicy>,
+
+    /// The destination for the withdrawal
+    /// CHECK: Any valid account can receive funds
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    /// Optional: Profile's token account for SPL transfers
+    #[account(
+        mut,
+        token::authority = profile_vault_authority
+    )]
+    pub profile_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Optional: Destination's token account for SPL transfers
+    #[account(mut)]
+    pub destination_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Token program for SPL transfers
+    pub token_program: Program<'info, Token>,
+
+    /// System program for SOL transfers
+    pub system_program: Program<'info, System>,
+
+    /// Optional owner set for M-of-N owner authorization
+    /// Co-signers are passed as remaining accounts
+    #[account(
+        seeds = [OWNER_SET_SEED, sns_name_account.key().as_ref()],
+        bump = owner_set.bump,
+        constraint = owner_set.vault == vault.key() @ HydentityError::InvalidOwnerSet
+    )]
+    pub owner_set: Option<Account<'info, VaultOwnerSet>>,
+
+    /// Direct withdrawal timelock config (required once configured)
+    #[account(
+        seeds = [WITHDRAWAL_CONFIG_SEED, sns_name_account.key().as_ref()],
+        bump = withdrawal_config.bump,
+        constraint = withdrawal_config.vault == vault.key() @ HydentityError::WithdrawalConfigRequired
+    )]
+    pub withdrawal_config: Option<Account<'info, DirectWithdrawalConfig>>,
+
+    /// The domain's outflow limit PDA for the asset being moved (may be uninitialized)
+    /// CHECK: Address and contents validated by enforce_outflow_limit
+    #[account(mut)]
+    pub outflow_limit: UncheckedAccount<'info>,
+
+    /// Optional recovery config, for a guardian co-sign above the outflow limit
+    #[account(
+        seeds = [RECOVERY_CONFIG_SEED, sns_name_account.key().as_ref()],
+        bump = recovery_config.bump,
+        constraint = recovery_config.vault == vault.key() @ HydentityError::InvalidRecoveryConfig
+    )]
+    pub recovery_config: Option<Account<'info, RecoveryConfig>>,
+
+    /// Optional guardian co-signing an outflow above the limit
+    pub guardian: Option<Signer<'info>>,
+}
+
+pub fn handler(ctx: Context<WithdrawProfileDirect>, profile_id: u8, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // A sold domain freezes the previous owner until the buyer claims the vault
+    ctx.accounts.vault.verify_domain_control(&ctx.accounts.sns_name_account)?;
+
+    verify_owner_authority(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_set.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    ctx.accounts.vault.touch_owner_activity(clock.unix_timestamp);
+
+    // Large withdrawals must be queued once the domain's timelock is configured
+    if ctx.accounts.vault.has_withdrawal_timelock {
+        let config = ctx.accounts.withdrawal_config
+            .as_deref()
+            .ok_or(HydentityError::WithdrawalConfigRequired)?;
+        require!(
+            !config.requires_queue(amount, clock.unix_timestamp),
+            HydentityError::DirectWithdrawalRequiresQueue
+        );
+    }
+
+    // Profiles count against the domain's rolling 24h outflow limit
+    let outflow_mint = ctx.accounts.profile_token_account.as_ref().map(|account| account.mint).unwrap_or_default();
+    enforce_outflow_limit(
+        &ctx.accounts.outflow_limit.to_account_info(),
+        &ctx.accounts.vault.key(),
+        ctx.accounts.vault.rekeyed_at,
+        &outflow_mint,
+        amount,
+        clock.unix_timestamp,
+        has_guardian_cosign(ctx.accounts.recovery_config.as_deref(), ctx.accounts.guardian.as_deref()),
+    )?;
+
+    // Only allowlisted recipients when the profile's policy enforces it
+    let recipient = match &ctx.accounts.destination_token_account {
+        Some(destination_token) if ctx.accounts.profile_token_account.is_some() => destination_token.owner,
+        _ => ctx.accounts.destination.key(),
+    };
+    ctx.accounts.profile_policy.verify_direct_destination(&recipient, clock.unix_timestamp)?;
+
+    if let Some(profile_token_account) = &ctx.accounts.profile_token_account {
+        if profile_token_account.amount < amount {
+            return Err(HydentityError::InsufficientBalance.into());
+        }
+
+        let destination_token = ctx.accounts.destination_token_account
+            .as_ref()
+            .ok_or(HydentityError::InvalidMint)?;
+
+        let sns_name_key = ctx.accounts.sns_name_account.key();
+        let vault_auth_bump = ctx.accounts.profile_vault_authority.bump;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            VAULT_AUTH_SEED,
+            sns_name_key.as_ref(),
+            &[profile_id],
+            &[vault_auth_bump],
+        ]];
+
+        let cpi_accounts = Transfer {
+            from: profile_token_account.to_account_info(),
+            to: destination_token.to_account_info(),
+            authority: ctx.accounts.profile_vault_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        transfer(cpi_ctx, amount)?;
+    } else {
+        // SOL is held by the profile's vault authority
+        let vault_auth_info = ctx.accounts.profile_vault_authority.to_account_info();
+        let rent = Rent::get()?.minimum_balance(VaultAuthority::LEN);
+        let available = vault_auth_info.lamports().saturating_sub(rent);
+
+        if available < amount {
+            return Err(HydentityError::InsufficientBalance.into());
+        }
+
+        let destination_info = ctx.accounts.destination.to_account_info();
+        **vault_auth_info.try_borrow_mut_lamports()? -= amount;
+        **destination_info.try_borrow_mut_lamports()? += amount;
+    }
+
+    let (mint, destination) = match (&ctx.accounts.profile_token_account, &ctx.accounts.destination_token_account) {
+        (Some(profile_token), Some(destination_token)) => (profile_token.mint, destination_token.key()),
+        _ => (Pubkey::default(), ctx.accounts.destination.key()),
+    };
+    emit!(DirectWithdrawal {
+        version: EVENT_SCHEMA_VERSION,
+        vault: ctx.accounts.profile.key(),
+        mint,
+        destination,
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    log_info!("WARNING: This withdrawal bypasses privacy protections");
+    log_sensitive!("Profile {} direct withdrawal: {} to {}", profile_id, amount, destination);
+
+    Ok(())
+}
@@ -2,19 +2,77 @@ use anchor_lang::prelude::*;
 #[cfg(feature = "arcium")]
 use arcium_anchor::prelude::*;
 
+#[macro_use]
+mod logging;
 pub mod constants;
+pub mod limits;
 pub mod errors;
 pub mod instructions;
 pub mod state;
 pub mod events;
 
+#[cfg(feature = "arcium")]
 use constants::*;
+#[cfg(feature = "arcium")]
 use errors::HydentityError;
+use instructions::initialize_vault::*;
+use instructions::withdraw_direct::*;
+use instructions::mark_domain_transferred::*;
+use instructions::reclaim_domain::*;
+use instructions::close_vault::*;
+use instructions::claim_vault::*;
+use instructions::add_delegate::*;
+use instructions::revoke_delegate::*;
+use instructions::add_sub_delegate::*;
+use instructions::update_policy::*;
+use instructions::queue_policy_update::*;
+use instructions::apply_policy_update::*;
+use instructions::cancel_policy_update::*;
+use instructions::apply_policy_preset::*;
+use instructions::deposit_to_umbra::*;
+use instructions::set_owner_set::*;
+use instructions::remove_owner_set::*;
+use instructions::set_recovery_config::*;
+use instructions::initiate_recovery::*;
+use instructions::approve_recovery::*;
+use instructions::cancel_recovery::*;
+use instructions::execute_recovery::*;
+use instructions::propose_owner_transfer::*;
+use instructions::accept_owner_transfer::*;
+use instructions::cancel_owner_transfer::*;
+use instructions::set_beneficiary::*;
+use instructions::claim_inheritance::*;
+use instructions::set_withdrawal_timelock::*;
+use instructions::queue_direct_withdrawal::*;
+use instructions::execute_direct_withdrawal::*;
+use instructions::cancel_direct_withdrawal::*;
+use instructions::set_outflow_limit::*;
+use instructions::transfer_domain_to_vault::*;
+use instructions::sync_domain_state::*;
+use instructions::set_subdomain_policy::*;
+use instructions::initialize_subdomain_vault::*;
+use instructions::set_vault_sol_record::*;
+use instructions::list_domain_for_sale::*;
+use instructions::buy_domain::*;
+use instructions::delist_domain::*;
+use instructions::set_reclaim_timelock::*;
+use instructions::initiate_reclaim::*;
+use instructions::complete_reclaim::*;
+use instructions::cancel_reclaim::*;
+use instructions::rebind_vault::*;
+use instructions::create_vault_profile::*;
+use instructions::update_profile_policy::*;
+use instructions::withdraw_profile_direct::*;
+use instructions::close_vault_profile::*;
+use instructions::deposit_to_profile::*;
+use instructions::queue_profile_withdrawal::*;
+use instructions::execute_profile_withdrawal::*;
+use instructions::sync_owner_index::*;
 #[cfg(feature = "arcium")]
 use events::ConfigStored;
-use state::{NameVault, VaultAuthority, PrivacyPolicy};
+use state::PolicyPreset;
 #[cfg(feature = "arcium")]
-use state::{EncryptedVaultConfig, ENCRYPTED_CONFIG_SEED};
+use state::{NameVault, EncryptedVaultConfig, ENCRYPTED_CONFIG_SEED};
 
 #[cfg(feature = "arcium")]
 /// Computation definition offset for generate_withdrawal_plan
@@ -37,462 +95,358 @@ pub mod hydentity {
 
     /// Initialize a new vault for an SNS domain
     pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
-        let clock = Clock::get()?;
-        let now = clock.unix_timestamp;
-
-        let owner = ctx.accounts.owner.key();
-        let sns_name = ctx.accounts.sns_name_account.key();
-
-        // Verify SNS ownership - the signer must own the SNS domain
-        verify_sns_ownership(
-            &ctx.accounts.sns_name_account.to_account_info(),
-            &owner
-        )?;
-
-        // Initialize the vault
-        let vault = &mut ctx.accounts.vault;
-        vault.initialize(
-            owner,
-            sns_name,
-            ctx.bumps.vault,
-            now,
-        );
-
-        // Initialize the vault authority
-        let vault_authority = &mut ctx.accounts.vault_authority;
-        vault_authority.initialize(
-            vault.key(),
-            sns_name,
-            ctx.bumps.vault_authority,
-        );
-
-        // Initialize the privacy policy with defaults
-        let policy = &mut ctx.accounts.policy;
-        policy.initialize(
-            vault.key(),
-            sns_name,
-            owner,
-            ctx.bumps.policy,
-            now,
-        );
-
-        msg!("Vault initialized for SNS name: {}", sns_name);
-        msg!("Vault address: {}", vault.key());
-        msg!("Vault authority: {}", vault_authority.key());
-
-        Ok(())
+        instructions::initialize_vault::handler(ctx)
     }
 
-    // ========== Domain Transfer Instructions ==========
+    // ========== Policy & Delegation Instructions ==========
 
-    /// Mark a domain as transferred to the vault authority
-    /// Call this after transferring SNS ownership to the vault authority PDA
-    pub fn mark_domain_transferred(ctx: Context<MarkDomainTransferredAccounts>) -> Result<()> {
-        let vault = &mut ctx.accounts.vault;
-        let vault_authority = &ctx.accounts.vault_authority;
-        let sns_name_account = &ctx.accounts.sns_name_account;
+    /// Update the privacy policy (owner or delegate with PERMISSION_UPDATE_POLICY)
+    pub fn update_policy(ctx: Context<UpdatePolicy>, params: UpdatePolicyParams) -> Result<()> {
+        instructions::update_policy::handler(ctx, params)
+    }
 
-        msg!("Verifying domain transfer to vault authority");
-        msg!("SNS name account: {}", sns_name_account.key());
-        msg!("Expected owner (vault authority): {}", vault_authority.key());
+    /// Queue an update to a timelocked policy (owner or delegate with PERMISSION_UPDATE_POLICY)
+    pub fn queue_policy_update(ctx: Context<QueuePolicyUpdate>, params: UpdatePolicyParams) -> Result<()> {
+        instructions::queue_policy_update::handler(ctx, params)
+    }
 
-        // Verify the SNS name account is now owned by the vault authority
-        let name_data = sns_name_account.try_borrow_data()?;
-        let current_owner_bytes: [u8; 32] = name_data[32..64].try_into()
-            .map_err(|_| HydentityError::InvalidSnsName)?;
-        let current_owner = Pubkey::new_from_array(current_owner_bytes);
+    /// Apply a queued policy update once its delay has elapsed (permissionless)
+    pub fn apply_policy_update(ctx: Context<ApplyPolicyUpdate>) -> Result<()> {
+        instructions::apply_policy_update::handler(ctx)
+    }
 
-        msg!("Current SNS owner: {}", current_owner);
+    /// Cancel a queued policy update (owner only)
+    pub fn cancel_policy_update(ctx: Context<CancelPolicyUpdate>) -> Result<()> {
+        instructions::cancel_policy_update::handler(ctx)
+    }
 
-        require!(
-            current_owner == vault_authority.key(),
-            HydentityError::SnsOwnershipVerificationFailed
-        );
+    /// Apply a named privacy preset (owner or delegate with PERMISSION_UPDATE_POLICY)
+    pub fn apply_policy_preset(ctx: Context<ApplyPolicyPreset>, preset: PolicyPreset) -> Result<()> {
+        instructions::apply_policy_preset::handler(ctx, preset)
+    }
 
-        // Update vault state to mark domain as transferred
-        vault.set_domain_transferred(true);
+    /// Grant a time-bounded delegate session (owner only)
+    pub fn add_delegate(ctx: Context<AddDelegate>, expires_at: i64, permissions: u8) -> Result<()> {
+        instructions::add_delegate::handler(ctx, expires_at, permissions)
+    }
 
-        msg!("Domain ownership verified and marked as transferred to vault");
+    /// Issue a narrower child session from an existing delegate session
+    pub fn add_sub_delegate(
+        ctx: Context<AddSubDelegate>,
+        expires_at: i64,
+        permissions: u8,
+    ) -> Result<()> {
+        instructions::add_sub_delegate::handler(ctx, expires_at, permissions)
+    }
 
-        Ok(())
+    /// Revoke a delegate session (owner only)
+    /// Child sessions issued from it stop validating immediately
+    pub fn revoke_delegate(ctx: Context<RevokeDelegate>) -> Result<()> {
+        instructions::revoke_delegate::handler(ctx)
     }
 
-    /// Reclaim domain ownership from the vault
-    /// Transfers SNS ownership back from vault authority to a destination
-    pub fn reclaim_domain(ctx: Context<ReclaimDomainAccounts>) -> Result<()> {
-        use anchor_lang::solana_program::{program::invoke_signed, instruction::Instruction};
-
-        let vault = &mut ctx.accounts.vault;
-        let vault_authority = &ctx.accounts.vault_authority;
-        let sns_name_account = &ctx.accounts.sns_name_account;
-        let destination = &ctx.accounts.destination;
-
-        msg!("Reclaiming domain ownership from vault");
-        msg!("SNS name account: {}", sns_name_account.key());
-        msg!("Destination: {}", destination.key());
-
-        // Verify the SNS name account is currently owned by the vault authority
-        let name_data = sns_name_account.try_borrow_data()?;
-        let current_owner_bytes: [u8; 32] = name_data[32..64].try_into()
-            .map_err(|_| HydentityError::InvalidSnsName)?;
-        let current_owner = Pubkey::new_from_array(current_owner_bytes);
-
-        require!(
-            current_owner == vault_authority.key(),
-            HydentityError::DomainNotTransferred
-        );
-        drop(name_data);
-
-        // Build the SNS transfer instruction
-        let mut data = Vec::with_capacity(33);
-        data.push(2); // Transfer instruction tag
-        data.extend_from_slice(destination.key().as_ref());
-
-        let transfer_ix = Instruction {
-            program_id: SNS_NAME_PROGRAM_ID,
-            accounts: vec![
-                anchor_lang::solana_program::instruction::AccountMeta::new(*sns_name_account.key, false),
-                anchor_lang::solana_program::instruction::AccountMeta::new_readonly(vault_authority.key(), true),
-            ],
-            data,
-        };
-
-        // Get signer seeds for the vault authority PDA
-        let sns_name_key = vault.sns_name;
-        let bump = vault_authority.bump;
-        let signer_seeds: &[&[&[u8]]] = &[&[
-            VAULT_AUTH_SEED,
-            sns_name_key.as_ref(),
-            &[bump],
-        ]];
-
-        // Execute CPI to SNS Name Program
-        invoke_signed(
-            &transfer_ix,
-            &[
-                ctx.accounts.sns_name_account.to_account_info(),
-                ctx.accounts.vault_authority.to_account_info(),
-            ],
-            signer_seeds,
-        ).map_err(|_| HydentityError::SnsTransferFailed)?;
-
-        // Update vault state
-        vault.set_domain_transferred(false);
-
-        msg!("Domain ownership successfully reclaimed");
-        msg!("New owner: {}", destination.key());
-
-        Ok(())
+    /// Move vault funds into the Umbra pool (owner or delegate with PERMISSION_DEPOSIT_UMBRA)
+    pub fn deposit_to_umbra(
+        ctx: Context<DepositToUmbra>,
+        amount: u64,
+        mint: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::deposit_to_umbra::handler(ctx, amount, mint)
     }
 
-    // ========== Vault Lifecycle Instructions ==========
+    // ========== Owner Set Instructions ==========
 
-    /// Close a vault and reclaim rent
-    /// Owner closes their vault. No balance checks - user is responsible for clearing funds first.
-    /// Anchor's `close = owner` transfers all lamports from each PDA back to the owner.
-    pub fn close_vault(ctx: Context<CloseVault>) -> Result<()> {
-        let clock = Clock::get()?;
+    /// Create or replace the vault's M-of-N owner set
+    pub fn set_owner_set(
+        ctx: Context<SetOwnerSet>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::set_owner_set::handler(ctx, signers, threshold)
+    }
 
-        emit!(events::VaultClosed {
-            vault: ctx.accounts.vault.key(),
-            owner: ctx.accounts.owner.key(),
-            timestamp: clock.unix_timestamp,
-        });
+    /// Remove the vault's owner set, returning to single-owner control
+    pub fn remove_owner_set(ctx: Context<RemoveOwnerSet>) -> Result<()> {
+        instructions::remove_owner_set::handler(ctx)
+    }
 
-        msg!("Vault closed by owner: {}", ctx.accounts.owner.key());
+    // ========== Recovery Instructions ==========
 
-        Ok(())
+    /// Register or replace recovery guardians (owner only)
+    pub fn set_recovery_config(
+        ctx: Context<SetRecoveryConfig>,
+        guardians: Vec<Pubkey>,
+        threshold: u8,
+        timelock_seconds: i64,
+    ) -> Result<()> {
+        instructions::set_recovery_config::handler(ctx, guardians, threshold, timelock_seconds)
     }
 
-    /// Claim an existing vault after domain transfer/sale
-    /// New domain owner takes over an existing vault.
-    pub fn claim_vault(ctx: Context<ClaimVault>) -> Result<()> {
-        let clock = Clock::get()?;
-        let now = clock.unix_timestamp;
+    /// Open a recovery request proposing a new owner key (guardian only)
+    pub fn initiate_recovery(ctx: Context<InitiateRecovery>, new_owner: Pubkey) -> Result<()> {
+        instructions::initiate_recovery::handler(ctx, new_owner)
+    }
 
-        // Verify the signer is the current SNS domain owner
-        verify_sns_ownership(
-            &ctx.accounts.sns_name_account.to_account_info(),
-            &ctx.accounts.new_owner.key(),
-        )?;
+    /// Approve a pending recovery request (guardian only)
+    pub fn approve_recovery(ctx: Context<ApproveRecovery>) -> Result<()> {
+        instructions::approve_recovery::handler(ctx)
+    }
 
-        let previous_owner = ctx.accounts.vault.owner;
+    /// Veto a pending recovery request (owner only)
+    pub fn cancel_recovery(ctx: Context<CancelRecovery>) -> Result<()> {
+        instructions::cancel_recovery::handler(ctx)
+    }
 
-        // Update vault owner
-        let vault = &mut ctx.accounts.vault;
-        vault.owner = ctx.accounts.new_owner.key();
+    /// Rotate the vault owner once a recovery is approved and its timelock has passed
+    pub fn execute_recovery(ctx: Context<ExecuteRecovery>) -> Result<()> {
+        instructions::execute_recovery::handler(ctx)
+    }
 
-        // Reset policy for new owner
-        let policy = &mut ctx.accounts.policy;
-        policy.destinations = vec![ctx.accounts.new_owner.key()];
-        policy.policy_nonce = policy.policy_nonce
-            .checked_add(1)
-            .ok_or(HydentityError::ArithmeticOverflow)?;
-        policy.updated_at = now;
+    // ========== Owner Transfer Instructions ==========
 
-        emit!(events::VaultClaimed {
-            vault: ctx.accounts.vault.key(),
-            previous_owner,
-            new_owner: ctx.accounts.new_owner.key(),
-            timestamp: now,
-        });
+    /// Propose rotating the vault owner to a new key (owner only)
+    pub fn propose_owner_transfer(
+        ctx: Context<ProposeOwnerTransfer>,
+        new_owner: Pubkey,
+        delay_seconds: i64,
+        reset_destinations: bool,
+    ) -> Result<()> {
+        instructions::propose_owner_transfer::handler(ctx, new_owner, delay_seconds, reset_destinations)
+    }
 
-        msg!("Vault claimed by new owner: {}", ctx.accounts.new_owner.key());
-        msg!("Previous owner: {}", previous_owner);
+    /// Accept a proposed owner transfer once its delay has passed (new owner only)
+    pub fn accept_owner_transfer(ctx: Context<AcceptOwnerTransfer>) -> Result<()> {
+        instructions::accept_owner_transfer::handler(ctx)
+    }
 
-        Ok(())
+    /// Cancel a pending owner transfer (owner or proposed owner)
+    pub fn cancel_owner_transfer(ctx: Context<CancelOwnerTransfer>) -> Result<()> {
+        instructions::cancel_owner_transfer::handler(ctx)
     }
 
-    // ========== Withdrawal Instructions ==========
+    // ========== Inheritance Instructions ==========
 
-    /// Direct withdrawal - bypass privacy features (owner only)
-    pub fn withdraw_direct(
-        ctx: Context<WithdrawDirectAccounts>,
-        amount: u64,
-        _mint: Option<Pubkey>,
+    /// Configure the inactivity beneficiary (owner only)
+    pub fn set_beneficiary(
+        ctx: Context<SetBeneficiary>,
+        beneficiary: Pubkey,
+        inactivity_window_seconds: i64,
     ) -> Result<()> {
-        let vault_authority = &ctx.accounts.vault_authority;
-        let destination = &ctx.accounts.destination;
-
-        msg!("Emergency direct withdrawal initiated by owner: {}", ctx.accounts.owner.key());
+        instructions::set_beneficiary::handler(ctx, beneficiary, inactivity_window_seconds)
+    }
 
-        // For now, just do SOL transfer from vault authority
-        let balance = vault_authority.to_account_info().lamports();
-        if balance < amount {
-            return Err(HydentityError::InsufficientBalance.into());
-        }
+    /// Take over the vault after the owner's inactivity window (beneficiary only)
+    pub fn claim_inheritance(ctx: Context<ClaimInheritance>) -> Result<()> {
+        instructions::claim_inheritance::handler(ctx)
+    }
 
-        // Direct lamport transfer (required for PDAs with data - System Program transfer won't work)
-        **vault_authority.to_account_info().try_borrow_mut_lamports()? -= amount;
-        **destination.to_account_info().try_borrow_mut_lamports()? += amount;
+    // ========== Domain Transfer Instructions ==========
 
-        msg!("Transferred {} lamports to {}", amount, destination.key());
+    /// Mark a domain as transferred to the vault authority
+    /// Call this after transferring SNS ownership to the vault authority PDA
+    pub fn mark_domain_transferred(ctx: Context<MarkDomainTransferred>) -> Result<()> {
+        instructions::mark_domain_transferred::handler(ctx)
+    }
 
-        Ok(())
+    /// Transfer the domain to the vault authority and mark it transferred
+    /// Single-step alternative to an SDK transfer plus mark_domain_transferred
+    pub fn transfer_domain_to_vault(ctx: Context<TransferDomainToVault>) -> Result<()> {
+        instructions::transfer_domain_to_vault::handler(ctx)
     }
-}
 
-// ========== Account Structs for Domain Transfer Instructions ==========
+    /// Reconcile domain_transferred with the current SNS owner (permissionless)
+    /// Also flags vaults whose owner no longer controls the domain
+    pub fn sync_domain_state(ctx: Context<SyncDomainState>) -> Result<()> {
+        instructions::sync_domain_state::handler(ctx)
+    }
 
-/// Accounts for mark_domain_transferred instruction
-#[derive(Accounts)]
-pub struct MarkDomainTransferredAccounts<'info> {
-    /// The vault owner (must be signer)
-    pub owner: Signer<'info>,
+    /// Point the domain's SOL record at the vault authority (opt-in)
+    /// Signed by the domain owner, or by the vault authority once it holds the domain
+    pub fn set_vault_sol_record(ctx: Context<SetVaultSolRecord>) -> Result<()> {
+        instructions::set_vault_sol_record::handler(ctx)
+    }
 
-    /// The SNS name account that was transferred
-    /// CHECK: Validated manually - must be owned by SNS Name Program
-    #[account(
-        constraint = sns_name_account.owner == &SNS_NAME_PROGRAM_ID @ HydentityError::InvalidSnsName
-    )]
-    pub sns_name_account: UncheckedAccount<'info>,
+    // ========== Domain Sale Instructions ==========
 
-    /// The vault account (to verify ownership and update domain_transferred flag)
-    #[account(
-        mut,
-        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
-        bump = vault.bump,
-        constraint = vault.owner == owner.key() @ HydentityError::Unauthorized,
-        constraint = !vault.domain_transferred @ HydentityError::DomainAlreadyTransferred,
-    )]
-    pub vault: Account<'info, NameVault>,
+    /// List the vault-held domain for sale (owner only)
+    /// Payment mint defaults to SOL; buyer defaults to anyone
+    pub fn list_domain_for_sale(
+        ctx: Context<ListDomainForSale>,
+        price: u64,
+        mint: Option<Pubkey>,
+        buyer: Option<Pubkey>,
+        release_domain: bool,
+    ) -> Result<()> {
+        instructions::list_domain_for_sale::handler(ctx, price, mint, buyer, release_domain)
+    }
 
-    /// The vault authority PDA (should now be the owner of the SNS domain)
-    #[account(
-        seeds = [VAULT_AUTH_SEED, sns_name_account.key().as_ref()],
-        bump = vault_authority.bump,
-    )]
-    pub vault_authority: Account<'info, VaultAuthority>,
-}
+    /// Pay the listing price and receive the domain in one transaction
+    pub fn buy_domain(ctx: Context<BuyDomain>, price: u64) -> Result<()> {
+        instructions::buy_domain::handler(ctx, price)
+    }
 
-/// Accounts for reclaim_domain instruction
-#[derive(Accounts)]
-pub struct ReclaimDomainAccounts<'info> {
-    /// The vault owner (must be signer)
-    #[account(mut)]
-    pub owner: Signer<'info>,
+    /// Withdraw a domain listing (owner only)
+    pub fn delist_domain(ctx: Context<DelistDomain>) -> Result<()> {
+        instructions::delist_domain::handler(ctx)
+    }
 
-    /// The SNS name account to transfer
-    /// CHECK: Validated by the SNS Name Program during CPI
-    #[account(mut)]
-    pub sns_name_account: UncheckedAccount<'info>,
+    // ========== Vault Profile Instructions ==========
 
-    /// The vault account (to verify ownership and update domain_transferred flag)
-    #[account(
-        mut,
-        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
-        bump = vault.bump,
-        constraint = vault.owner == owner.key() @ HydentityError::Unauthorized,
-        constraint = vault.domain_transferred @ HydentityError::DomainNotTransferred,
-    )]
-    pub vault: Account<'info, NameVault>,
+    /// Create a named receiving profile with its own vault authority and policy (owner only)
+    pub fn create_vault_profile(ctx: Context<CreateVaultProfile>, label: String, profile_id: u8) -> Result<()> {
+        instructions::create_vault_profile::handler(ctx, label, profile_id)
+    }
 
-    /// The vault authority PDA (current owner of the SNS domain)
-    #[account(
-        seeds = [VAULT_AUTH_SEED, sns_name_account.key().as_ref()],
-        bump = vault_authority.bump,
-    )]
-    pub vault_authority: Account<'info, VaultAuthority>,
+    /// Update a profile's privacy policy (owner only)
+    pub fn update_profile_policy(
+        ctx: Context<UpdateProfilePolicy>,
+        profile_id: u8,
+        params: UpdatePolicyParams,
+    ) -> Result<()> {
+        instructions::update_profile_policy::handler(ctx, profile_id, params)
+    }
 
-    /// The destination address for the domain
-    /// CHECK: Can be any valid pubkey - the vault owner decides where to send
-    pub destination: UncheckedAccount<'info>,
+    /// Emergency direct withdrawal from a profile (owner only, bypasses privacy)
+    pub fn withdraw_profile_direct(ctx: Context<WithdrawProfileDirect>, profile_id: u8, amount: u64) -> Result<()> {
+        instructions::withdraw_profile_direct::handler(ctx, profile_id, amount)
+    }
 
-    /// SNS Name Program
-    /// CHECK: Validated by constraint
-    #[account(
-        constraint = sns_name_program.key() == SNS_NAME_PROGRAM_ID @ HydentityError::InvalidSnsName
-    )]
-    pub sns_name_program: UncheckedAccount<'info>,
-}
+    /// Close a profile and reclaim rent (owner only)
+    pub fn close_vault_profile(ctx: Context<CloseVaultProfile>, profile_id: u8) -> Result<()> {
+        instructions::close_vault_profile::handler(ctx, profile_id)
+    }
 
-/// Accounts for withdraw_direct instruction
-#[derive(Accounts)]
-pub struct WithdrawDirectAccounts<'info> {
-    /// The vault owner (must be signer)
-    #[account(mut)]
-    pub owner: Signer<'info>,
+    /// Send SOL or SPL tokens to a domain's profile, selected by label
+    pub fn deposit_to_profile(ctx: Context<DepositToProfile>, label: String, amount: u64) -> Result<()> {
+        instructions::deposit_to_profile::handler(ctx, label, amount)
+    }
 
-    /// The SNS name account
-    /// CHECK: Validated via vault's sns_name field
-    pub sns_name_account: UncheckedAccount<'info>,
+    /// Queue a profile withdrawal above the timelock threshold (owner only)
+    pub fn queue_profile_withdrawal(
+        ctx: Context<QueueProfileWithdrawal>,
+        profile_id: u8,
+        amount: u64,
+        mint: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::queue_profile_withdrawal::handler(ctx, profile_id, amount, mint)
+    }
 
-    /// The vault holding the funds
-    #[account(
-        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
-        bump = vault.bump,
-        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName,
-        constraint = vault.owner == owner.key() @ HydentityError::Unauthorized
-    )]
-    pub vault: Account<'info, NameVault>,
+    /// Execute a queued profile withdrawal once unlocked (permissionless)
+    pub fn execute_profile_withdrawal(ctx: Context<ExecuteProfileWithdrawal>, profile_id: u8) -> Result<()> {
+        instructions::execute_profile_withdrawal::handler(ctx, profile_id)
+    }
 
-    /// The vault authority for signing transfers
-    #[account(
-        mut,
-        seeds = [VAULT_AUTH_SEED, sns_name_account.key().as_ref()],
-        bump = vault_authority.bump,
-    )]
-    pub vault_authority: Account<'info, VaultAuthority>,
+    // ========== Subdomain Instructions ==========
 
-    /// The destination for the withdrawal
-    /// CHECK: Any valid account can receive funds
-    #[account(mut)]
-    pub destination: UncheckedAccount<'info>,
+    /// Allow or forbid this domain's owner to manage its subdomain vaults (owner only)
+    pub fn set_subdomain_policy(ctx: Context<SetSubdomainPolicy>, parent_controls_subdomains: bool) -> Result<()> {
+        instructions::set_subdomain_policy::handler(ctx, parent_controls_subdomains)
+    }
 
-    /// System program for SOL transfers
-    pub system_program: Program<'info, System>,
-}
+    /// Initialize a vault for a subdomain and link it to its parent domain
+    pub fn initialize_subdomain_vault(ctx: Context<InitializeSubdomainVault>) -> Result<()> {
+        instructions::initialize_subdomain_vault::handler(ctx)
+    }
 
-// ========== Vault Lifecycle Account Structs ==========
+    /// Configure the delay on two-step domain reclaims (owner only)
+    /// Once set, reclaim_domain is disabled while the delay is non-zero
+    pub fn set_reclaim_timelock(ctx: Context<SetReclaimTimelock>, delay_seconds: i64) -> Result<()> {
+        instructions::set_reclaim_timelock::handler(ctx, delay_seconds)
+    }
 
-/// Accounts for close_vault instruction
-#[derive(Accounts)]
-pub struct CloseVault<'info> {
-    /// The vault owner (must be signer, receives closed account lamports)
-    #[account(mut)]
-    pub owner: Signer<'info>,
+    /// Start a timelocked reclaim of the domain to a validated destination (owner only)
+    pub fn initiate_reclaim(ctx: Context<InitiateReclaim>, allow_program_owned: bool) -> Result<()> {
+        instructions::initiate_reclaim::handler(ctx, allow_program_owned)
+    }
 
-    /// The SNS name account
-    /// CHECK: Validated via vault's sns_name field
-    pub sns_name_account: UncheckedAccount<'info>,
+    /// Transfer the domain once the reclaim delay has passed (permissionless)
+    pub fn complete_reclaim(ctx: Context<CompleteReclaim>) -> Result<()> {
+        instructions::complete_reclaim::handler(ctx)
+    }
 
-    /// The vault to close
-    #[account(
-        mut,
-        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
-        bump = vault.bump,
-        constraint = vault.owner == owner.key() @ HydentityError::Unauthorized,
-        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName,
-        constraint = !vault.domain_transferred @ HydentityError::DomainAlreadyTransferred,
-        close = owner,
-    )]
-    pub vault: Account<'info, NameVault>,
+    /// Cancel a pending reclaim (owner only)
+    pub fn cancel_reclaim(ctx: Context<CancelReclaim>) -> Result<()> {
+        instructions::cancel_reclaim::handler(ctx)
+    }
 
-    /// The vault authority to close
-    #[account(
-        mut,
-        seeds = [VAULT_AUTH_SEED, sns_name_account.key().as_ref()],
-        bump = vault_authority.bump,
-        close = owner,
-    )]
-    pub vault_authority: Account<'info, VaultAuthority>,
+    /// Reclaim domain ownership from the vault
+    /// Transfers SNS ownership back from vault authority to a destination
+    /// Destinations that would strand the domain are rejected unless
+    /// allow_program_owned is set for program-owned accounts
+    pub fn reclaim_domain(ctx: Context<ReclaimDomain>, allow_program_owned: bool) -> Result<()> {
+        instructions::reclaim_domain::handler(ctx, allow_program_owned)
+    }
 
-    /// The privacy policy to close
-    #[account(
-        mut,
-        seeds = [POLICY_SEED, sns_name_account.key().as_ref()],
-        bump = policy.bump,
-        close = owner,
-    )]
-    pub policy: Account<'info, PrivacyPolicy>,
-}
+    // ========== Vault Lifecycle Instructions ==========
 
-/// Accounts for claim_vault instruction
-#[derive(Accounts)]
-pub struct ClaimVault<'info> {
-    /// The new domain owner (must be signer)
-    #[account(mut)]
-    pub new_owner: Signer<'info>,
+    /// Move a vault to another domain held by the owner (owner only)
+    /// Token balances are passed as (old, new) vault authority token account pairs in remaining accounts
+    pub fn rebind_vault<'info>(ctx: Context<'_, '_, 'info, 'info, RebindVault<'info>>) -> Result<()> {
+        instructions::rebind_vault::handler(ctx)
+    }
 
-    /// The SNS name account
-    /// CHECK: Validated by constraint and in handler via verify_sns_ownership
-    #[account(
-        constraint = sns_name_account.owner == &SNS_NAME_PROGRAM_ID @ HydentityError::InvalidSnsName
-    )]
-    pub sns_name_account: UncheckedAccount<'info>,
+    /// List a vault in its current owner's index (permissionless, for vaults created before the index)
+    pub fn sync_owner_index(ctx: Context<SyncOwnerIndex>) -> Result<()> {
+        instructions::sync_owner_index::handler(ctx)
+    }
 
-    /// The vault to claim
-    #[account(
-        mut,
-        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
-        bump = vault.bump,
-        constraint = vault.sns_name == sns_name_account.key() @ HydentityError::InvalidSnsName,
-        constraint = vault.owner != new_owner.key() @ HydentityError::VaultOwnerUnchanged,
-    )]
-    pub vault: Account<'info, NameVault>,
+    /// Close a vault and reclaim rent
+    /// Owner closes their vault. Anchor's `close = owner` transfers all lamports from each PDA back to the owner,
+    /// so any SOL balance is checked against the withdrawal timelock and outflow limit first.
+    pub fn close_vault(ctx: Context<CloseVault>) -> Result<()> {
+        instructions::close_vault::handler(ctx)
+    }
 
-    /// The privacy policy to reset
-    #[account(
-        mut,
-        seeds = [POLICY_SEED, sns_name_account.key().as_ref()],
-        bump = policy.bump,
-        constraint = policy.vault == vault.key() @ HydentityError::InvalidPolicyConfig,
-    )]
-    pub policy: Account<'info, PrivacyPolicy>,
-}
+    /// Claim an existing vault after domain transfer/sale
+    /// New domain owner takes over an existing vault.
+    pub fn claim_vault(ctx: Context<ClaimVault>) -> Result<()> {
+        instructions::claim_vault::handler(ctx)
+    }
 
-// ========== Helper Functions ==========
+    // ========== Withdrawal Instructions ==========
 
-/// Verify that the signer owns the SNS name account
-///
-/// SNS Name Account Data Layout:
-/// - bytes 0-32: parent_name (Pubkey)
-/// - bytes 32-64: owner (Pubkey) <- This is what we verify
-/// - bytes 64-96: class (Pubkey)
-/// - bytes 96+: data (variable)
-fn verify_sns_ownership(sns_account: &AccountInfo, expected_owner: &Pubkey) -> Result<()> {
-    let data = sns_account.try_borrow_data()?;
+    /// Configure the direct withdrawal timelock (owner only)
+    pub fn set_withdrawal_timelock(
+        ctx: Context<SetWithdrawalTimelock>,
+        threshold: u64,
+        delay_seconds: i64,
+        security_key: Pubkey,
+    ) -> Result<()> {
+        instructions::set_withdrawal_timelock::handler(ctx, threshold, delay_seconds, security_key)
+    }
 
-    // Ensure account has enough data for the owner field
-    if data.len() < 64 {
-        return Err(HydentityError::InvalidSnsName.into());
+    /// Queue a direct withdrawal behind the timelock (owner only)
+    pub fn queue_direct_withdrawal(
+        ctx: Context<QueueDirectWithdrawal>,
+        amount: u64,
+        mint: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::queue_direct_withdrawal::handler(ctx, amount, mint)
     }
 
-    // Extract the owner pubkey from offset 32
-    let owner_bytes: [u8; 32] = data[32..64]
-        .try_into()
-        .map_err(|_| HydentityError::InvalidSnsName)?;
+    /// Execute a queued direct withdrawal once unlocked (permissionless)
+    pub fn execute_direct_withdrawal(ctx: Context<ExecuteDirectWithdrawal>) -> Result<()> {
+        instructions::execute_direct_withdrawal::handler(ctx)
+    }
 
-    let sns_owner = Pubkey::new_from_array(owner_bytes);
+    /// Cancel a queued direct withdrawal (owner, guardian or security key)
+    pub fn cancel_direct_withdrawal(ctx: Context<CancelDirectWithdrawal>) -> Result<()> {
+        instructions::cancel_direct_withdrawal::handler(ctx)
+    }
 
-    // Verify the signer is the SNS domain owner
-    require!(
-        sns_owner == *expected_owner,
-        HydentityError::SnsOwnershipVerificationFailed
-    );
+    /// Set the rolling 24h outflow limit for a mint (owner only)
+    pub fn set_outflow_limit(ctx: Context<SetOutflowLimit>, mint: Pubkey, daily_limit: u64) -> Result<()> {
+        instructions::set_outflow_limit::handler(ctx, mint, daily_limit)
+    }
 
-    msg!("SNS ownership verified for domain owner: {}", sns_owner);
-    Ok(())
+    /// Direct withdrawal - bypass privacy features (owner or owner set quorum only)
+    pub fn withdraw_direct(
+        ctx: Context<WithdrawDirect>,
+        amount: u64,
+        mint: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::withdraw_direct::handler(ctx, amount, mint)
+    }
 }
 
 // ========== Arcium Account Structs ==========
@@ -516,6 +470,7 @@ pub struct StorePrivateConfig<'info> {
 
     /// The vault this config is for
     #[account(
+        mut,
         seeds = [VAULT_SEED, vault.sns_name.as_ref()],
         bump = vault.bump,
         constraint = vault.owner == payer.key() @ HydentityError::Unauthorized
@@ -650,55 +605,3 @@ pub struct InitGeneratePlanCompDef<'info> {
     pub arcium_program: Program<'info, Arcium>,
     pub system_program: Program<'info, System>,
 }
-
-// ========== Core Vault Account Structs ==========
-
-/// Accounts for initializing a new vault for an SNS domain
-#[derive(Accounts)]
-pub struct InitializeVault<'info> {
-    /// The owner of the SNS name (must be signer)
-    #[account(mut)]
-    pub owner: Signer<'info>,
-
-    /// The SNS name account that this vault is for
-    /// CHECK: Validated in handler by verifying:
-    /// 1. Account is owned by SNS Name Program
-    /// 2. Owner field in account data matches signer
-    #[account(
-        constraint = sns_name_account.owner == &SNS_NAME_PROGRAM_ID @ HydentityError::InvalidSnsName
-    )]
-    pub sns_name_account: UncheckedAccount<'info>,
-
-    /// The vault PDA to be created
-    #[account(
-        init,
-        payer = owner,
-        space = NameVault::LEN,
-        seeds = [VAULT_SEED, sns_name_account.key().as_ref()],
-        bump
-    )]
-    pub vault: Account<'info, NameVault>,
-
-    /// The vault authority PDA to be created
-    #[account(
-        init,
-        payer = owner,
-        space = VaultAuthority::LEN,
-        seeds = [VAULT_AUTH_SEED, sns_name_account.key().as_ref()],
-        bump
-    )]
-    pub vault_authority: Account<'info, VaultAuthority>,
-
-    /// The privacy policy PDA to be created
-    #[account(
-        init,
-        payer = owner,
-        space = PrivacyPolicy::LEN,
-        seeds = [POLICY_SEED, sns_name_account.key().as_ref()],
-        bump
-    )]
-    pub policy: Account<'info, PrivacyPolicy>,
-
-    /// System program for account creation
-    pub system_program: Program<'info, System>,
-}
@@ -0,0 +1,17 @@
+//! Privacy policy limits shared with the encrypted instructions
+//!
+//! The MPC circuits include this file directly (see `encrypted-ixs/build.rs`
+//! and `encrypted-ixs/types.rs`), so it must stay free of imports and only
+//! contain plain constants.
+
+/// Maximum number of destination wallets per vault
+pub const MAX_DESTINATIONS: usize = 5;
+
+/// Maximum number of splits per withdrawal
+pub const MAX_SPLITS: usize = 10;
+
+/// Minimum delay between splits (60 seconds = 1 minute)
+pub const MIN_DELAY_FLOOR_SECONDS: u32 = 60;
+
+/// Maximum delay between splits (7 days)
+pub const MAX_DELAY_CEILING_SECONDS: u32 = 604800;
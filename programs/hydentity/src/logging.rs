@@ -0,0 +1,58 @@
+//! Privacy-safe program logging
+//!
+//! Program logs are public and permanently indexed, so a log line that
+//! prints an owner, destination or amount undoes the privacy the vault is
+//! meant to provide. All logging goes through the macros below; calling `msg!`
+//! directly anywhere in the crate is a compile error:
+//!
+//! - `log_info!` for static or non-identifying lines (nonces, counts,
+//!   timestamps). Always compiled in.
+//! - `log_sensitive!` for lines that reveal addresses or amounts. Compiled
+//!   out unless the `verbose-logs` feature is enabled, which should only be
+//!   used for local debugging builds.
+//!
+//! Production builds surface addresses and amounts only through events and
+//! error codes.
+
+/// Log a line that reveals no addresses or amounts
+macro_rules! log_info {
+    ($($arg:tt)*) => {{
+        ::anchor_lang::prelude::msg!($($arg)*);
+    }};
+}
+
+/// Log a line that may reveal addresses or amounts
+///
+/// Only emitted with the `verbose-logs` feature. The arguments are still
+/// type-checked (and not evaluated for side effects) in default builds.
+#[cfg(feature = "verbose-logs")]
+macro_rules! log_sensitive {
+    ($($arg:tt)*) => {{
+        ::anchor_lang::prelude::msg!($($arg)*);
+    }};
+}
+
+/// Log a line that may reveal addresses or amounts
+///
+/// Only emitted with the `verbose-logs` feature. The arguments are still
+/// type-checked (and not evaluated for side effects) in default builds.
+#[cfg(not(feature = "verbose-logs"))]
+macro_rules! log_sensitive {
+    ($($arg:tt)*) => {{
+        if false {
+            let _ = ::core::format_args!($($arg)*);
+        }
+    }};
+}
+
+/// Shadows `msg!` for every module declared after this one
+///
+/// A direct `msg!` call fails to compile: it is either ambiguous with the
+/// Anchor prelude import, pointing back here, or hits this error. Anchor's
+/// own generated logging uses the fully qualified path and is unaffected.
+#[allow(unused_macros)]
+macro_rules! msg {
+    ($($arg:tt)*) => {
+        compile_error!("use log_info! or log_sensitive! instead of msg! (see src/logging.rs)")
+    };
+}
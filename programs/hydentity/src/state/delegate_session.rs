@@ -1,11 +1,20 @@
 use anchor_lang::prelude::*;
-use crate::constants::{PERMISSION_UPDATE_POLICY, PERMISSION_DEPOSIT_UMBRA};
+use crate::constants::{
+    PERMISSION_UPDATE_POLICY, PERMISSION_DEPOSIT_UMBRA, PERMISSION_SUB_DELEGATE, MAX_DELEGATION_DEPTH,
+};
+use crate::errors::HydentityError;
+use crate::state::NameVault;
 
 /// DelegateSession - Time-bounded execution permissions
 /// 
 /// Allows vault owners to grant temporary permissions to other
 /// accounts for executing specific operations on their behalf.
 /// 
+/// A delegate holding `PERMISSION_SUB_DELEGATE` can issue child sessions
+/// with a subset of its permissions and an expiry no later than its own.
+/// Child sessions record their parent session, and the whole chain must
+/// still exist and be unexpired for a child to act.
+/// 
 /// PDA Seeds: ["delegate", sns_name_account_pubkey, delegate_pubkey]
 #[account]
 #[derive(Default)]
@@ -28,6 +37,7 @@ pub struct DelegateSession {
     /// Permission flags (bitmap)
     /// Bit 0: Can update policy
     /// Bit 1: Can deposit to Umbra
+    /// Bit 2: Can issue child sessions
     pub permissions: u8,
     
     /// Timestamp when this delegation was created
@@ -36,8 +46,9 @@ pub struct DelegateSession {
     /// Bump seed for PDA derivation
     pub bump: u8,
     
-    /// Reserved space for future upgrades
-    pub _reserved: [u8; 32],
+    /// The parent session this session was issued from
+    /// Pubkey::default() for sessions granted directly by the owner
+    pub parent: Pubkey,
 }
 
 impl DelegateSession {
@@ -51,7 +62,7 @@ impl DelegateSession {
         1 +  // permissions
         8 +  // created_at
         1 +  // bump
-        32;  // reserved
+        32;  // parent
     
     /// Initialize the delegate session
     pub fn initialize(
@@ -73,7 +84,7 @@ impl DelegateSession {
         self.permissions = permissions;
         self.created_at = now;
         self.bump = bump;
-        self._reserved = [0u8; 32];
+        self.parent = Pubkey::default();
     }
     
     /// Check if the delegate session is still valid
@@ -91,19 +102,156 @@ impl DelegateSession {
         self.permissions & PERMISSION_DEPOSIT_UMBRA != 0
     }
     
+    /// Check if delegate has permission to issue child sessions
+    pub fn can_sub_delegate(&self) -> bool {
+        self.permissions & PERMISSION_SUB_DELEGATE != 0
+    }
+    
     /// Validate delegate for a specific permission
-    pub fn validate_permission(&self, permission: u8, now: i64) -> Result<()> {
+    /// 
+    /// `ancestors` must hold the parent session chain, nearest parent first.
+    /// Sessions granted directly by the owner need no ancestors.
+    pub fn validate_permission(
+        &self,
+        permission: u8,
+        now: i64,
+        vault: &NameVault,
+        ancestors: &[AccountInfo],
+    ) -> Result<()> {
         // Check if session is expired
         if !self.is_valid(now) {
-            return Err(crate::errors::HydentityError::DelegateExpired.into());
+            return Err(HydentityError::DelegateExpired.into());
         }
         
         // Check if delegate has the required permission
         if self.permissions & permission == 0 {
-            return Err(crate::errors::HydentityError::InsufficientPermissions.into());
+            return Err(HydentityError::InsufficientPermissions.into());
         }
         
+        self.validate_chain(now, vault, ancestors)?;
+        
         Ok(())
     }
+    
+    /// Walk the parent chain and verify every ancestor is still live
+    /// 
+    /// Returns the number of ancestors, i.e. the depth of this session
+    /// (0 for sessions granted directly by the owner).
+    ///
+    /// A chain whose root session `vault` no longer accepts (see
+    /// `NameVault::accepts_delegation_from`) is void.
+    pub fn validate_chain(&self, now: i64, vault: &NameVault, ancestors: &[AccountInfo]) -> Result<usize> {
+        let mut expected_parent = self.parent;
+        let mut child_created_at = self.created_at;
+        let mut depth = 0usize;
+        
+        while expected_parent != Pubkey::default() {
+            if depth + 1 >= MAX_DELEGATION_DEPTH {
+                return Err(HydentityError::DelegationDepthExceeded.into());
+            }
+            
+            let info = ancestors
+                .get(depth)
+                .ok_or(HydentityError::InvalidDelegateChain)?;
+            require_keys_eq!(info.key(), expected_parent, HydentityError::InvalidDelegateChain);
+            
+            // A revoked parent has been closed, so it is no longer a program account
+            require_keys_eq!(*info.owner, crate::ID, HydentityError::ParentDelegateRevoked);
+            let parent = {
+                let data = info.try_borrow_data()?;
+                DelegateSession::try_deserialize(&mut &data[..])
+                    .map_err(|_| HydentityError::ParentDelegateRevoked)?
+            };
+            
+            require_keys_eq!(parent.vault, self.vault, HydentityError::InvalidDelegateChain);
+            
+            // A parent re-created after revocation does not revive old children
+            require!(
+                parent.created_at <= child_created_at,
+                HydentityError::ParentDelegateRevoked
+            );
+            
+            if !parent.is_valid(now) {
+                return Err(HydentityError::DelegateExpired.into());
+            }
+            
+            expected_parent = parent.parent;
+            child_created_at = parent.created_at;
+            depth += 1;
+        }
+        
+        // child_created_at now holds the root session's creation time
+        require!(
+            vault.accepts_delegation_from(child_created_at),
+            HydentityError::DelegateStale
+        );
+        
+        Ok(depth)
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::PERMISSION_ALL;
+
+    fn root_session(created_at: i64) -> DelegateSession {
+        let mut session = DelegateSession::default();
+        session.initialize(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            created_at + 1_000,
+            PERMISSION_ALL,
+            255,
+            created_at,
+        );
+        session
+    }
+
+    fn vault(created_at: i64, rekeyed_at: i64) -> NameVault {
+        NameVault {
+            created_at,
+            rekeyed_at,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_session_valid_before_any_sale() {
+        let session = root_session(100);
+        assert!(session.validate_permission(PERMISSION_UPDATE_POLICY, 200, &vault(100, 0), &[]).is_ok());
+    }
+
+    #[test]
+    fn test_sale_voids_earlier_sessions() {
+        let session = root_session(100);
+        assert!(session.validate_permission(PERMISSION_UPDATE_POLICY, 200, &vault(50, 100), &[]).is_err());
+        assert!(session.validate_permission(PERMISSION_UPDATE_POLICY, 200, &vault(50, 150), &[]).is_err());
+
+        // Sessions granted by the buyer after the sale still work
+        assert!(session.validate_permission(PERMISSION_UPDATE_POLICY, 200, &vault(50, 99), &[]).is_ok());
+    }
+
+    #[test]
+    fn test_claim_voids_sessions_granted_before_it() {
+        let session = root_session(100);
+        let mut claimed = vault(50, 0);
+        assert!(session.validate_permission(PERMISSION_UPDATE_POLICY, 200, &claimed, &[]).is_ok());
+
+        // claim_vault re-keys the vault for the domain's new holder
+        claimed.rekey_for_sale(Pubkey::new_unique(), 150);
+        assert_eq!(
+            session.validate_permission(PERMISSION_UPDATE_POLICY, 200, &claimed, &[]).unwrap_err(),
+            HydentityError::DelegateStale.into()
+        );
+    }
+
+    #[test]
+    fn test_recreated_vault_ignores_earlier_sessions() {
+        // A vault closed and re-created for the same name has the same address
+        let session = root_session(100);
+        assert!(session.validate_permission(PERMISSION_UPDATE_POLICY, 200, &vault(150, 0), &[]).is_err());
+    }
+}
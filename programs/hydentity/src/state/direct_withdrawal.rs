@@ -0,0 +1,284 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::constants::MAX_DIRECT_WITHDRAWAL_DELAY_SECONDS;
+use crate::errors::HydentityError;
+
+/// DirectWithdrawalConfig - Optional timelock for `withdraw_direct`
+///
+/// Once configured, direct withdrawals above `threshold` (in the asset's
+/// base units) must be queued as a `PendingDirectWithdrawal` and can only
+/// execute after `delay_seconds`. Changes that tighten the settings apply
+/// immediately; changes that loosen them only take effect after the
+/// current delay, so a stolen owner key cannot simply switch it off.
+///
+/// PDA Seeds: ["withdraw_config", sns_name_account_pubkey]
+#[account]
+#[derive(Default)]
+pub struct DirectWithdrawalConfig {
+    /// The vault this config is associated with
+    pub vault: Pubkey,
+
+    /// Amounts above this must be queued (base units)
+    pub threshold: u64,
+
+    /// Delay between queueing and execution (0 = timelock disabled)
+    pub delay_seconds: i64,
+
+    /// Optional key allowed to cancel queued withdrawals (default = none)
+    pub security_key: Pubkey,
+
+    /// Loosened threshold waiting to take effect
+    pub pending_threshold: u64,
+
+    /// Loosened delay waiting to take effect
+    pub pending_delay_seconds: i64,
+
+    /// Security key change waiting to take effect
+    pub pending_security_key: Pubkey,
+
+    /// When the pending change takes effect (0 = no pending change)
+    pub pending_effective_at: i64,
+
+    /// Identifier for the next queued withdrawal
+    pub next_withdrawal_id: u64,
+
+    /// Timestamp of last config update
+    pub updated_at: i64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// Reserved space for future upgrades
+    pub _reserved: [u8; 32],
+}
+
+impl DirectWithdrawalConfig {
+    /// Account size for rent calculation
+    pub const LEN: usize = 8 + // discriminator
+        32 + // vault
+        8 +  // threshold
+        8 +  // delay_seconds
+        32 + // security_key
+        8 +  // pending_threshold
+        8 +  // pending_delay_seconds
+        32 + // pending_security_key
+        8 +  // pending_effective_at
+        8 +  // next_withdrawal_id
+        8 +  // updated_at
+        1 +  // bump
+        32;  // reserved
+
+    /// Validate a requested delay
+    pub fn validate_delay(delay_seconds: i64) -> Result<()> {
+        require!(
+            (0..=MAX_DIRECT_WITHDRAWAL_DELAY_SECONDS).contains(&delay_seconds),
+            HydentityError::InvalidWithdrawalTimelock
+        );
+        Ok(())
+    }
+
+    /// Promote a pending change once it has matured
+    pub fn apply_matured(&mut self, now: i64) {
+        if self.pending_effective_at != 0 && now >= self.pending_effective_at {
+            self.threshold = self.pending_threshold;
+            self.delay_seconds = self.pending_delay_seconds;
+            self.security_key = self.pending_security_key;
+            self.clear_pending();
+        }
+    }
+
+    /// Drop any pending change
+    pub fn clear_pending(&mut self) {
+        self.pending_threshold = 0;
+        self.pending_delay_seconds = 0;
+        self.pending_security_key = Pubkey::default();
+        self.pending_effective_at = 0;
+    }
+
+    /// Check if new settings are at least as strict as the current ones
+    pub fn is_tightening(&self, threshold: u64, delay_seconds: i64, security_key: &Pubkey) -> bool {
+        threshold <= self.threshold
+            && delay_seconds >= self.delay_seconds
+            && (self.security_key == Pubkey::default() || self.security_key == *security_key)
+    }
+
+    /// Settings in force at `now`: (threshold, delay_seconds, security_key)
+    pub fn active_settings(&self, now: i64) -> (u64, i64, Pubkey) {
+        if self.pending_effective_at != 0 && now >= self.pending_effective_at {
+            (self.pending_threshold, self.pending_delay_seconds, self.pending_security_key)
+        } else {
+            (self.threshold, self.delay_seconds, self.security_key)
+        }
+    }
+
+    /// Check if a direct withdrawal of `amount` must be queued
+    pub fn requires_queue(&self, amount: u64, now: i64) -> bool {
+        let (threshold, delay_seconds, _) = self.active_settings(now);
+        delay_seconds > 0 && amount > threshold
+    }
+
+    /// Check if a key is the configured security key
+    pub fn is_security_key(&self, key: &Pubkey, now: i64) -> bool {
+        let (_, _, security_key) = self.active_settings(now);
+        security_key != Pubkey::default() && security_key == *key
+    }
+}
+
+/// PendingDirectWithdrawal - A queued direct withdrawal
+///
+/// Created by `queue_direct_withdrawal`; executes after `unlock_at` via
+/// `execute_direct_withdrawal`, or is closed early by the owner, a
+/// recovery guardian or the security key.
+///
+/// PDA Seeds: ["direct_withdrawal", vault_pubkey, withdrawal_id (le bytes)]
+#[account]
+#[derive(Default)]
+pub struct PendingDirectWithdrawal {
+    /// The vault funds are withdrawn from
+    pub vault: Pubkey,
+
+    /// Sequential identifier within the vault
+    pub withdrawal_id: u64,
+
+    /// Vault owner at queue time (the request is void if ownership changes)
+    pub owner: Pubkey,
+
+    /// Token mint (default pubkey = SOL)
+    pub mint: Pubkey,
+
+    /// Recipient (wallet for SOL, token account for SPL)
+    pub destination: Pubkey,
+
+    /// Amount in base units
+    pub amount: u64,
+
+    /// Account that paid for the request (receives rent on close)
+    pub requested_by: Pubkey,
+
+    /// Timestamp when the withdrawal was queued
+    pub requested_at: i64,
+
+    /// Earliest execution time
+    pub unlock_at: i64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl PendingDirectWithdrawal {
+    /// Account size for rent calculation
+    pub const LEN: usize = 8 + // discriminator
+        32 + // vault
+        8 +  // withdrawal_id
+        32 + // owner
+        32 + // mint
+        32 + // destination
+        8 +  // amount
+        32 + // requested_by
+        8 +  // requested_at
+        8 +  // unlock_at
+        1;   // bump
+
+    /// Check if this withdrawal moves SOL rather than an SPL token
+    pub fn is_sol(&self) -> bool {
+        self.mint == Pubkey::default()
+    }
+
+    /// Resolve the wallet that ends up controlling the funds
+    ///
+    /// For SOL this is the destination itself; for SPL it is the owner of
+    /// the destination token account.
+    pub fn recipient(&self, destination: &AccountInfo) -> Result<Pubkey> {
+        if self.is_sol() {
+            return Ok(*destination.key);
+        }
+
+        require_keys_eq!(*destination.owner, anchor_spl::token::ID, HydentityError::InvalidMint);
+        let data = destination.try_borrow_data()?;
+        let token_account = TokenAccount::try_deserialize(&mut &data[..])?;
+        require_keys_eq!(token_account.mint, self.mint, HydentityError::InvalidMint);
+        Ok(token_account.owner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(threshold: u64, delay_seconds: i64) -> DirectWithdrawalConfig {
+        DirectWithdrawalConfig {
+            threshold,
+            delay_seconds,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_requires_queue_above_threshold() {
+        let config = config(1_000, 3_600);
+        assert!(!config.requires_queue(999, 0));
+        assert!(!config.requires_queue(1_000, 0));
+        assert!(config.requires_queue(1_001, 0));
+    }
+
+    #[test]
+    fn test_zero_delay_disables_queue() {
+        let config = config(0, 0);
+        assert!(!config.requires_queue(u64::MAX, 0));
+    }
+
+    #[test]
+    fn test_loosening_waits_for_current_delay() {
+        let mut config = config(1_000, 3_600);
+        assert!(!config.is_tightening(5_000, 3_600, &Pubkey::default()));
+
+        // Loosened settings are staged behind the current delay
+        config.pending_threshold = 5_000;
+        config.pending_delay_seconds = 3_600;
+        config.pending_effective_at = 100 + config.delay_seconds;
+
+        assert!(config.requires_queue(2_000, 100));
+        assert!(config.requires_queue(2_000, 3_699));
+        assert!(!config.requires_queue(2_000, 3_700));
+
+        config.apply_matured(3_699);
+        assert_eq!(config.threshold, 1_000);
+        config.apply_matured(3_700);
+        assert_eq!(config.threshold, 5_000);
+        assert_eq!(config.pending_effective_at, 0);
+    }
+
+    #[test]
+    fn test_tightening_rules() {
+        let security_key = Pubkey::new_unique();
+        let mut config = config(1_000, 3_600);
+        assert!(config.is_tightening(500, 7_200, &security_key));
+        assert!(!config.is_tightening(1_000, 60, &Pubkey::default()));
+
+        // Replacing a configured security key counts as loosening
+        config.security_key = security_key;
+        assert!(config.is_tightening(1_000, 3_600, &security_key));
+        assert!(!config.is_tightening(1_000, 3_600, &Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_security_key_matches_active_settings() {
+        let security_key = Pubkey::new_unique();
+        let mut config = config(1_000, 3_600);
+        assert!(!config.is_security_key(&Pubkey::default(), 0));
+
+        config.pending_security_key = security_key;
+        config.pending_delay_seconds = 3_600;
+        config.pending_effective_at = 50;
+        assert!(!config.is_security_key(&security_key, 49));
+        assert!(config.is_security_key(&security_key, 50));
+    }
+
+    #[test]
+    fn test_delay_bounds() {
+        assert!(DirectWithdrawalConfig::validate_delay(0).is_ok());
+        assert!(DirectWithdrawalConfig::validate_delay(MAX_DIRECT_WITHDRAWAL_DELAY_SECONDS).is_ok());
+        assert!(DirectWithdrawalConfig::validate_delay(-1).is_err());
+        assert!(DirectWithdrawalConfig::validate_delay(MAX_DIRECT_WITHDRAWAL_DELAY_SECONDS + 1).is_err());
+    }
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::errors::HydentityError;
+
+/// DomainListing - An escrowed sale of a vault-held domain
+///
+/// Only domains held by the vault authority can be listed, so the domain
+/// is already in escrow. `buy_domain` pays `proceeds_destination` and hands
+/// over the domain in the same transaction. With `release_domain` the SNS
+/// domain is transferred to the buyer; otherwise the buyer takes over the
+/// vault (and the domain it holds) as its new owner.
+///
+/// PDA Seeds: ["listing", sns_name_account_pubkey]
+#[account]
+#[derive(Default)]
+pub struct DomainListing {
+    /// The vault holding the listed domain
+    pub vault: Pubkey,
+
+    /// The vault owner at listing time
+    pub seller: Pubkey,
+
+    /// Account that paid for the listing (receives rent on close)
+    pub listed_by: Pubkey,
+
+    /// Where the buyer's payment goes (a wallet for SOL, a token account otherwise)
+    pub proceeds_destination: Pubkey,
+
+    /// Asking price (base units of `mint`)
+    pub price: u64,
+
+    /// Payment mint (default pubkey = SOL)
+    pub mint: Pubkey,
+
+    /// Only this key may buy (default pubkey = anyone)
+    pub buyer: Pubkey,
+
+    /// Transfer the SNS domain to the buyer instead of re-keying the vault
+    pub release_domain: bool,
+
+    /// Timestamp when the domain was listed
+    pub listed_at: i64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl DomainListing {
+    /// Account size for rent calculation
+    pub const LEN: usize = 8 + // discriminator
+        32 + // vault
+        32 + // seller
+        32 + // listed_by
+        32 + // proceeds_destination
+        8 +  // price
+        32 + // mint
+        32 + // buyer
+        1 +  // release_domain
+        8 +  // listed_at
+        1;   // bump
+
+    /// Check if the payment is in SOL
+    pub fn is_sol(&self) -> bool {
+        self.mint == Pubkey::default()
+    }
+
+    /// Check the proceeds destination can receive the payment asset
+    pub fn validate_proceeds_destination(&self, destination: &AccountInfo) -> Result<()> {
+        require!(
+            self.proceeds_destination != Pubkey::default(),
+            HydentityError::InvalidProceedsDestination
+        );
+        if self.is_sol() {
+            return Ok(());
+        }
+
+        require_keys_eq!(*destination.owner, anchor_spl::token::ID, HydentityError::InvalidProceedsDestination);
+        let data = destination.try_borrow_data()?;
+        let token_account = TokenAccount::try_deserialize(&mut &data[..])?;
+        require_keys_eq!(token_account.mint, self.mint, HydentityError::InvalidMint);
+        Ok(())
+    }
+
+    /// Check that `buyer` may settle the listing at `price` for `seller`
+    ///
+    /// `price` is the amount the buyer agreed to, so a relisting at a
+    /// different price cannot be front-run into their transaction.
+    pub fn verify_purchase(&self, seller: &Pubkey, buyer: &Pubkey, price: u64) -> Result<()> {
+        require_keys_eq!(*seller, self.seller, HydentityError::ListingStale);
+        require!(
+            self.buyer == Pubkey::default() || self.buyer == *buyer,
+            HydentityError::ListingBuyerMismatch
+        );
+        require_keys_neq!(*buyer, self.seller, HydentityError::ListingBuyerMismatch);
+        require!(price == self.price, HydentityError::ListingPriceMismatch);
+        Ok(())
+    }
+}
@@ -4,6 +4,19 @@ pub mod privacy_policy;
 pub mod delegate_session;
 pub mod encrypted_config;
 pub mod pending_withdrawal;
+pub mod vault_owner_set;
+pub mod recovery;
+pub mod owner_transfer;
+pub mod direct_withdrawal;
+pub mod outflow_limit;
+pub mod policy_update;
+pub mod policy_history;
+pub mod subdomain;
+pub mod tokenized_domain;
+pub mod domain_listing;
+pub mod reclaim;
+pub mod vault_profile;
+pub mod owner_index;
 
 pub use name_vault::*;
 pub use vault_authority::*;
@@ -11,4 +24,90 @@ pub use privacy_policy::*;
 pub use delegate_session::*;
 pub use encrypted_config::*;
 pub use pending_withdrawal::*;
+pub use vault_owner_set::*;
+pub use recovery::*;
+pub use owner_transfer::*;
+pub use direct_withdrawal::*;
+pub use outflow_limit::*;
+pub use policy_update::*;
+pub use policy_history::*;
+pub use subdomain::*;
+pub use tokenized_domain::*;
+pub use domain_listing::*;
+pub use reclaim::*;
+pub use vault_profile::*;
+pub use owner_index::*;
 
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::errors::HydentityError;
+
+/// Whether a PDA has been created as one of this program's accounts
+pub fn is_program_account(info: &AccountInfo) -> bool {
+    *info.owner == crate::ID && !info.data_is_empty()
+}
+
+/// Create a program account of `space` bytes at a PDA, paid by `payer`
+///
+/// An address someone already sent lamports to is funded up to rent and
+/// taken over, so pre-funding a PDA cannot block its creation.
+pub fn create_program_account<'info>(
+    info: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[&[u8]]],
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+
+    if info.lamports() == 0 {
+        let cpi_accounts = system_program::CreateAccount {
+            from: payer.clone(),
+            to: info.clone(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(system_program.clone(), cpi_accounts, signer_seeds);
+        system_program::create_account(cpi_ctx, rent, space as u64, &crate::ID)?;
+    } else {
+        // Someone already sent lamports to the address; fund the rest and take it over
+        let shortfall = rent.saturating_sub(info.lamports());
+        if shortfall > 0 {
+            let cpi_accounts = system_program::Transfer {
+                from: payer.clone(),
+                to: info.clone(),
+            };
+            let cpi_ctx = CpiContext::new(system_program.clone(), cpi_accounts);
+            system_program::transfer(cpi_ctx, shortfall)?;
+        }
+
+        let cpi_accounts = system_program::Allocate { account_to_allocate: info.clone() };
+        let cpi_ctx = CpiContext::new_with_signer(system_program.clone(), cpi_accounts, signer_seeds);
+        system_program::allocate(cpi_ctx, space as u64)?;
+
+        let cpi_accounts = system_program::Assign { account_to_assign: info.clone() };
+        let cpi_ctx = CpiContext::new_with_signer(system_program.clone(), cpi_accounts, signer_seeds);
+        system_program::assign(cpi_ctx, &crate::ID)?;
+    }
+
+    Ok(())
+}
+
+/// Close a program account at a PDA, if it was ever created
+///
+/// Rent goes to `destination`. Used for per-name accounts that must not
+/// outlive the owner or the vault they were set up for.
+pub fn close_program_account(info: &AccountInfo, destination: &AccountInfo) -> Result<()> {
+    if !is_program_account(info) {
+        return Ok(());
+    }
+
+    let lamports = info.lamports();
+    **destination.try_borrow_mut_lamports()? = destination.lamports()
+        .checked_add(lamports)
+        .ok_or(HydentityError::ArithmeticOverflow)?;
+    **info.try_borrow_mut_lamports()? = 0;
+
+    info.assign(&system_program::ID);
+    info.resize(0)?;
+
+    Ok(())
+}
@@ -1,4 +1,7 @@
 use anchor_lang::prelude::*;
+use crate::constants::VAULT_AUTH_SEED;
+use crate::errors::HydentityError;
+use super::tokenizer_central_state;
 
 /// NameVault - Holds received funds for an SNS name
 /// 
@@ -35,9 +38,45 @@ pub struct NameVault {
     /// When true, the vault authority PDA owns the domain for enhanced privacy
     pub domain_transferred: bool,
     
-    /// Reserved space for future upgrades (split for Default trait)
-    pub _reserved1: [u8; 31],  // Reduced by 1 to accommodate domain_transferred
-    pub _reserved2: [u8; 32],
+    /// Whether a VaultOwnerSet has been configured for M-of-N owner control
+    pub has_owner_set: bool,
+    
+    /// Beneficiary who can take over the vault after owner inactivity
+    /// (default pubkey = no beneficiary configured)
+    pub beneficiary: Pubkey,
+    
+    /// Seconds of owner inactivity after which the beneficiary can claim
+    pub inactivity_window_seconds: i64,
+    
+    /// Timestamp of the last owner-signed instruction
+    pub last_owner_activity_at: i64,
+    
+    /// Whether a DirectWithdrawalConfig timelock has been configured
+    pub has_withdrawal_timelock: bool,
+    
+    /// Whether the SNS domain was last seen owned by neither `owner` nor the
+    /// vault authority (set by `sync_domain_state`, cleared by `claim_vault`)
+    pub owner_lost_domain: bool,
+    
+    /// Whether the owner key was rotated (recovery, owner transfer or
+    /// inheritance) while the domain was still held by a previous key
+    pub owner_rotated: bool,
+    
+    /// Whether the owner of this (parent) domain may create and claim
+    /// vaults for its subdomains
+    pub parent_controls_subdomains: bool,
+    
+    /// Whether this subdomain vault is owned through its parent domain
+    /// rather than the subdomain's own SNS owner
+    pub managed_by_parent: bool,
+    
+    /// Whether a ReclaimConfig timelock has been configured
+    pub has_reclaim_timelock: bool,
+    
+    /// Timestamp of the last sale that re-keyed the vault, or of the rebind
+    /// that created it (0 = neither). Delegate sessions and outflow limits
+    /// from before it no longer apply
+    pub rekeyed_at: i64,  // Takes the last of the reserved space
 }
 
 impl NameVault {
@@ -51,8 +90,17 @@ impl NameVault {
         8 +  // last_deposit_at
         1 +  // bump
         1 +  // domain_transferred
-        31 + // reserved1
-        32;  // reserved2
+        1 +  // has_owner_set
+        32 + // beneficiary
+        8 +  // inactivity_window_seconds
+        8 +  // last_owner_activity_at
+        1 +  // has_withdrawal_timelock
+        1 +  // owner_lost_domain
+        1 +  // owner_rotated
+        1 +  // parent_controls_subdomains
+        1 +  // managed_by_parent
+        1 +  // has_reclaim_timelock
+        8;   // rekeyed_at
     
     /// Initialize the vault with owner and SNS name
     pub fn initialize(&mut self, owner: Pubkey, sns_name: Pubkey, bump: u8, now: i64) {
@@ -64,8 +112,17 @@ impl NameVault {
         self.last_deposit_at = 0;
         self.bump = bump;
         self.domain_transferred = false;
-        self._reserved1 = [0u8; 31];
-        self._reserved2 = [0u8; 32];
+        self.has_owner_set = false;
+        self.beneficiary = Pubkey::default();
+        self.inactivity_window_seconds = 0;
+        self.last_owner_activity_at = now;
+        self.has_withdrawal_timelock = false;
+        self.owner_lost_domain = false;
+        self.owner_rotated = false;
+        self.parent_controls_subdomains = false;
+        self.managed_by_parent = false;
+        self.has_reclaim_timelock = false;
+        self.rekeyed_at = 0;
     }
     
     /// Record a new deposit
@@ -85,8 +142,281 @@ impl NameVault {
         self.owner == *caller
     }
     
+    /// Record that the owner (or owner set) signed an instruction
+    pub fn touch_owner_activity(&mut self, now: i64) {
+        self.last_owner_activity_at = now;
+    }
+    
+    /// Check if a beneficiary is configured
+    pub fn has_beneficiary(&self) -> bool {
+        self.beneficiary != Pubkey::default()
+    }
+    
+    /// Remove the configured beneficiary
+    pub fn clear_beneficiary(&mut self) {
+        self.beneficiary = Pubkey::default();
+        self.inactivity_window_seconds = 0;
+    }
+    
+    /// Check if the owner has been inactive long enough for the beneficiary to claim
+    pub fn is_inheritance_claimable(&self, now: i64) -> bool {
+        self.has_beneficiary()
+            && self.inactivity_window_seconds > 0
+            && now.saturating_sub(self.last_owner_activity_at) >= self.inactivity_window_seconds
+    }
+    
+    /// Check that the beneficiary can take over the vault now
+    ///
+    /// The domain must be in custody so it moves to the beneficiary with the
+    /// vault. Otherwise the inactive owner would still hold it, and
+    /// `verify_domain_control` would freeze the beneficiary for good.
+    pub fn verify_inheritance_claim(&self, now: i64) -> Result<()> {
+        require!(self.is_inheritance_claimable(now), HydentityError::OwnerStillActive);
+        require!(self.domain_transferred, HydentityError::DomainNotTransferred);
+        Ok(())
+    }
+    
     /// Mark domain as transferred to vault
     pub fn set_domain_transferred(&mut self, transferred: bool) {
         self.domain_transferred = transferred;
+        if transferred {
+            self.owner_lost_domain = false;
+            self.owner_rotated = false;
+        }
+    }
+    
+    /// Hand the vault to a new owner key without moving the domain
+    ///
+    /// If the vault authority does not hold the domain, a previous owner key
+    /// still owns it on SNS. `verify_domain_control` only ignores that
+    /// mismatch when the domain's holder co-signed the rotation; otherwise
+    /// a seller could rotate the key after a sale to escape the freeze.
+    pub fn rotate_owner(&mut self, new_owner: Pubkey, domain_holder_signed: bool) {
+        self.owner = new_owner;
+        self.owner_rotated = !self.domain_transferred && domain_holder_signed;
+    }
+    
+    /// Hand the vault to a buyer while the domain stays in custody
+    ///
+    /// The buyer starts over: the seller's owner set, beneficiary and
+    /// withdrawal timelock are dropped, and `rekeyed_at` voids the seller's
+    /// delegate sessions and outflow limits. A leftover owner set account is
+    /// ignored until the buyer configures a new one. The caller closes the
+    /// seller's withdrawal timelock and recovery config accounts. Used by
+    /// `buy_domain`, and by `claim_vault` after a sale outside the program.
+    pub fn rekey_for_sale(&mut self, buyer: Pubkey, now: i64) {
+        self.owner = buyer;
+        self.owner_lost_domain = false;
+        self.owner_rotated = false;
+        self.managed_by_parent = false;
+        self.has_owner_set = false;
+        self.has_withdrawal_timelock = false;
+        self.clear_beneficiary();
+        self.rekeyed_at = now;
+        self.touch_owner_activity(now);
+    }
+    
+    /// Check if a delegate chain whose root session was granted at `granted_at` still applies
+    ///
+    /// Sessions are keyed by name, so they outlive the vault they were
+    /// granted for. Those from before this vault was created, sold or
+    /// rebound belong to another vault or owner and are void.
+    pub fn accepts_delegation_from(&self, granted_at: i64) -> bool {
+        granted_at >= self.created_at && (self.rekeyed_at == 0 || granted_at > self.rekeyed_at)
+    }
+    
+    /// Reject owner-side actions after the domain changed hands
+    ///
+    /// Once a domain is sold, the previous owner keeps control of the vault
+    /// until the buyer calls `claim_vault`. Sensitive instructions call this so
+    /// the seller cannot sweep funds or rewrite the policy in the meantime.
+    /// Vaults whose owner key was rotated without custody, with the domain's
+    /// holder co-signing, are exempt, since a previous key is expected to
+    /// still own the domain, as are subdomain vaults owned through their
+    /// parent domain.
+    ///
+    /// A tokenized domain's holder cannot be read from the name account, so
+    /// for those this relies on `owner_lost_domain` as last recorded by
+    /// `sync_domain_state` with the NFT's token account.
+    pub fn verify_domain_control(&self, sns_name_account: &AccountInfo) -> Result<()> {
+        if self.owner_rotated || self.managed_by_parent {
+            return Ok(());
+        }
+        
+        let sns_owner = read_sns_owner(sns_name_account)?;
+        if sns_owner == self.owner {
+            return Ok(());
+        }
+        
+        if sns_owner == tokenizer_central_state() {
+            require!(!self.owner_lost_domain, HydentityError::DomainClaimPending);
+            return Ok(());
+        }
+        
+        let (vault_authority, _) = Pubkey::find_program_address(
+            &[VAULT_AUTH_SEED, self.sns_name.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(sns_owner, vault_authority, HydentityError::DomainClaimPending);
+        Ok(())
+    }
+    
+    /// Reconcile the domain flags with the current domain holder
+    ///
+    /// `domain_holder` is the SNS owner, or the NFT holder for a tokenized
+    /// domain (see `resolve_domain_holder`). Returns true if either flag changed.
+    pub fn sync_domain_state(&mut self, domain_holder: &Pubkey, vault_authority: &Pubkey) -> bool {
+        let transferred = domain_holder == vault_authority;
+        let lost = !transferred && *domain_holder != self.owner;
+        let changed = transferred != self.domain_transferred || lost != self.owner_lost_domain;
+        self.domain_transferred = transferred;
+        self.owner_lost_domain = lost;
+        changed
+    }
+}
+
+/// Read the current owner of an SNS name account
+///
+/// SNS Name Account Data Layout:
+/// - bytes 0-32: parent_name (Pubkey)
+/// - bytes 32-64: owner (Pubkey)
+/// - bytes 64-96: class (Pubkey)
+/// - bytes 96+: data (variable)
+pub fn read_sns_owner(sns_name_account: &AccountInfo) -> Result<Pubkey> {
+    let data = sns_name_account.try_borrow_data()?;
+    let owner_bytes: [u8; 32] = data
+        .get(32..64)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(HydentityError::InvalidSnsName)?;
+    Ok(Pubkey::new_from_array(owner_bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation_exempt_only_with_holder_signature() {
+        let mut vault = NameVault::default();
+        vault.rotate_owner(Pubkey::new_unique(), true);
+        assert!(vault.owner_rotated);
+
+        // A rotation the domain holder did not sign lifts the exemption
+        vault.rotate_owner(Pubkey::new_unique(), false);
+        assert!(!vault.owner_rotated);
+    }
+
+    #[test]
+    fn test_rotation_in_custody_is_never_exempt() {
+        let mut vault = NameVault::default();
+        vault.set_domain_transferred(true);
+        vault.rotate_owner(Pubkey::new_unique(), true);
+        assert!(!vault.owner_rotated);
+    }
+
+    #[test]
+    fn test_rekey_for_sale_drops_seller_state() {
+        let mut vault = NameVault::default();
+        vault.initialize(Pubkey::new_unique(), Pubkey::new_unique(), 255, 10);
+        vault.has_owner_set = true;
+        vault.has_withdrawal_timelock = true;
+        vault.beneficiary = Pubkey::new_unique();
+        vault.inactivity_window_seconds = crate::constants::MIN_INACTIVITY_WINDOW_SECONDS;
+        vault.owner_rotated = true;
+
+        let buyer = Pubkey::new_unique();
+        vault.rekey_for_sale(buyer, 500);
+
+        assert_eq!(vault.owner, buyer);
+        assert!(!vault.has_owner_set);
+        assert!(!vault.has_withdrawal_timelock);
+        assert!(!vault.has_beneficiary());
+        assert!(!vault.owner_rotated);
+        assert_eq!(vault.rekeyed_at, 500);
+        assert_eq!(vault.last_owner_activity_at, 500);
+    }
+
+    #[test]
+    fn test_delegations_from_before_rebind_are_void() {
+        let mut vault = NameVault::default();
+        vault.initialize(Pubkey::new_unique(), Pubkey::new_unique(), 255, 10);
+        assert!(!vault.accepts_delegation_from(9));
+        assert!(vault.accepts_delegation_from(10));
+
+        // A rebind keeps created_at but starts a new epoch under the new name
+        vault.rekeyed_at = 500;
+        assert!(!vault.accepts_delegation_from(400));
+        assert!(!vault.accepts_delegation_from(500));
+        assert!(vault.accepts_delegation_from(501));
+    }
+
+    #[test]
+    fn test_sync_domain_state_follows_holder() {
+        let owner = Pubkey::new_unique();
+        let vault_authority = Pubkey::new_unique();
+        let mut vault = NameVault::default();
+        vault.initialize(owner, Pubkey::new_unique(), 255, 10);
+
+        // Still held by the owner
+        assert!(!vault.sync_domain_state(&owner, &vault_authority));
+
+        // Moved into custody outside the program
+        assert!(vault.sync_domain_state(&vault_authority, &vault_authority));
+        assert!(vault.domain_transferred && !vault.owner_lost_domain);
+
+        // Sold to someone else
+        assert!(vault.sync_domain_state(&Pubkey::new_unique(), &vault_authority));
+        assert!(!vault.domain_transferred && vault.owner_lost_domain);
+
+        // Back with the owner
+        assert!(vault.sync_domain_state(&owner, &vault_authority));
+        assert!(!vault.domain_transferred && !vault.owner_lost_domain);
+    }
+
+    fn inheritable_vault(domain_transferred: bool) -> NameVault {
+        let mut vault = NameVault::default();
+        vault.initialize(Pubkey::new_unique(), Pubkey::new_unique(), 255, 10);
+        vault.beneficiary = Pubkey::new_unique();
+        vault.inactivity_window_seconds = crate::constants::MIN_INACTIVITY_WINDOW_SECONDS;
+        vault.set_domain_transferred(domain_transferred);
+        vault
+    }
+
+    #[test]
+    fn test_inheritance_requires_domain_in_custody() {
+        let now = 10 + crate::constants::MIN_INACTIVITY_WINDOW_SECONDS;
+
+        // The inactive owner still holds the domain
+        let vault = inheritable_vault(false);
+        assert_eq!(
+            vault.verify_inheritance_claim(now).unwrap_err(),
+            HydentityError::DomainNotTransferred.into()
+        );
+
+        let vault = inheritable_vault(true);
+        assert_eq!(
+            vault.verify_inheritance_claim(now - 1).unwrap_err(),
+            HydentityError::OwnerStillActive.into()
+        );
+        assert!(vault.verify_inheritance_claim(now).is_ok());
+    }
+
+    #[test]
+    fn test_beneficiary_controls_domain_after_inheritance() {
+        let mut vault = inheritable_vault(true);
+        let beneficiary = vault.beneficiary;
+
+        // claim_inheritance moves the domain out of custody to the beneficiary
+        vault.rotate_owner(beneficiary, false);
+        vault.set_domain_transferred(false);
+        vault.clear_beneficiary();
+
+        let name_key = vault.sns_name;
+        let mut lamports = 0;
+        let mut data = [Pubkey::default().to_bytes(), beneficiary.to_bytes(), Pubkey::default().to_bytes()].concat();
+        let name_account = AccountInfo::new(
+            &name_key, false, true, &mut lamports, &mut data, &crate::constants::SNS_NAME_PROGRAM_ID, false, 0,
+        );
+        assert!(vault.verify_domain_control(&name_account).is_ok());
     }
 }
@@ -0,0 +1,324 @@
+use anchor_lang::prelude::*;
+use crate::constants::{OUTFLOW_LIMIT_SEED, OUTFLOW_WINDOW_BUCKETS, OUTFLOW_BUCKET_SECONDS};
+use crate::errors::HydentityError;
+use crate::events::{OutflowLimitOverridden, EVENT_SCHEMA_VERSION};
+use crate::state::{NameVault, RecoveryConfig, is_program_account, create_program_account, close_program_account};
+
+/// OutflowLimit - Rolling 24h outflow cap for one mint of a vault
+///
+/// Outflows are tracked in hourly buckets covering the last 24 hours.
+/// Lowering the limit applies immediately; raising or removing it only
+/// takes effect after a full window, so a stolen owner key cannot lift
+/// the cap and drain the vault in one go.
+///
+/// PDA Seeds: ["outflow_limit", vault_pubkey, mint_pubkey (default = SOL)]
+#[account]
+#[derive(Default)]
+pub struct OutflowLimit {
+    /// The vault this limit applies to
+    pub vault: Pubkey,
+
+    /// Token mint (default pubkey = SOL)
+    pub mint: Pubkey,
+
+    /// Maximum outflow per rolling 24h in base units (0 = unlimited)
+    pub daily_limit: u64,
+
+    /// Raised limit waiting to take effect
+    pub pending_daily_limit: u64,
+
+    /// When the pending limit takes effect (0 = no pending change)
+    pub pending_effective_at: i64,
+
+    /// Outflow per hour, indexed by hour % OUTFLOW_WINDOW_BUCKETS
+    pub buckets: [u64; OUTFLOW_WINDOW_BUCKETS],
+
+    /// Hour index (unix time / 3600) of the most recent bucket written
+    pub current_hour: i64,
+
+    /// Timestamp of last limit update
+    pub updated_at: i64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// Reserved space for future upgrades
+    pub _reserved: [u8; 32],
+}
+
+impl OutflowLimit {
+    /// Account size for rent calculation
+    pub const LEN: usize = 8 + // discriminator
+        32 + // vault
+        32 + // mint
+        8 +  // daily_limit
+        8 +  // pending_daily_limit
+        8 +  // pending_effective_at
+        8 * OUTFLOW_WINDOW_BUCKETS + // buckets
+        8 +  // current_hour
+        8 +  // updated_at
+        1 +  // bump
+        32;  // reserved
+
+    /// Promote a pending limit once it has matured
+    pub fn apply_matured(&mut self, now: i64) {
+        if self.pending_effective_at != 0 && now >= self.pending_effective_at {
+            self.daily_limit = self.pending_daily_limit;
+            self.pending_daily_limit = 0;
+            self.pending_effective_at = 0;
+        }
+    }
+
+    /// Check if a new limit is at least as strict as the current one
+    pub fn is_tightening(&self, daily_limit: u64) -> bool {
+        self.daily_limit == 0 || (daily_limit != 0 && daily_limit <= self.daily_limit)
+    }
+
+    /// Check if the limit was last set before the vault was sold
+    pub fn is_stale(&self, rekeyed_at: i64) -> bool {
+        self.updated_at < rekeyed_at
+    }
+
+    /// Drop buckets that fell out of the 24h window
+    fn roll(&mut self, now: i64) {
+        let hour = now / OUTFLOW_BUCKET_SECONDS;
+        let elapsed = hour.saturating_sub(self.current_hour);
+        if elapsed >= OUTFLOW_WINDOW_BUCKETS as i64 {
+            self.buckets = [0u64; OUTFLOW_WINDOW_BUCKETS];
+        } else {
+            for step in 1..=elapsed {
+                let index = ((self.current_hour + step) as usize) % OUTFLOW_WINDOW_BUCKETS;
+                self.buckets[index] = 0;
+            }
+        }
+        self.current_hour = self.current_hour.max(hour);
+    }
+
+    /// Total outflow over the rolling window
+    pub fn window_total(&self) -> u64 {
+        self.buckets.iter().fold(0u64, |total, amount| total.saturating_add(*amount))
+    }
+
+    /// Record an outflow, rejecting it if it breaks the limit without a second factor
+    ///
+    /// Returns `true` when the outflow exceeded the limit and was allowed
+    /// by the second factor.
+    pub fn record_outflow(&mut self, amount: u64, now: i64, second_factor: bool) -> Result<bool> {
+        self.apply_matured(now);
+        self.roll(now);
+
+        let window_total = self.window_total()
+            .checked_add(amount)
+            .ok_or(HydentityError::ArithmeticOverflow)?;
+        let exceeds = self.daily_limit != 0 && window_total > self.daily_limit;
+        require!(!exceeds || second_factor, HydentityError::OutflowLimitExceeded);
+
+        let index = (self.current_hour as usize) % OUTFLOW_WINDOW_BUCKETS;
+        self.buckets[index] = self.buckets[index]
+            .checked_add(amount)
+            .ok_or(HydentityError::ArithmeticOverflow)?;
+
+        Ok(exceeds)
+    }
+}
+
+/// Check if a recovery guardian co-signed the transaction
+pub fn has_guardian_cosign(recovery_config: Option<&RecoveryConfig>, guardian: Option<&AccountInfo>) -> bool {
+    match (recovery_config, guardian) {
+        (Some(config), Some(guardian)) => guardian.is_signer && config.is_guardian(guardian.key),
+        _ => false,
+    }
+}
+
+/// Count an outflow against the vault's limit for `mint`, if one is configured
+///
+/// `limit_info` must be the OutflowLimit PDA for (vault, mint). When it has
+/// not been initialized, the vault has no limit for that mint. Limits last
+/// set before `rekeyed_at` belong to a previous owner and are ignored.
+pub fn enforce_outflow_limit(
+    limit_info: &AccountInfo,
+    vault: &Pubkey,
+    rekeyed_at: i64,
+    mint: &Pubkey,
+    amount: u64,
+    now: i64,
+    second_factor: bool,
+) -> Result<()> {
+    let (expected, _) = Pubkey::find_program_address(
+        &[OUTFLOW_LIMIT_SEED, vault.as_ref(), mint.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(*limit_info.key, expected, HydentityError::InvalidOutflowLimit);
+
+    // No limit configured for this mint
+    if *limit_info.owner != crate::ID || limit_info.data_is_empty() {
+        return Ok(());
+    }
+
+    let mut limit = {
+        let data = limit_info.try_borrow_data()?;
+        OutflowLimit::try_deserialize(&mut &data[..])?
+    };
+
+    if limit.is_stale(rekeyed_at) {
+        return Ok(());
+    }
+
+    let overridden = limit.record_outflow(amount, now, second_factor)?;
+
+    let mut data = limit_info.try_borrow_mut_data()?;
+    limit.try_serialize(&mut &mut data[..])?;
+
+    if overridden {
+        emit!(OutflowLimitOverridden {
+            version: EVENT_SCHEMA_VERSION,
+            vault: *vault,
+            mint: *mint,
+            amount,
+            window_total: limit.window_total(),
+            timestamp: now,
+        });
+    }
+
+    Ok(())
+}
+
+/// Carry `mint`'s outflow limit over to a vault rebound to a new name
+///
+/// `old_info` and `new_info` must be the OutflowLimit PDAs for `mint` under
+/// the old and the new vault. The new vault inherits the limit, any pending
+/// raise and the outflow already counted in the window, so rebinding cannot
+/// reset the cap. The copy counts as set at rebind time, and the old account
+/// is closed with its rent going to `payer`.
+pub fn migrate_outflow_limit<'info>(
+    old_info: &AccountInfo<'info>,
+    new_info: &AccountInfo<'info>,
+    old_vault: &Account<'info, NameVault>,
+    new_vault: &Pubkey,
+    mint: &Pubkey,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let old_vault_key = old_vault.key();
+    let (old_expected, _) = Pubkey::find_program_address(
+        &[OUTFLOW_LIMIT_SEED, old_vault_key.as_ref(), mint.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(*old_info.key, old_expected, HydentityError::InvalidOutflowLimit);
+    let (new_expected, bump) = Pubkey::find_program_address(
+        &[OUTFLOW_LIMIT_SEED, new_vault.as_ref(), mint.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(*new_info.key, new_expected, HydentityError::InvalidOutflowLimit);
+
+    // No limit configured for this mint
+    if !is_program_account(old_info) {
+        return Ok(());
+    }
+
+    let mut limit = {
+        let data = old_info.try_borrow_data()?;
+        OutflowLimit::try_deserialize(&mut &data[..])?
+    };
+
+    // A limit left by an owner before a sale does not apply, so it is not carried over
+    if !limit.is_stale(old_vault.rekeyed_at) {
+        limit.vault = *new_vault;
+        limit.updated_at = Clock::get()?.unix_timestamp;
+        limit.bump = bump;
+
+        if !is_program_account(new_info) {
+            let signer_seeds: &[&[&[u8]]] = &[&[OUTFLOW_LIMIT_SEED, new_vault.as_ref(), mint.as_ref(), &[bump]]];
+            create_program_account(new_info, OutflowLimit::LEN, signer_seeds, payer, system_program)?;
+        }
+
+        let mut data = new_info.try_borrow_mut_data()?;
+        limit.try_serialize(&mut &mut data[..])?;
+    }
+
+    close_program_account(old_info, payer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: i64 = OUTFLOW_BUCKET_SECONDS;
+
+    fn limit(daily_limit: u64) -> OutflowLimit {
+        OutflowLimit {
+            daily_limit,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_outflow_within_limit() {
+        let mut limit = limit(1_000);
+        assert!(!limit.record_outflow(600, 0, false).unwrap());
+        assert!(!limit.record_outflow(400, HOUR, false).unwrap());
+        assert_eq!(limit.window_total(), 1_000);
+        assert!(limit.record_outflow(1, 2 * HOUR, false).is_err());
+    }
+
+    #[test]
+    fn test_second_factor_overrides_limit() {
+        let mut limit = limit(1_000);
+        assert!(limit.record_outflow(1_500, 0, true).unwrap());
+        assert_eq!(limit.window_total(), 1_500);
+    }
+
+    #[test]
+    fn test_buckets_roll_out_of_window() {
+        let mut limit = limit(1_000);
+        limit.record_outflow(700, 0, false).unwrap();
+        limit.record_outflow(300, 5 * HOUR, false).unwrap();
+
+        // The first outflow is still inside the 24h window
+        assert!(limit.record_outflow(100, 23 * HOUR, false).is_err());
+
+        // One hour later only the second outflow counts
+        limit.record_outflow(700, 24 * HOUR, false).unwrap();
+        assert_eq!(limit.window_total(), 1_000);
+    }
+
+    #[test]
+    fn test_full_window_elapsed_clears_buckets() {
+        let mut limit = limit(1_000);
+        limit.record_outflow(1_000, 0, false).unwrap();
+        limit.record_outflow(1_000, 100 * HOUR, false).unwrap();
+        assert_eq!(limit.window_total(), 1_000);
+    }
+
+    #[test]
+    fn test_same_bucket_reused_after_a_day() {
+        let mut limit = limit(0);
+        limit.record_outflow(500, 3 * HOUR, false).unwrap();
+        limit.record_outflow(200, 27 * HOUR, false).unwrap();
+        assert_eq!(limit.buckets[3], 200);
+        assert_eq!(limit.window_total(), 200);
+    }
+
+    #[test]
+    fn test_raised_limit_waits_for_window() {
+        let mut limit = limit(1_000);
+        assert!(!limit.is_tightening(5_000));
+        assert!(!limit.is_tightening(0));
+        assert!(limit.is_tightening(500));
+
+        limit.pending_daily_limit = 5_000;
+        limit.pending_effective_at = OUTFLOW_WINDOW_BUCKETS as i64 * HOUR;
+        assert!(limit.record_outflow(2_000, HOUR, false).is_err());
+        limit.record_outflow(2_000, OUTFLOW_WINDOW_BUCKETS as i64 * HOUR, false).unwrap();
+        assert_eq!(limit.daily_limit, 5_000);
+    }
+
+    #[test]
+    fn test_limit_set_before_sale_is_stale() {
+        let mut limit = limit(1_000);
+        limit.updated_at = 100;
+        assert!(!limit.is_stale(0));
+        assert!(!limit.is_stale(100));
+        assert!(limit.is_stale(101));
+    }
+}
@@ -0,0 +1,237 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::constants::OWNER_INDEX_SEED;
+use crate::errors::HydentityError;
+use super::{is_program_account, create_program_account};
+
+/// OwnerIndex - The vaults an owner key controls
+///
+/// Lets clients list an owner's vaults with a single account fetch instead
+/// of a `getProgramAccounts` scan. Created on first use and grown by one
+/// entry at a time by every instruction that gives a key ownership of a
+/// vault; entries are removed when the key loses it. Owners of vaults
+/// created before the index existed can add them with `sync_owner_index`.
+///
+/// PDA Seeds: ["owner_index", owner_pubkey]
+#[account]
+#[derive(Default)]
+pub struct OwnerIndex {
+    /// The owner key this index belongs to
+    pub owner: Pubkey,
+
+    /// Vaults currently owned by `owner`
+    pub vaults: Vec<Pubkey>,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl OwnerIndex {
+    /// Account size for an index listing `vault_count` vaults
+    pub const fn space_for(vault_count: usize) -> usize {
+        8 +  // discriminator
+        32 + // owner
+        4 + 32 * vault_count + // vaults
+        1    // bump
+    }
+}
+
+/// Check `index_info` is the OwnerIndex PDA of `owner` and return its bump
+fn verify_owner_index_address(index_info: &AccountInfo, owner: &Pubkey) -> Result<u8> {
+    let (expected, bump) = Pubkey::find_program_address(
+        &[OWNER_INDEX_SEED, owner.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(*index_info.key, expected, HydentityError::InvalidOwnerIndex);
+    Ok(bump)
+}
+
+/// Create an empty index account, paid by `payer`
+fn create_owner_index<'info>(
+    index_info: &AccountInfo<'info>,
+    owner: &Pubkey,
+    bump: u8,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[OWNER_INDEX_SEED, owner.as_ref(), &[bump]]];
+    create_program_account(index_info, OwnerIndex::space_for(0), signer_seeds, payer, system_program)
+}
+
+/// Record `vault` in `owner`'s index, creating or growing the index as needed
+///
+/// `payer` funds the account creation and the rent for each added entry.
+pub fn add_to_owner_index<'info>(
+    index_info: &AccountInfo<'info>,
+    owner: &Pubkey,
+    vault: &Pubkey,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let bump = verify_owner_index_address(index_info, owner)?;
+
+    let mut index = if is_program_account(index_info) {
+        let data = index_info.try_borrow_data()?;
+        OwnerIndex::try_deserialize(&mut &data[..])?
+    } else {
+        create_owner_index(index_info, owner, bump, payer, system_program)?;
+        OwnerIndex {
+            owner: *owner,
+            vaults: Vec::new(),
+            bump,
+        }
+    };
+
+    if index.vaults.contains(vault) {
+        return Ok(());
+    }
+    index.vaults.push(*vault);
+
+    // Grow by one entry, topping up rent for the larger account
+    let required = OwnerIndex::space_for(index.vaults.len());
+    if index_info.data_len() < required {
+        let rent = Rent::get()?.minimum_balance(required);
+        let shortfall = rent.saturating_sub(index_info.lamports());
+        if shortfall > 0 {
+            let cpi_accounts = system_program::Transfer {
+                from: payer.clone(),
+                to: index_info.clone(),
+            };
+            let cpi_ctx = CpiContext::new(system_program.clone(), cpi_accounts);
+            system_program::transfer(cpi_ctx, shortfall)?;
+        }
+        index_info.resize(required)?;
+    }
+
+    let mut data = index_info.try_borrow_mut_data()?;
+    index.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
+
+/// Drop `vault` from `owner`'s index
+///
+/// The account keeps its size so the space is reused by the next entry.
+/// Owners whose vaults predate the index may not have one; that is not an error.
+pub fn remove_from_owner_index(index_info: &AccountInfo, owner: &Pubkey, vault: &Pubkey) -> Result<()> {
+    verify_owner_index_address(index_info, owner)?;
+
+    if !is_program_account(index_info) {
+        return Ok(());
+    }
+
+    let mut index = {
+        let data = index_info.try_borrow_data()?;
+        OwnerIndex::try_deserialize(&mut &data[..])?
+    };
+    index.vaults.retain(|entry| entry != vault);
+
+    let mut data = index_info.try_borrow_mut_data()?;
+    data.fill(0);
+    index.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
+
+/// Move `vault` from `previous_owner`'s index to `new_owner`'s after an ownership change
+pub fn move_owner_index_entry<'info>(
+    previous_index_info: &AccountInfo<'info>,
+    previous_owner: &Pubkey,
+    new_index_info: &AccountInfo<'info>,
+    new_owner: &Pubkey,
+    vault: &Pubkey,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    remove_from_owner_index(previous_index_info, previous_owner, vault)?;
+    add_to_owner_index(new_index_info, new_owner, vault, payer, system_program)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index_address(owner: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[OWNER_INDEX_SEED, owner.as_ref()], &crate::ID).0
+    }
+
+    fn index_data(owner: &Pubkey, vaults: Vec<Pubkey>, space: usize) -> Vec<u8> {
+        let index = OwnerIndex { owner: *owner, vaults, bump: 255 };
+        let mut data = vec![0u8; space];
+        index.try_serialize(&mut &mut data[..]).unwrap();
+        data
+    }
+
+    fn read_vaults(info: &AccountInfo) -> Vec<Pubkey> {
+        let data = info.try_borrow_data().unwrap();
+        OwnerIndex::try_deserialize(&mut &data[..]).unwrap().vaults
+    }
+
+    #[test]
+    fn test_remove_keeps_account_size() {
+        let owner = Pubkey::new_unique();
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let key = index_address(&owner);
+        let mut lamports = 1_000_000;
+        let mut data = index_data(&owner, vec![first, second], OwnerIndex::space_for(2));
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &crate::ID, false, 0);
+
+        remove_from_owner_index(&info, &owner, &first).unwrap();
+        assert_eq!(read_vaults(&info), vec![second]);
+        assert_eq!(info.data_len(), OwnerIndex::space_for(2));
+
+        // Removing a vault that is not listed leaves the index alone
+        remove_from_owner_index(&info, &owner, &first).unwrap();
+        assert_eq!(read_vaults(&info), vec![second]);
+    }
+
+    #[test]
+    fn test_add_reuses_freed_space_and_skips_duplicates() {
+        let owner = Pubkey::new_unique();
+        let (listed, added) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let key = index_address(&owner);
+        let payer_key = Pubkey::new_unique();
+        let system_key = system_program::ID;
+        let mut lamports = 1_000_000;
+        let mut payer_lamports = 1_000_000;
+        let mut system_lamports = 1;
+        let mut data = index_data(&owner, vec![listed], OwnerIndex::space_for(2));
+        let mut payer_data = [];
+        let mut system_data = [];
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &crate::ID, false, 0);
+        let payer = AccountInfo::new(&payer_key, true, true, &mut payer_lamports, &mut payer_data, &system_key, false, 0);
+        let system = AccountInfo::new(&system_key, false, false, &mut system_lamports, &mut system_data, &system_key, true, 0);
+
+        add_to_owner_index(&info, &owner, &added, &payer, &system).unwrap();
+        add_to_owner_index(&info, &owner, &listed, &payer, &system).unwrap();
+        assert_eq!(read_vaults(&info), vec![listed, added]);
+        assert_eq!(info.data_len(), OwnerIndex::space_for(2));
+        assert_eq!(payer.lamports(), 1_000_000);
+    }
+
+    #[test]
+    fn test_remove_without_index_is_a_no_op() {
+        let owner = Pubkey::new_unique();
+        let key = index_address(&owner);
+        let system_key = system_program::ID;
+        let mut lamports = 0;
+        let mut data = [];
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &system_key, false, 0);
+
+        remove_from_owner_index(&info, &owner, &Pubkey::new_unique()).unwrap();
+    }
+
+    #[test]
+    fn test_rejects_another_owners_index() {
+        let owner = Pubkey::new_unique();
+        let vault = Pubkey::new_unique();
+        let key = index_address(&Pubkey::new_unique());
+        let mut lamports = 1_000_000;
+        let mut data = index_data(&owner, vec![vault], OwnerIndex::space_for(1));
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &crate::ID, false, 0);
+
+        assert!(remove_from_owner_index(&info, &owner, &vault).is_err());
+        assert!(add_to_owner_index(&info, &owner, &vault, &info, &info).is_err());
+        assert_eq!(read_vaults(&info), vec![vault]);
+    }
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_OWNER_TRANSFER_DELAY_SECONDS;
+use crate::errors::HydentityError;
+
+/// PendingOwnerTransfer - A proposed rotation of `NameVault.owner`
+///
+/// Lets the owner move the vault to a new key without transferring the
+/// SNS domain. The new key must sign to accept, and only after the
+/// optional delay has passed. Policy and encrypted config are preserved.
+///
+/// PDA Seeds: ["owner_transfer", sns_name_account_pubkey]
+#[account]
+#[derive(Default)]
+pub struct PendingOwnerTransfer {
+    /// The vault whose owner is being rotated
+    pub vault: Pubkey,
+
+    /// The owner key at proposal time
+    pub current_owner: Pubkey,
+
+    /// The key that must accept the transfer
+    pub new_owner: Pubkey,
+
+    /// Account that paid for the proposal (receives rent on close)
+    pub proposed_by: Pubkey,
+
+    /// Whether to reset `PrivacyPolicy.destinations` to the new owner
+    pub reset_destinations: bool,
+
+    /// Timestamp when the transfer was proposed
+    pub proposed_at: i64,
+
+    /// Earliest time the new owner can accept
+    pub executable_at: i64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl PendingOwnerTransfer {
+    /// Account size for rent calculation
+    pub const LEN: usize = 8 + // discriminator
+        32 + // vault
+        32 + // current_owner
+        32 + // new_owner
+        32 + // proposed_by
+        1 +  // reset_destinations
+        8 +  // proposed_at
+        8 +  // executable_at
+        1;   // bump
+
+    /// Validate the requested acceptance delay
+    pub fn validate_delay(delay_seconds: i64) -> Result<()> {
+        require!(
+            (0..=MAX_OWNER_TRANSFER_DELAY_SECONDS).contains(&delay_seconds),
+            HydentityError::InvalidOwnerTransferDelay
+        );
+        Ok(())
+    }
+
+    /// Check whether the transfer can be accepted now
+    pub fn verify_acceptable(&self, current_owner: &Pubkey, now: i64) -> Result<()> {
+        require_keys_eq!(*current_owner, self.current_owner, HydentityError::OwnerTransferStale);
+        require!(now >= self.executable_at, HydentityError::OwnerTransferTimelockActive);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_bounds() {
+        assert!(PendingOwnerTransfer::validate_delay(0).is_ok());
+        assert!(PendingOwnerTransfer::validate_delay(MAX_OWNER_TRANSFER_DELAY_SECONDS).is_ok());
+        assert!(PendingOwnerTransfer::validate_delay(-1).is_err());
+        assert!(PendingOwnerTransfer::validate_delay(MAX_OWNER_TRANSFER_DELAY_SECONDS + 1).is_err());
+    }
+
+    #[test]
+    fn test_acceptance_waits_for_delay_and_same_owner() {
+        let owner = Pubkey::new_unique();
+        let pending = PendingOwnerTransfer {
+            current_owner: owner,
+            new_owner: Pubkey::new_unique(),
+            proposed_at: 100,
+            executable_at: 200,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            pending.verify_acceptable(&owner, 199).unwrap_err(),
+            HydentityError::OwnerTransferTimelockActive.into()
+        );
+        assert!(pending.verify_acceptable(&owner, 200).is_ok());
+
+        // The vault changed hands since the proposal
+        assert_eq!(
+            pending.verify_acceptable(&Pubkey::new_unique(), 200).unwrap_err(),
+            HydentityError::OwnerTransferStale.into()
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use crate::constants::POLICY_HISTORY_ENTRIES;
+use crate::errors::HydentityError;
+use super::PrivacyPolicy;
+
+/// Who made a policy change
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum PolicyActor {
+    /// The vault owner
+    #[default]
+    Owner,
+    /// An owner set member with quorum
+    OwnerSet,
+    /// A delegate with `PERMISSION_UPDATE_POLICY`
+    Delegate,
+    /// A new domain owner taking over the vault via `claim_vault`
+    Claimant,
+    /// A buyer taking over the vault via `buy_domain`
+    Buyer,
+    /// A new owner key accepting `accept_owner_transfer` with a destination reset
+    NewOwner,
+    /// A beneficiary taking over the vault via `claim_inheritance`
+    Beneficiary,
+}
+
+impl PolicyActor {
+    /// Whether the change was authorized as the owner
+    pub fn is_owner(&self) -> bool {
+        matches!(self, PolicyActor::Owner | PolicyActor::OwnerSet)
+    }
+}
+
+/// A single recorded policy change
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct PolicyHistoryEntry {
+    /// `PrivacyPolicy.policy_nonce` after the change
+    pub nonce: u64,
+
+    /// Timestamp of the change
+    pub timestamp: i64,
+
+    /// Key that made the change (queuer for timelocked updates)
+    pub actor: Pubkey,
+
+    /// How the actor was authorized
+    pub actor_type: PolicyActor,
+
+    /// BLAKE3 hash of the serialized policy after the change
+    pub policy_hash: [u8; 32],
+}
+
+impl PolicyHistoryEntry {
+    pub const LEN: usize = 8 + // nonce
+        8 +  // timestamp
+        32 + // actor
+        1 +  // actor_type
+        32;  // policy_hash
+}
+
+/// PolicyHistory - Ring buffer of the most recent policy changes
+///
+/// Appended on every policy change so that changes (and whether an owner
+/// or a delegate made them) can be audited on-chain. Gaps in `nonce`
+/// between consecutive entries indicate changes made by instructions that
+/// do not record history.
+///
+/// PDA Seeds: ["policy_history", sns_name_account_pubkey]
+#[account]
+pub struct PolicyHistory {
+    /// The vault this history belongs to
+    pub vault: Pubkey,
+
+    /// Total number of entries ever recorded
+    pub total_entries: u64,
+
+    /// Slot the next entry is written to
+    pub next_index: u16,
+
+    /// Recorded changes (oldest entries are overwritten first)
+    pub entries: [PolicyHistoryEntry; POLICY_HISTORY_ENTRIES],
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl PolicyHistory {
+    /// Account size for rent calculation
+    pub const LEN: usize = 8 + // discriminator
+        32 + // vault
+        8 +  // total_entries
+        2 +  // next_index
+        (PolicyHistoryEntry::LEN * POLICY_HISTORY_ENTRIES) + // entries
+        1;   // bump
+
+    /// Bind a freshly created history account to its vault
+    pub fn initialize_if_needed(&mut self, vault: Pubkey, bump: u8) {
+        if self.vault == Pubkey::default() {
+            self.vault = vault;
+            self.bump = bump;
+        }
+    }
+
+    /// Append an entry for the policy's current state
+    pub fn record(
+        &mut self,
+        policy: &PrivacyPolicy,
+        actor: Pubkey,
+        actor_type: PolicyActor,
+        now: i64,
+    ) -> Result<()> {
+        let mut data = Vec::with_capacity(PrivacyPolicy::LEN);
+        policy
+            .serialize(&mut data)
+            .map_err(|_| HydentityError::InvalidPolicyConfig)?;
+
+        let index = self.next_index as usize % POLICY_HISTORY_ENTRIES;
+        self.entries[index] = PolicyHistoryEntry {
+            nonce: policy.policy_nonce,
+            timestamp: now,
+            actor,
+            actor_type,
+            policy_hash: *blake3::hash(&data).as_bytes(),
+        };
+        self.next_index = ((index + 1) % POLICY_HISTORY_ENTRIES) as u16;
+        self.total_entries = self.total_entries
+            .checked_add(1)
+            .ok_or(HydentityError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
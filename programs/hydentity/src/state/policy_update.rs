@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use crate::errors::HydentityError;
+use crate::instructions::update_policy::UpdatePolicyParams;
+use super::PolicyActor;
+
+/// PendingPolicyUpdate - A queued change to a timelocked `PrivacyPolicy`
+///
+/// Created by `queue_policy_update` when the policy has a non-zero
+/// `policy_update_delay_seconds`. Anyone can apply it once `effective_at`
+/// has passed; the owner can cancel it before then. This gives the owner
+/// a window to react to a compromised key or delegate swapping the
+/// destinations before a withdrawal.
+///
+/// PDA Seeds: ["pending_policy", sns_name_account_pubkey]
+#[account]
+#[derive(Default)]
+pub struct PendingPolicyUpdate {
+    /// The vault whose policy is being changed
+    pub vault: Pubkey,
+
+    /// The vault owner at queue time
+    pub owner: Pubkey,
+
+    /// The owner, owner set member or delegate that queued the update
+    pub queued_by: Pubkey,
+
+    /// How `queued_by` was authorized (recorded in the policy history)
+    pub actor_type: PolicyActor,
+
+    /// Account that paid for the update (receives rent on close)
+    pub payer: Pubkey,
+
+    /// `PrivacyPolicy.policy_nonce` the update was queued against
+    pub base_nonce: u64,
+
+    /// The policy fields to apply
+    pub params: UpdatePolicyParams,
+
+    /// Timestamp when the update was queued
+    pub queued_at: i64,
+
+    /// Earliest time the update can be applied
+    pub effective_at: i64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl PendingPolicyUpdate {
+    /// Account size for rent calculation (with max destinations)
+    pub const LEN: usize = 8 + // discriminator
+        32 + // vault
+        32 + // owner
+        32 + // queued_by
+        1 +  // actor_type
+        32 + // payer
+        8 +  // base_nonce
+        UpdatePolicyParams::MAX_LEN + // params
+        8 +  // queued_at
+        8 +  // effective_at
+        1;   // bump
+
+    /// Check whether the update can be applied now
+    ///
+    /// Fails if the vault owner changed or the policy was modified by
+    /// another path (claim, inheritance, owner transfer) after queueing.
+    pub fn verify_applicable(&self, current_owner: &Pubkey, policy_nonce: u64, now: i64) -> Result<()> {
+        require_keys_eq!(*current_owner, self.owner, HydentityError::PolicyUpdateStale);
+        require!(policy_nonce == self.base_nonce, HydentityError::PolicyUpdateStale);
+        require!(now >= self.effective_at, HydentityError::PolicyUpdateLocked);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_applies_after_delay_against_same_policy() {
+        let owner = Pubkey::new_unique();
+        let pending = PendingPolicyUpdate {
+            owner,
+            base_nonce: 4,
+            queued_at: 100,
+            effective_at: 400,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            pending.verify_applicable(&owner, 4, 399).unwrap_err(),
+            HydentityError::PolicyUpdateLocked.into()
+        );
+        assert!(pending.verify_applicable(&owner, 4, 400).is_ok());
+
+        // Another path changed the policy, or the vault changed hands
+        assert_eq!(
+            pending.verify_applicable(&owner, 5, 400).unwrap_err(),
+            HydentityError::PolicyUpdateStale.into()
+        );
+        assert_eq!(
+            pending.verify_applicable(&Pubkey::new_unique(), 4, 400).unwrap_err(),
+            HydentityError::PolicyUpdateStale.into()
+        );
+    }
+}
@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::constants::MAX_DESTINATIONS;
+use crate::constants::{
+    MAX_DESTINATIONS, MAX_SPLITS, MIN_DELAY_FLOOR_SECONDS, MAX_DELAY_CEILING_SECONDS,
+    DUST_THRESHOLD_LAMPORTS,
+};
 
 /// Distribution strategy for splitting amounts
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
@@ -37,6 +40,86 @@ pub enum DestinationMode {
     Random,
 }
 
+/// Named combination of split, delay and routing settings
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum PolicyPreset {
+    /// Settings do not match any preset
+    #[default]
+    Custom,
+    /// Quick withdrawals with minimal obfuscation
+    Low,
+    /// Balanced privacy and convenience (program defaults)
+    Medium,
+    /// Longer delays and weighted splits across rotating destinations
+    High,
+    /// Maximum splits and delays with random destination selection
+    Paranoid,
+}
+
+/// Settings a preset applies to the policy
+pub struct PresetSettings {
+    pub min_splits: u8,
+    pub max_splits: u8,
+    pub min_delay_seconds: u32,
+    pub max_delay_seconds: u32,
+    pub distribution: Distribution,
+    pub privacy_mode: PrivacyMode,
+    pub destination_mode: DestinationMode,
+}
+
+impl PolicyPreset {
+    /// Named presets, in increasing order of privacy
+    pub const ALL: [PolicyPreset; 4] = [
+        PolicyPreset::Low,
+        PolicyPreset::Medium,
+        PolicyPreset::High,
+        PolicyPreset::Paranoid,
+    ];
+    
+    /// Settings for the preset (`None` for `Custom`)
+    pub fn settings(&self) -> Option<PresetSettings> {
+        match self {
+            PolicyPreset::Custom => None,
+            PolicyPreset::Low => Some(PresetSettings {
+                min_splits: 1,
+                max_splits: 3,
+                min_delay_seconds: 60,   // 1 minute
+                max_delay_seconds: 600,  // 10 minutes
+                distribution: Distribution::Uniform,
+                privacy_mode: PrivacyMode::FullPrivacy,
+                destination_mode: DestinationMode::Single,
+            }),
+            PolicyPreset::Medium => Some(PresetSettings {
+                min_splits: crate::constants::DEFAULT_MIN_SPLITS,
+                max_splits: crate::constants::DEFAULT_MAX_SPLITS,
+                min_delay_seconds: crate::constants::DEFAULT_MIN_DELAY_SECONDS,
+                max_delay_seconds: crate::constants::DEFAULT_MAX_DELAY_SECONDS,
+                distribution: Distribution::Uniform,
+                privacy_mode: PrivacyMode::FullPrivacy,
+                destination_mode: DestinationMode::Single,
+            }),
+            PolicyPreset::High => Some(PresetSettings {
+                min_splits: 3,
+                max_splits: 6,
+                min_delay_seconds: 7_200,  // 2 hours
+                max_delay_seconds: 28_800, // 8 hours
+                distribution: Distribution::Weighted,
+                privacy_mode: PrivacyMode::FullPrivacy,
+                destination_mode: DestinationMode::Rotating,
+            }),
+            PolicyPreset::Paranoid => Some(PresetSettings {
+                min_splits: 5,
+                max_splits: 10,
+                min_delay_seconds: 21_600, // 6 hours
+                max_delay_seconds: 86_400, // 24 hours
+                distribution: Distribution::Weighted,
+                privacy_mode: PrivacyMode::FullPrivacy,
+                destination_mode: DestinationMode::Random,
+            }),
+        }
+    }
+}
+
 /// PrivacyPolicy - User-configurable privacy settings for claims
 /// 
 /// Controls how funds are withdrawn from Umbra to the private wallet.
@@ -93,8 +176,25 @@ pub struct PrivacyPolicy {
     /// Bump seed for PDA derivation
     pub bump: u8,
     
+    /// Whether direct withdrawals may only go to `destinations`
+    pub enforce_destination_allowlist: bool,
+    
+    /// Seconds after a destinations change before the new list is usable
+    /// for allowlisted direct withdrawals
+    pub allowlist_delay_seconds: u32,
+    
+    /// Timestamp of the last change to `destinations`
+    pub destinations_changed_at: i64,
+    
+    /// Activation delay for policy updates (0 = updates apply immediately)
+    /// When set, updates go through a `PendingPolicyUpdate`
+    pub policy_update_delay_seconds: u32,
+    
+    /// Preset matching the current settings (for display)
+    pub preset: PolicyPreset,
+    
     /// Reserved space for future upgrades
-    pub _reserved: [u8; 64],
+    pub _reserved: [u8; 46],
 }
 
 impl Default for PrivacyPolicy {
@@ -114,7 +214,12 @@ impl Default for PrivacyPolicy {
             policy_nonce: 0,
             updated_at: 0,
             bump: 0,
-            _reserved: [0u8; 64],
+            enforce_destination_allowlist: false,
+            allowlist_delay_seconds: 0,
+            destinations_changed_at: 0,
+            policy_update_delay_seconds: 0,
+            preset: PolicyPreset::Medium,
+            _reserved: [0u8; 46],
         }
     }
 }
@@ -136,7 +241,12 @@ impl PrivacyPolicy {
         8 +  // policy_nonce
         8 +  // updated_at
         1 +  // bump
-        64;  // reserved
+        1 +  // enforce_destination_allowlist
+        4 +  // allowlist_delay_seconds
+        8 +  // destinations_changed_at
+        4 +  // policy_update_delay_seconds
+        1 +  // preset
+        46;  // reserved
     
     /// Initialize the policy with default values
     pub fn initialize(
@@ -161,32 +271,166 @@ impl PrivacyPolicy {
         self.policy_nonce = 0;
         self.updated_at = now;
         self.bump = bump;
-        self._reserved = [0u8; 64];
+        self.enforce_destination_allowlist = false;
+        self.allowlist_delay_seconds = 0;
+        self.destinations_changed_at = now;
+        self.policy_update_delay_seconds = 0;
+        self.preset = PolicyPreset::Medium;
+        self._reserved = [0u8; 46];
     }
     
     /// Validate the policy configuration
+    ///
+    /// Uses the same limits as the MPC circuits so that any policy accepted
+    /// here is also accepted by `store_private_config`.
     pub fn validate(&self) -> Result<()> {
+        self.validate_fields(true, true, true)
+    }
+    
+    /// Validate the policy after an update to `previous`
+    ///
+    /// Policies written before the circuit limits were enforced on-chain
+    /// may hold up to 10 destinations, or split and delay ranges outside
+    /// the circuit limits. Those settings stay valid until they are
+    /// changed: the circuit limits only apply to the split range, delay
+    /// range or destination list when the update touches it.
+    pub fn validate_update(&self, previous: &PrivacyPolicy) -> Result<()> {
+        self.validate_fields(
+            self.min_splits != previous.min_splits || self.max_splits != previous.max_splits,
+            self.min_delay_seconds != previous.min_delay_seconds
+                || self.max_delay_seconds != previous.max_delay_seconds,
+            self.destinations != previous.destinations,
+        )
+    }
+    
+    fn validate_fields(&self, splits_changed: bool, delays_changed: bool, destinations_changed: bool) -> Result<()> {
         // Check split range
-        if self.min_splits > self.max_splits {
+        if self.min_splits > self.max_splits
+            || (splits_changed && (self.min_splits == 0 || self.max_splits as usize > MAX_SPLITS))
+        {
             return Err(crate::errors::HydentityError::InvalidSplitRange.into());
         }
         
         // Check delay range
-        if self.min_delay_seconds > self.max_delay_seconds {
+        if self.min_delay_seconds > self.max_delay_seconds
+            || (delays_changed
+                && (self.min_delay_seconds < MIN_DELAY_FLOOR_SECONDS
+                    || self.max_delay_seconds > MAX_DELAY_CEILING_SECONDS))
+        {
             return Err(crate::errors::HydentityError::InvalidDelayRange.into());
         }
         
         // Check destinations
-        if self.destinations.len() > MAX_DESTINATIONS {
-            return Err(crate::errors::HydentityError::TooManyDestinations.into());
+        if destinations_changed {
+            if self.destinations.len() > MAX_DESTINATIONS {
+                return Err(crate::errors::HydentityError::TooManyDestinations.into());
+            }
+            for (i, destination) in self.destinations.iter().enumerate() {
+                if *destination == Pubkey::default() {
+                    return Err(crate::errors::HydentityError::ZeroAddressDestination.into());
+                }
+                if self.destinations[..i].contains(destination) {
+                    return Err(crate::errors::HydentityError::DuplicateDestination.into());
+                }
+            }
+        }
+        
+        // Check policy timelock
+        if self.policy_update_delay_seconds > crate::constants::MAX_POLICY_UPDATE_DELAY_SECONDS {
+            return Err(crate::errors::HydentityError::InvalidPolicyUpdateDelay.into());
+        }
+        
+        Ok(())
+    }
+    
+    /// Replace the destination list and restart the allowlist delay
+    pub fn set_destinations(&mut self, destinations: Vec<Pubkey>, now: i64) {
+        if destinations != self.destinations {
+            self.destinations = destinations;
+            self.destinations_changed_at = now;
         }
+    }
+    
+    /// Check that a direct withdrawal recipient is allowed by the policy
+    ///
+    /// `recipient` is the destination wallet, or the owner of the
+    /// destination token account for SPL withdrawals.
+    pub fn verify_direct_destination(&self, recipient: &Pubkey, now: i64) -> Result<()> {
+        if !self.enforce_destination_allowlist {
+            return Ok(());
+        }
+        
+        if !self.destinations.contains(recipient) {
+            return Err(crate::errors::HydentityError::DestinationNotAllowlisted.into());
+        }
+        
+        let active_at = self.destinations_changed_at
+            .saturating_add(self.allowlist_delay_seconds as i64);
+        if now < active_at {
+            return Err(crate::errors::HydentityError::AllowlistChangePending.into());
+        }
+        
+        Ok(())
+    }
+    
+    /// Overwrite split, delay and routing settings with a preset
+    ///
+    /// Destinations and allowlist settings are left unchanged.
+    pub fn apply_preset(&mut self, preset: PolicyPreset) -> Result<()> {
+        let settings = preset
+            .settings()
+            .ok_or(crate::errors::HydentityError::InvalidPolicyPreset)?;
         
+        self.min_splits = settings.min_splits;
+        self.max_splits = settings.max_splits;
+        self.min_delay_seconds = settings.min_delay_seconds;
+        self.max_delay_seconds = settings.max_delay_seconds;
+        self.distribution = settings.distribution;
+        self.privacy_mode = settings.privacy_mode;
+        self.destination_mode = settings.destination_mode;
+        self.preset = preset;
         Ok(())
     }
     
+    /// Recompute `preset` from the current settings
+    pub fn refresh_preset(&mut self) {
+        self.preset = PolicyPreset::ALL
+            .into_iter()
+            .find(|preset| preset.settings().is_some_and(|s| self.matches(&s)))
+            .unwrap_or(PolicyPreset::Custom);
+    }
+    
+    fn matches(&self, settings: &PresetSettings) -> bool {
+        self.min_splits == settings.min_splits
+            && self.max_splits == settings.max_splits
+            && self.min_delay_seconds == settings.min_delay_seconds
+            && self.max_delay_seconds == settings.max_delay_seconds
+            && self.distribution == settings.distribution
+            && self.privacy_mode == settings.privacy_mode
+            && self.destination_mode == settings.destination_mode
+    }
+    
+    /// Check that `amount` can be split into `min_splits` parts above dust
+    pub fn verify_split_feasible(&self, amount: u64) -> Result<()> {
+        let minimum = (self.min_splits as u64)
+            .checked_mul(DUST_THRESHOLD_LAMPORTS)
+            .ok_or(crate::errors::HydentityError::ArithmeticOverflow)?;
+        if amount < minimum {
+            return Err(crate::errors::HydentityError::SplitAmountBelowDust.into());
+        }
+        Ok(())
+    }
+    
+    /// Whether updates must be queued behind the policy timelock
+    pub fn is_timelocked(&self) -> bool {
+        self.policy_update_delay_seconds > 0
+    }
+    
     /// Update the policy and increment nonce
-    pub fn update(&mut self, now: i64) -> Result<()> {
-        self.validate()?;
+    ///
+    /// `previous` is the policy as it was before this update.
+    pub fn update(&mut self, previous: &PrivacyPolicy, now: i64) -> Result<()> {
+        self.validate_update(previous)?;
         self.policy_nonce = self.policy_nonce
             .checked_add(1)
             .ok_or(crate::errors::HydentityError::ArithmeticOverflow)?;
@@ -195,3 +439,155 @@ impl PrivacyPolicy {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A policy written under the old limits (10 destinations, 5s delays)
+    fn legacy_policy() -> PrivacyPolicy {
+        PrivacyPolicy {
+            min_splits: 0,
+            max_splits: 12,
+            min_delay_seconds: 5,
+            max_delay_seconds: 30 * 86_400,
+            destinations: (0..10).map(|_| Pubkey::new_unique()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_validate_enforces_circuit_limits() {
+        let mut policy = PrivacyPolicy {
+            destinations: vec![Pubkey::new_unique()],
+            ..Default::default()
+        };
+        assert!(policy.validate().is_ok());
+
+        policy.min_delay_seconds = MIN_DELAY_FLOOR_SECONDS - 1;
+        assert!(policy.validate().is_err());
+        assert!(legacy_policy().validate().is_err());
+    }
+
+    #[test]
+    fn test_legacy_policy_stays_updatable() {
+        let previous = legacy_policy();
+        let mut policy = previous.clone();
+        policy.enabled = false;
+        policy.enforce_destination_allowlist = true;
+        assert!(policy.validate_update(&previous).is_ok());
+    }
+
+    #[test]
+    fn test_changed_fields_must_meet_circuit_limits() {
+        let previous = legacy_policy();
+
+        let mut policy = previous.clone();
+        policy.destinations.pop();
+        assert!(policy.validate_update(&previous).is_err());
+        policy.destinations.truncate(MAX_DESTINATIONS);
+        assert!(policy.validate_update(&previous).is_ok());
+
+        let mut policy = previous.clone();
+        policy.max_splits = 11;
+        assert!(policy.validate_update(&previous).is_err());
+        policy.min_splits = 1;
+        policy.max_splits = MAX_SPLITS as u8;
+        assert!(policy.validate_update(&previous).is_ok());
+
+        let mut policy = previous.clone();
+        policy.min_delay_seconds = 10;
+        assert!(policy.validate_update(&previous).is_err());
+    }
+
+    #[test]
+    fn test_presets_meet_circuit_limits_and_round_trip() {
+        let destination = Pubkey::new_unique();
+        for preset in PolicyPreset::ALL {
+            let mut policy = PrivacyPolicy {
+                destinations: vec![destination],
+                ..Default::default()
+            };
+            policy.apply_preset(preset).unwrap();
+            assert!(policy.validate().is_ok());
+            assert_eq!(policy.destinations, vec![destination]);
+
+            policy.preset = PolicyPreset::Custom;
+            policy.refresh_preset();
+            assert!(policy.preset == preset);
+        }
+    }
+
+    #[test]
+    fn test_custom_preset_rejected_and_edits_become_custom() {
+        let mut policy = PrivacyPolicy::default();
+        assert_eq!(
+            policy.apply_preset(PolicyPreset::Custom).unwrap_err(),
+            crate::errors::HydentityError::InvalidPolicyPreset.into()
+        );
+
+        policy.apply_preset(PolicyPreset::High).unwrap();
+        policy.max_splits += 1;
+        policy.refresh_preset();
+        assert!(policy.preset == PolicyPreset::Custom);
+    }
+
+    #[test]
+    fn test_direct_destination_allowlist() {
+        let allowed = Pubkey::new_unique();
+        let mut policy = PrivacyPolicy {
+            destinations: vec![allowed],
+            destinations_changed_at: 1_000,
+            allowlist_delay_seconds: 600,
+            ..Default::default()
+        };
+
+        // Not enforced: any recipient
+        assert!(policy.verify_direct_destination(&Pubkey::new_unique(), 1_000).is_ok());
+
+        policy.enforce_destination_allowlist = true;
+        assert_eq!(
+            policy.verify_direct_destination(&Pubkey::new_unique(), 2_000).unwrap_err(),
+            crate::errors::HydentityError::DestinationNotAllowlisted.into()
+        );
+        assert_eq!(
+            policy.verify_direct_destination(&allowed, 1_599).unwrap_err(),
+            crate::errors::HydentityError::AllowlistChangePending.into()
+        );
+        assert!(policy.verify_direct_destination(&allowed, 1_600).is_ok());
+    }
+
+    #[test]
+    fn test_split_feasibility_uses_min_splits() {
+        let policy = PrivacyPolicy {
+            min_splits: 3,
+            max_splits: 5,
+            ..Default::default()
+        };
+        assert!(policy.verify_split_feasible(3 * DUST_THRESHOLD_LAMPORTS).is_ok());
+        assert_eq!(
+            policy.verify_split_feasible(3 * DUST_THRESHOLD_LAMPORTS - 1).unwrap_err(),
+            crate::errors::HydentityError::SplitAmountBelowDust.into()
+        );
+    }
+
+    #[test]
+    fn test_inverted_ranges_always_rejected() {
+        let previous = legacy_policy();
+        let mut policy = previous.clone();
+        policy.min_delay_seconds = policy.max_delay_seconds + 1;
+        assert!(policy.validate_update(&previous).is_err());
+    }
+
+    #[test]
+    fn test_duplicate_and_zero_destinations_rejected() {
+        let previous = PrivacyPolicy::default();
+        let destination = Pubkey::new_unique();
+
+        let mut policy = previous.clone();
+        policy.destinations = vec![destination, destination];
+        assert!(policy.validate_update(&previous).is_err());
+
+        policy.destinations = vec![Pubkey::default()];
+        assert!(policy.validate_update(&previous).is_err());
+    }
+}
@@ -0,0 +1,208 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::constants::MAX_RECLAIM_DELAY_SECONDS;
+use crate::errors::HydentityError;
+
+/// ReclaimConfig - Optional timelock for taking the domain out of the vault
+///
+/// Once configured with a non-zero delay, `reclaim_domain` is disabled and
+/// the domain can only leave through `initiate_reclaim` / `complete_reclaim`.
+/// Lengthening the delay applies immediately; shortening it only takes
+/// effect after the current delay, so a stolen owner key cannot simply
+/// switch it off.
+///
+/// PDA Seeds: ["reclaim_config", sns_name_account_pubkey]
+#[account]
+#[derive(Default)]
+pub struct ReclaimConfig {
+    /// The vault this config is associated with
+    pub vault: Pubkey,
+
+    /// Delay between initiating and completing a reclaim (0 = disabled)
+    pub delay_seconds: i64,
+
+    /// Shorter delay waiting to take effect
+    pub pending_delay_seconds: i64,
+
+    /// When the pending change takes effect (0 = no pending change)
+    pub pending_effective_at: i64,
+
+    /// Timestamp of last config update
+    pub updated_at: i64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl ReclaimConfig {
+    /// Account size for rent calculation
+    pub const LEN: usize = 8 + // discriminator
+        32 + // vault
+        8 +  // delay_seconds
+        8 +  // pending_delay_seconds
+        8 +  // pending_effective_at
+        8 +  // updated_at
+        1;   // bump
+
+    /// Validate a requested delay
+    pub fn validate_delay(delay_seconds: i64) -> Result<()> {
+        require!(
+            (0..=MAX_RECLAIM_DELAY_SECONDS).contains(&delay_seconds),
+            HydentityError::InvalidReclaimDelay
+        );
+        Ok(())
+    }
+
+    /// Promote a pending change once it has matured
+    pub fn apply_matured(&mut self, now: i64) {
+        if self.pending_effective_at != 0 && now >= self.pending_effective_at {
+            self.delay_seconds = self.pending_delay_seconds;
+            self.clear_pending();
+        }
+    }
+
+    /// Drop any pending change
+    pub fn clear_pending(&mut self) {
+        self.pending_delay_seconds = 0;
+        self.pending_effective_at = 0;
+    }
+
+    /// Delay in force at `now`
+    pub fn active_delay(&self, now: i64) -> i64 {
+        if self.pending_effective_at != 0 && now >= self.pending_effective_at {
+            self.pending_delay_seconds
+        } else {
+            self.delay_seconds
+        }
+    }
+}
+
+/// PendingReclaim - A domain reclaim waiting out the reclaim delay
+///
+/// Created by `initiate_reclaim`; completed after `executable_at` via
+/// `complete_reclaim`, or closed early with `cancel_reclaim`.
+///
+/// PDA Seeds: ["pending_reclaim", sns_name_account_pubkey]
+#[account]
+#[derive(Default)]
+pub struct PendingReclaim {
+    /// The vault whose domain is being reclaimed
+    pub vault: Pubkey,
+
+    /// The vault owner when the reclaim was initiated
+    pub owner: Pubkey,
+
+    /// Where the domain will be transferred
+    pub destination: Pubkey,
+
+    /// Whether the destination may be a program-owned account
+    pub allow_program_owned: bool,
+
+    /// Account that paid for the request (receives rent on close)
+    pub initiated_by: Pubkey,
+
+    /// Timestamp when the reclaim was initiated
+    pub initiated_at: i64,
+
+    /// Earliest time the reclaim can complete
+    pub executable_at: i64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl PendingReclaim {
+    /// Account size for rent calculation
+    pub const LEN: usize = 8 + // discriminator
+        32 + // vault
+        32 + // owner
+        32 + // destination
+        1 +  // allow_program_owned
+        32 + // initiated_by
+        8 +  // initiated_at
+        8 +  // executable_at
+        1;   // bump
+
+    /// Check whether the reclaim can complete now
+    pub fn verify_completable(&self, current_owner: &Pubkey, now: i64) -> Result<()> {
+        require_keys_eq!(*current_owner, self.owner, HydentityError::ReclaimStale);
+        require!(now >= self.executable_at, HydentityError::ReclaimLocked);
+        Ok(())
+    }
+}
+
+/// Reject reclaim destinations that would strand the domain
+///
+/// The zero key (which is also the system program), executables, the
+/// vault and its authority, and any other Hydentity account are always
+/// rejected. Accounts owned by another program (e.g. a multisig's data
+/// account) are only accepted with `allow_program_owned`.
+pub fn validate_reclaim_destination(
+    destination: &AccountInfo,
+    vault: &Pubkey,
+    vault_authority: &Pubkey,
+    allow_program_owned: bool,
+) -> Result<()> {
+    let key = destination.key;
+    require!(
+        *key != system_program::ID
+            && key != vault
+            && key != vault_authority
+            && *destination.owner != crate::ID
+            && !destination.executable,
+        HydentityError::InvalidReclaimDestination
+    );
+
+    if !allow_program_owned {
+        require_keys_eq!(
+            *destination.owner,
+            system_program::ID,
+            HydentityError::InvalidReclaimDestination
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::SNS_NAME_PROGRAM_ID;
+
+    fn check(key: Pubkey, owner: &Pubkey, executable: bool, allow_program_owned: bool) -> Result<()> {
+        let (vault, vault_authority) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mut lamports, mut data) = (0, vec![]);
+        let destination = AccountInfo::new(&key, false, false, &mut lamports, &mut data, owner, executable, 0);
+        validate_reclaim_destination(&destination, &vault, &vault_authority, allow_program_owned)
+    }
+
+    #[test]
+    fn test_wallet_destination_accepted() {
+        assert!(check(Pubkey::new_unique(), &system_program::ID, false, false).is_ok());
+    }
+
+    #[test]
+    fn test_stranding_destinations_rejected() {
+        let invalid: Result<()> = Err(HydentityError::InvalidReclaimDestination.into());
+        // The zero key is the system program
+        assert_eq!(check(system_program::ID, &system_program::ID, false, true), invalid);
+        assert_eq!(check(Pubkey::new_unique(), &system_program::ID, true, true), invalid);
+        assert_eq!(check(Pubkey::new_unique(), &crate::ID, false, true), invalid);
+
+        let (vault, vault_authority) = (Pubkey::new_unique(), Pubkey::new_unique());
+        for key in [vault, vault_authority] {
+            let (mut lamports, mut data) = (0, vec![]);
+            let destination = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &system_program::ID, false, 0);
+            assert_eq!(validate_reclaim_destination(&destination, &vault, &vault_authority, true), invalid);
+        }
+    }
+
+    #[test]
+    fn test_program_owned_destination_needs_opt_in() {
+        assert_eq!(
+            check(Pubkey::new_unique(), &SNS_NAME_PROGRAM_ID, false, false),
+            Err(HydentityError::InvalidReclaimDestination.into())
+        );
+        assert!(check(Pubkey::new_unique(), &SNS_NAME_PROGRAM_ID, false, true).is_ok());
+    }
+}
@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+use crate::constants::{MAX_GUARDIANS, MIN_RECOVERY_TIMELOCK_SECONDS};
+use crate::errors::HydentityError;
+
+/// RecoveryConfig - Guardians allowed to rotate a lost owner key
+///
+/// The owner registers guardian keys and a threshold. Once `threshold`
+/// guardians approve a new owner key, the rotation can be executed after
+/// `timelock_seconds`, during which the current owner can veto it.
+///
+/// PDA Seeds: ["recovery", sns_name_account_pubkey]
+#[account]
+#[derive(Default)]
+pub struct RecoveryConfig {
+    /// The vault this recovery config is associated with
+    pub vault: Pubkey,
+
+    /// The SNS name account (for verification)
+    pub sns_name: Pubkey,
+
+    /// Guardian public keys
+    pub guardians: Vec<Pubkey>,
+
+    /// Number of guardian approvals required
+    pub threshold: u8,
+
+    /// Veto window between reaching the threshold and execution
+    pub timelock_seconds: i64,
+
+    /// Timestamp of last config update
+    pub updated_at: i64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// Reserved space for future upgrades
+    pub _reserved: [u8; 32],
+}
+
+impl RecoveryConfig {
+    /// Account size for rent calculation (with max guardians)
+    pub const LEN: usize = 8 + // discriminator
+        32 + // vault
+        32 + // sns_name
+        4 + (32 * MAX_GUARDIANS) + // guardians vec (len + data)
+        1 +  // threshold
+        8 +  // timelock_seconds
+        8 +  // updated_at
+        1 +  // bump
+        32;  // reserved
+
+    /// Validate a proposed guardian list, threshold and timelock
+    pub fn validate_config(guardians: &[Pubkey], threshold: u8, timelock_seconds: i64) -> Result<()> {
+        if guardians.is_empty() || guardians.len() > MAX_GUARDIANS {
+            return Err(HydentityError::InvalidRecoveryConfig.into());
+        }
+        if threshold == 0 || threshold as usize > guardians.len() {
+            return Err(HydentityError::InvalidRecoveryConfig.into());
+        }
+        if timelock_seconds < MIN_RECOVERY_TIMELOCK_SECONDS {
+            return Err(HydentityError::InvalidRecoveryConfig.into());
+        }
+        for (i, guardian) in guardians.iter().enumerate() {
+            if *guardian == Pubkey::default() || guardians[..i].contains(guardian) {
+                return Err(HydentityError::InvalidRecoveryConfig.into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Check if a key is a registered guardian
+    pub fn is_guardian(&self, key: &Pubkey) -> bool {
+        self.guardians.contains(key)
+    }
+
+    /// Count approvals that still belong to current guardians
+    pub fn count_valid_approvals(&self, approvals: &[Pubkey]) -> usize {
+        approvals.iter().filter(|key| self.is_guardian(key)).count()
+    }
+}
+
+/// RecoveryRequest - A pending guardian-initiated owner rotation
+///
+/// Created by the first guardian to propose a new owner key. The timelock
+/// starts once the threshold is reached; the current owner can cancel the
+/// request at any point before it is executed.
+///
+/// PDA Seeds: ["recovery_request", sns_name_account_pubkey]
+#[account]
+#[derive(Default)]
+pub struct RecoveryRequest {
+    /// The vault being recovered
+    pub vault: Pubkey,
+
+    /// The owner key being replaced
+    pub previous_owner: Pubkey,
+
+    /// The owner key guardians are approving
+    pub proposed_owner: Pubkey,
+
+    /// Guardian that opened the request (receives rent on close)
+    pub initiator: Pubkey,
+
+    /// Guardians that approved so far
+    pub approvals: Vec<Pubkey>,
+
+    /// Timestamp when the request was opened
+    pub initiated_at: i64,
+
+    /// Earliest execution time (0 until the threshold is reached)
+    pub executable_at: i64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl RecoveryRequest {
+    /// Account size for rent calculation (with max guardians)
+    pub const LEN: usize = 8 + // discriminator
+        32 + // vault
+        32 + // previous_owner
+        32 + // proposed_owner
+        32 + // initiator
+        4 + (32 * MAX_GUARDIANS) + // approvals vec (len + data)
+        8 +  // initiated_at
+        8 +  // executable_at
+        1;   // bump
+
+    /// Record a guardian approval and start the timelock once the threshold is met
+    pub fn approve(&mut self, guardian: Pubkey, config: &RecoveryConfig, now: i64) -> Result<()> {
+        require!(config.is_guardian(&guardian), HydentityError::NotAGuardian);
+        require!(
+            !self.approvals.contains(&guardian),
+            HydentityError::RecoveryAlreadyApproved
+        );
+
+        self.approvals.push(guardian);
+
+        if self.executable_at == 0
+            && config.count_valid_approvals(&self.approvals) >= config.threshold as usize
+        {
+            self.executable_at = now
+                .checked_add(config.timelock_seconds)
+                .ok_or(HydentityError::ArithmeticOverflow)?;
+        }
+
+        Ok(())
+    }
+
+    /// Check whether the request no longer applies to the vault
+    ///
+    /// Requests are void once the vault's owner changed, or if they were
+    /// left behind by an earlier vault for the same domain.
+    pub fn is_stale(&self, vault: &Pubkey, current_owner: &Pubkey) -> bool {
+        self.vault != *vault || self.previous_owner != *current_owner
+    }
+
+    /// Check whether the request can be executed now
+    pub fn verify_executable(&self, config: &RecoveryConfig, current_owner: &Pubkey, now: i64) -> Result<()> {
+        require_keys_eq!(*current_owner, self.previous_owner, HydentityError::RecoveryStale);
+        require!(
+            config.count_valid_approvals(&self.approvals) >= config.threshold as usize,
+            HydentityError::RecoveryThresholdNotMet
+        );
+        require!(
+            self.executable_at != 0 && now >= self.executable_at,
+            HydentityError::RecoveryTimelockActive
+        );
+        Ok(())
+    }
+}
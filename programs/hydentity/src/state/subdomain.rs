@@ -0,0 +1,182 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::constants::{VAULT_SEED, SOL_TLD_AUTHORITY};
+use crate::errors::HydentityError;
+use super::{NameVault, resolve_domain_holder};
+
+/// SubdomainLink - Links a subdomain vault to its parent domain
+///
+/// Created by `initialize_subdomain_vault`. Indexers enumerate the vaults
+/// of a parent domain with a `memcmp` filter on `parent_name`.
+///
+/// PDA Seeds: ["subdomain", parent_name_account_pubkey, subdomain_name_account_pubkey]
+#[account]
+pub struct SubdomainLink {
+    /// The parent domain's SNS name account
+    pub parent_name: Pubkey,
+
+    /// The subdomain's SNS name account
+    pub subdomain_name: Pubkey,
+
+    /// The subdomain's vault
+    pub vault: Pubkey,
+
+    /// Timestamp when the link was created
+    pub created_at: i64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl SubdomainLink {
+    /// Account size for rent calculation
+    pub const LEN: usize = 8 + // discriminator
+        32 + // parent_name
+        32 + // subdomain_name
+        32 + // vault
+        8 +  // created_at
+        1;   // bump
+}
+
+/// Read the parent name (bytes 0..32) of an SNS name account
+pub fn read_sns_parent(sns_name_account: &AccountInfo) -> Result<Pubkey> {
+    let data = sns_name_account.try_borrow_data()?;
+    let parent_bytes: [u8; 32] = data
+        .get(0..32)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(HydentityError::InvalidSnsName)?;
+    Ok(Pubkey::new_from_array(parent_bytes))
+}
+
+/// Whether a name with this parent is a subdomain (rather than a .sol domain)
+pub fn is_subdomain_parent(parent_name: &Pubkey) -> bool {
+    *parent_name != SOL_TLD_AUTHORITY && *parent_name != Pubkey::default()
+}
+
+/// Verify `claimant` may act for a subdomain as the owner of its parent
+///
+/// Requires the parent domain's vault to have opted in with
+/// `set_subdomain_policy`, the claimant to own that vault, and the parent
+/// domain to still be controlled by it.
+fn verify_parent_control(
+    subdomain_name_account: &AccountInfo,
+    parent_name_account: Option<&AccountInfo>,
+    parent_vault: Option<&Account<NameVault>>,
+    claimant: &Pubkey,
+) -> Result<()> {
+    let (Some(parent_name_account), Some(parent_vault)) = (parent_name_account, parent_vault) else {
+        return Err(HydentityError::SnsOwnershipVerificationFailed.into());
+    };
+
+    let parent_name = read_sns_parent(subdomain_name_account)?;
+    require!(is_subdomain_parent(&parent_name), HydentityError::NotASubdomain);
+    require_keys_eq!(*parent_name_account.key, parent_name, HydentityError::InvalidParentDomain);
+
+    let (expected_vault, _) = Pubkey::find_program_address(
+        &[VAULT_SEED, parent_name.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(parent_vault.key(), expected_vault, HydentityError::InvalidParentDomain);
+
+    require!(parent_vault.parent_controls_subdomains, HydentityError::ParentControlDisabled);
+    require!(parent_vault.is_owner(claimant), HydentityError::SnsOwnershipVerificationFailed);
+    parent_vault.verify_domain_control(parent_name_account)?;
+
+    Ok(())
+}
+
+/// Check that the signer holds the name, or controls it through its parent
+///
+/// A tokenized name is held by whoever holds its NFT (see
+/// `resolve_domain_holder`). Returns `true` when ownership was granted
+/// through the parent domain.
+pub fn verify_subdomain_claim(
+    subdomain_name_account: &AccountInfo,
+    nft_token_account: Option<&TokenAccount>,
+    parent_name_account: Option<&AccountInfo>,
+    parent_vault: Option<&Account<NameVault>>,
+    claimant: &Pubkey,
+) -> Result<bool> {
+    if resolve_domain_holder(subdomain_name_account, nft_token_account)? == *claimant {
+        return Ok(false);
+    }
+
+    verify_parent_control(subdomain_name_account, parent_name_account, parent_vault, claimant)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::SNS_NAME_PROGRAM_ID;
+
+    /// Name account data: parent, owner, class
+    fn name_data(parent: &Pubkey, owner: &Pubkey) -> Vec<u8> {
+        [parent.to_bytes(), owner.to_bytes(), Pubkey::default().to_bytes()].concat()
+    }
+
+    fn vault_data(owner: Pubkey, sns_name: Pubkey, parent_controls_subdomains: bool) -> Vec<u8> {
+        let mut vault = NameVault::default();
+        vault.initialize(owner, sns_name, 255, 10);
+        vault.parent_controls_subdomains = parent_controls_subdomains;
+        let mut data = Vec::new();
+        vault.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_only_names_below_a_domain_are_subdomains() {
+        assert!(!is_subdomain_parent(&SOL_TLD_AUTHORITY));
+        assert!(!is_subdomain_parent(&Pubkey::default()));
+        assert!(is_subdomain_parent(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_subdomain_holder_claims_directly() {
+        let (key, holder) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mut lamports, mut data) = (0, name_data(&Pubkey::new_unique(), &holder));
+        let name = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &SNS_NAME_PROGRAM_ID, false, 0);
+
+        assert!(!verify_subdomain_claim(&name, None, None, None, &holder).unwrap());
+        assert_eq!(
+            verify_subdomain_claim(&name, None, None, None, &Pubkey::new_unique()).unwrap_err(),
+            HydentityError::SnsOwnershipVerificationFailed.into()
+        );
+    }
+
+    #[test]
+    fn test_parent_owner_claims_only_when_enabled() {
+        let parent_owner = Pubkey::new_unique();
+        let parent_key = Pubkey::new_unique();
+        let (mut parent_lamports, mut parent_data) = (0, name_data(&SOL_TLD_AUTHORITY, &parent_owner));
+        let parent = AccountInfo::new(&parent_key, false, false, &mut parent_lamports, &mut parent_data, &SNS_NAME_PROGRAM_ID, false, 0);
+
+        let sub_key = Pubkey::new_unique();
+        let (mut sub_lamports, mut sub_data) = (0, name_data(&parent_key, &Pubkey::new_unique()));
+        let subdomain = AccountInfo::new(&sub_key, false, false, &mut sub_lamports, &mut sub_data, &SNS_NAME_PROGRAM_ID, false, 0);
+
+        let (vault_key, _) = Pubkey::find_program_address(&[VAULT_SEED, parent_key.as_ref()], &crate::ID);
+        let (mut lamports, mut data) = (0, vault_data(parent_owner, parent_key, true));
+        let vault_info = AccountInfo::new(&vault_key, false, false, &mut lamports, &mut data, &crate::ID, false, 0);
+        let parent_vault = Account::<NameVault>::try_from(&vault_info).unwrap();
+
+        assert!(verify_subdomain_claim(&subdomain, None, Some(&parent), Some(&parent_vault), &parent_owner).unwrap());
+        assert_eq!(
+            verify_subdomain_claim(&subdomain, None, Some(&parent), Some(&parent_vault), &Pubkey::new_unique()).unwrap_err(),
+            HydentityError::SnsOwnershipVerificationFailed.into()
+        );
+        // The parent vault must belong to the subdomain's actual parent
+        assert_eq!(
+            verify_subdomain_claim(&subdomain, None, Some(&subdomain), Some(&parent_vault), &parent_owner).unwrap_err(),
+            HydentityError::InvalidParentDomain.into()
+        );
+
+        let (mut lamports, mut data) = (0, vault_data(parent_owner, parent_key, false));
+        let vault_info = AccountInfo::new(&vault_key, false, false, &mut lamports, &mut data, &crate::ID, false, 0);
+        let disabled = Account::<NameVault>::try_from(&vault_info).unwrap();
+        assert_eq!(
+            verify_subdomain_claim(&subdomain, None, Some(&parent), Some(&disabled), &parent_owner).unwrap_err(),
+            HydentityError::ParentControlDisabled.into()
+        );
+    }
+}
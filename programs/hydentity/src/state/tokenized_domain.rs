@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::constants::{NAME_TOKENIZER_PROGRAM_ID, TOKENIZED_NAME_MINT_SEED};
+use crate::errors::HydentityError;
+use super::read_sns_owner;
+
+/// The tokenizer's central state PDA, which owns every tokenized domain
+///
+/// PDA Seeds (tokenizer program): [tokenizer_program_id]
+pub fn tokenizer_central_state() -> Pubkey {
+    Pubkey::find_program_address(
+        &[NAME_TOKENIZER_PROGRAM_ID.as_ref()],
+        &NAME_TOKENIZER_PROGRAM_ID,
+    ).0
+}
+
+/// The NFT mint the tokenizer issues for a domain
+///
+/// PDA Seeds (tokenizer program): ["tokenized_name", sns_name_account_pubkey]
+pub fn tokenized_domain_mint(sns_name: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[TOKENIZED_NAME_MINT_SEED, sns_name.as_ref()],
+        &NAME_TOKENIZER_PROGRAM_ID,
+    ).0
+}
+
+/// Resolve who actually controls a domain
+///
+/// For a plain domain this is the SNS owner. A tokenized domain is owned
+/// by the tokenizer's central state, so its holder is the owner of the
+/// token account holding the domain's NFT, which the caller must pass.
+pub fn resolve_domain_holder(
+    sns_name_account: &AccountInfo,
+    nft_token_account: Option<&TokenAccount>,
+) -> Result<Pubkey> {
+    let sns_owner = read_sns_owner(sns_name_account)?;
+    if sns_owner != tokenizer_central_state() {
+        return Ok(sns_owner);
+    }
+
+    let nft_token_account = nft_token_account.ok_or(HydentityError::TokenizedDomainProofRequired)?;
+    require_keys_eq!(
+        nft_token_account.mint,
+        tokenized_domain_mint(sns_name_account.key),
+        HydentityError::TokenizedDomainNotHeld
+    );
+    require!(nft_token_account.amount == 1, HydentityError::TokenizedDomainNotHeld);
+
+    Ok(nft_token_account.owner)
+}
+
+/// Check if the domain's current holder signed the transaction
+///
+/// Rotating a vault's owner key without moving the domain leaves the old
+/// key holding it. Only the holder can vouch that the domain was not sold
+/// in the meantime, so the rotation keeps the vault usable only with its
+/// signature (see `NameVault::rotate_owner`).
+pub fn is_domain_holder_signer(
+    sns_name_account: &AccountInfo,
+    nft_token_account: Option<&TokenAccount>,
+    domain_holder: Option<&AccountInfo>,
+) -> Result<bool> {
+    let Some(domain_holder) = domain_holder.filter(|holder| holder.is_signer) else {
+        return Ok(false);
+    };
+    Ok(resolve_domain_holder(sns_name_account, nft_token_account)? == *domain_holder.key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::program_pack::Pack;
+    use anchor_spl::token::spl_token::state::{Account as SplTokenAccount, AccountState};
+    use crate::constants::SNS_NAME_PROGRAM_ID;
+    use crate::state::NameVault;
+
+    /// Name account data as the tokenizer leaves it: owned by the central state
+    fn tokenized_name_data() -> Vec<u8> {
+        [Pubkey::default().to_bytes(), tokenizer_central_state().to_bytes(), Pubkey::default().to_bytes()].concat()
+    }
+
+    /// Token account holding `amount` of `mint` for `holder`
+    fn nft_account(mint: Pubkey, holder: Pubkey, amount: u64) -> TokenAccount {
+        let mut data = vec![0u8; SplTokenAccount::LEN];
+        SplTokenAccount::pack(
+            SplTokenAccount { mint, owner: holder, amount, state: AccountState::Initialized, ..Default::default() },
+            &mut data,
+        ).unwrap();
+        TokenAccount::try_deserialize(&mut data.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn test_nft_holder_resolves_as_domain_holder() {
+        let name_key = Pubkey::new_unique();
+        let (mut lamports, mut data) = (0, tokenized_name_data());
+        let name_account = AccountInfo::new(&name_key, false, false, &mut lamports, &mut data, &SNS_NAME_PROGRAM_ID, false, 0);
+
+        let holder = Pubkey::new_unique();
+        let nft = nft_account(tokenized_domain_mint(&name_key), holder, 1);
+        assert_eq!(resolve_domain_holder(&name_account, Some(&nft)).unwrap(), holder);
+
+        assert_eq!(
+            resolve_domain_holder(&name_account, None).unwrap_err(),
+            HydentityError::TokenizedDomainProofRequired.into()
+        );
+        let other_mint = nft_account(Pubkey::new_unique(), holder, 1);
+        assert_eq!(
+            resolve_domain_holder(&name_account, Some(&other_mint)).unwrap_err(),
+            HydentityError::TokenizedDomainNotHeld.into()
+        );
+        let emptied = nft_account(tokenized_domain_mint(&name_key), holder, 0);
+        assert_eq!(
+            resolve_domain_holder(&name_account, Some(&emptied)).unwrap_err(),
+            HydentityError::TokenizedDomainNotHeld.into()
+        );
+    }
+
+    #[test]
+    fn test_only_nft_holder_signs_as_domain_holder() {
+        let name_key = Pubkey::new_unique();
+        let (mut lamports, mut data) = (0, tokenized_name_data());
+        let name_account = AccountInfo::new(&name_key, false, false, &mut lamports, &mut data, &SNS_NAME_PROGRAM_ID, false, 0);
+
+        let holder = Pubkey::new_unique();
+        let nft = nft_account(tokenized_domain_mint(&name_key), holder, 1);
+
+        let (mut holder_lamports, mut holder_data) = (0, vec![]);
+        let holder_info = AccountInfo::new(&holder, true, false, &mut holder_lamports, &mut holder_data, &anchor_lang::system_program::ID, false, 0);
+        assert!(is_domain_holder_signer(&name_account, Some(&nft), Some(&holder_info)).unwrap());
+
+        let stranger = Pubkey::new_unique();
+        let (mut stranger_lamports, mut stranger_data) = (0, vec![]);
+        let stranger_info = AccountInfo::new(&stranger, true, false, &mut stranger_lamports, &mut stranger_data, &anchor_lang::system_program::ID, false, 0);
+        assert!(!is_domain_holder_signer(&name_account, Some(&nft), Some(&stranger_info)).unwrap());
+    }
+
+    #[test]
+    fn test_tokenized_holder_keeps_control_until_nft_moves() {
+        let name_key = Pubkey::new_unique();
+        let (mut lamports, mut data) = (0, tokenized_name_data());
+        let name_account = AccountInfo::new(&name_key, false, false, &mut lamports, &mut data, &SNS_NAME_PROGRAM_ID, false, 0);
+
+        let holder = Pubkey::new_unique();
+        let mut vault = NameVault::default();
+        vault.initialize(holder, name_key, 255, 10);
+        let (vault_authority, _) = Pubkey::find_program_address(
+            &[crate::constants::VAULT_AUTH_SEED, name_key.as_ref()],
+            &crate::ID,
+        );
+
+        let nft = nft_account(tokenized_domain_mint(&name_key), holder, 1);
+        vault.sync_domain_state(&resolve_domain_holder(&name_account, Some(&nft)).unwrap(), &vault_authority);
+        assert!(vault.verify_domain_control(&name_account).is_ok());
+
+        // The NFT was sold: once synced, the previous holder is frozen
+        let nft = nft_account(tokenized_domain_mint(&name_key), Pubkey::new_unique(), 1);
+        vault.sync_domain_state(&resolve_domain_holder(&name_account, Some(&nft)).unwrap(), &vault_authority);
+        assert_eq!(
+            vault.verify_domain_control(&name_account).unwrap_err(),
+            HydentityError::DomainClaimPending.into()
+        );
+    }
+}
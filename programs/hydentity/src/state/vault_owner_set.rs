@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_OWNER_SET_SIGNERS;
+use crate::errors::HydentityError;
+use crate::state::NameVault;
+
+/// VaultOwnerSet - Optional M-of-N co-owners for a vault
+///
+/// When present, owner-gated instructions accept either the single
+/// `NameVault.owner` signature or `threshold` distinct signatures from
+/// the keys listed here (the caller plus co-signers passed as remaining
+/// accounts).
+///
+/// PDA Seeds: ["owner_set", sns_name_account_pubkey]
+#[account]
+#[derive(Default)]
+pub struct VaultOwnerSet {
+    /// The vault this owner set is associated with
+    pub vault: Pubkey,
+
+    /// The SNS name account (for verification)
+    pub sns_name: Pubkey,
+
+    /// Keys allowed to co-sign owner actions
+    pub signers: Vec<Pubkey>,
+
+    /// Number of distinct signers required
+    pub threshold: u8,
+
+    /// Timestamp of last owner set update
+    pub updated_at: i64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+
+    /// Reserved space for future upgrades
+    pub _reserved: [u8; 32],
+}
+
+impl VaultOwnerSet {
+    /// Account size for rent calculation (with max signers)
+    pub const LEN: usize = 8 + // discriminator
+        32 + // vault
+        32 + // sns_name
+        4 + (32 * MAX_OWNER_SET_SIGNERS) + // signers vec (len + data)
+        1 +  // threshold
+        8 +  // updated_at
+        1 +  // bump
+        32;  // reserved
+
+    /// Validate a proposed signer list and threshold
+    pub fn validate_config(signers: &[Pubkey], threshold: u8) -> Result<()> {
+        if signers.is_empty() || signers.len() > MAX_OWNER_SET_SIGNERS {
+            return Err(HydentityError::InvalidOwnerSet.into());
+        }
+        if threshold == 0 || threshold as usize > signers.len() {
+            return Err(HydentityError::InvalidOwnerSet.into());
+        }
+        for (i, signer) in signers.iter().enumerate() {
+            if *signer == Pubkey::default() || signers[..i].contains(signer) {
+                return Err(HydentityError::InvalidOwnerSet.into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Check if a key is part of the owner set
+    pub fn is_member(&self, key: &Pubkey) -> bool {
+        self.signers.contains(key)
+    }
+
+    /// Count distinct owner-set members that signed the transaction
+    pub fn count_approvals(&self, caller: &AccountInfo, co_signers: &[AccountInfo]) -> usize {
+        let signed = std::iter::once((caller.key, caller.is_signer))
+            .chain(co_signers.iter().map(|info| (info.key, info.is_signer)));
+
+        let mut approved: Vec<Pubkey> = Vec::with_capacity(self.signers.len());
+        for (key, is_signer) in signed {
+            if is_signer && self.is_member(key) && !approved.contains(key) {
+                approved.push(*key);
+            }
+        }
+        approved.len()
+    }
+
+    /// Verify that enough owner-set members signed
+    pub fn verify_quorum(&self, caller: &AccountInfo, co_signers: &[AccountInfo]) -> Result<()> {
+        // An uninitialized (zeroed) set never authorizes anything
+        if self.threshold == 0 || self.signers.is_empty() {
+            return Err(HydentityError::Unauthorized.into());
+        }
+
+        if self.count_approvals(caller, co_signers) < self.threshold as usize {
+            return Err(HydentityError::OwnerSetThresholdNotMet.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Verify the caller acts with owner authority over the vault
+///
+/// Accepts either the single vault owner, or a quorum of the vault's
+/// owner set when one is configured and provided.
+pub fn verify_owner_authority(
+    vault: &NameVault,
+    caller: &AccountInfo,
+    owner_set: Option<&VaultOwnerSet>,
+    co_signers: &[AccountInfo],
+) -> Result<()> {
+    if caller.is_signer && vault.is_owner(caller.key) {
+        return Ok(());
+    }
+
+    match owner_set {
+        Some(owner_set) if vault.has_owner_set => owner_set.verify_quorum(caller, co_signers),
+        _ => Err(HydentityError::Unauthorized.into()),
+    }
+}
@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+use crate::constants::{MAX_VAULT_PROFILES, MAX_PROFILE_LABEL_LEN};
+use crate::errors::HydentityError;
+use crate::state::is_program_account;
+
+/// VaultProfile - A named receiving profile behind a domain
+///
+/// Lets one domain receive into several separate vaults (e.g. "business"
+/// and "personal"). Each profile has its own vault authority holding its
+/// deposits and its own privacy policy, derived like the domain's vault
+/// accounts with the profile id appended to the seeds. Profiles have no
+/// owner of their own: every profile instruction is authorized against
+/// the domain's vault, so owner changes apply to all profiles at once.
+///
+/// Profile ids start at 1 and are never reused for a domain.
+///
+/// PDA Seeds: ["vault_profile", sns_name_account_pubkey, profile_id]
+/// Vault authority: ["vault_auth", sns_name_account_pubkey, profile_id]
+/// Policy: ["policy", sns_name_account_pubkey, profile_id]
+#[account]
+#[derive(Default)]
+pub struct VaultProfile {
+    /// The domain's vault this profile belongs to
+    pub vault: Pubkey,
+
+    /// The SNS name account
+    pub sns_name: Pubkey,
+
+    /// Profile id (part of the PDA seeds)
+    pub profile_id: u8,
+
+    /// Label senders use to pick the profile
+    pub label: String,
+
+    /// Timestamp when the profile was created
+    pub created_at: i64,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl VaultProfile {
+    /// Account size for rent calculation
+    pub const LEN: usize = 8 + // discriminator
+        32 + // vault
+        32 + // sns_name
+        1 +  // profile_id
+        4 + MAX_PROFILE_LABEL_LEN + // label
+        8 +  // created_at
+        1;   // bump
+}
+
+/// A profile listed in the registry
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, PartialEq, Eq)]
+pub struct ProfileEntry {
+    /// Profile id
+    pub profile_id: u8,
+
+    /// Profile label
+    pub label: String,
+}
+
+impl ProfileEntry {
+    pub const LEN: usize = 1 + 4 + MAX_PROFILE_LABEL_LEN;
+}
+
+/// ProfileRegistry - The profiles of a domain, by label
+///
+/// Senders look up a label here to find the profile id, then derive the
+/// profile's vault authority to deposit into (or call `deposit_to_profile`
+/// with the label).
+///
+/// PDA Seeds: ["profile_registry", sns_name_account_pubkey]
+#[account]
+#[derive(Default)]
+pub struct ProfileRegistry {
+    /// The domain's vault
+    pub vault: Pubkey,
+
+    /// Id for the next profile created
+    pub next_profile_id: u8,
+
+    /// Open profiles
+    pub profiles: Vec<ProfileEntry>,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl ProfileRegistry {
+    /// Account size for rent calculation
+    pub const LEN: usize = 8 + // discriminator
+        32 + // vault
+        1 +  // next_profile_id
+        4 + (ProfileEntry::LEN * MAX_VAULT_PROFILES) + // profiles
+        1;   // bump
+
+    /// Bind a freshly created registry to its vault
+    pub fn initialize_if_needed(&mut self, vault: Pubkey, bump: u8) {
+        if self.vault == Pubkey::default() {
+            self.vault = vault;
+            self.next_profile_id = 1;
+            self.bump = bump;
+        }
+    }
+
+    /// Labels are short lowercase identifiers so senders can type them unambiguously
+    pub fn validate_label(label: &str) -> Result<()> {
+        require!(
+            !label.is_empty()
+                && label.len() <= MAX_PROFILE_LABEL_LEN
+                && label.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-' || b == b'_'),
+            HydentityError::InvalidProfileLabel
+        );
+        Ok(())
+    }
+
+    /// Register a new profile and return its id
+    pub fn add(&mut self, label: &str) -> Result<u8> {
+        Self::validate_label(label)?;
+        require!(self.find(label).is_none(), HydentityError::ProfileLabelTaken);
+        require!(self.profiles.len() < MAX_VAULT_PROFILES, HydentityError::TooManyProfiles);
+
+        let profile_id = self.next_profile_id;
+        self.next_profile_id = profile_id
+            .checked_add(1)
+            .ok_or(HydentityError::TooManyProfiles)?;
+        self.profiles.push(ProfileEntry {
+            profile_id,
+            label: label.to_string(),
+        });
+        Ok(profile_id)
+    }
+
+    /// Look up a profile id by label
+    pub fn find(&self, label: &str) -> Option<u8> {
+        self.profiles
+            .iter()
+            .find(|entry| entry.label == label)
+            .map(|entry| entry.profile_id)
+    }
+
+    /// Drop a profile from the registry
+    pub fn remove(&mut self, profile_id: u8) -> Result<()> {
+        let index = self.profiles
+            .iter()
+            .position(|entry| entry.profile_id == profile_id)
+            .ok_or(HydentityError::ProfileNotFound)?;
+        self.profiles.remove(index);
+        Ok(())
+    }
+}
+
+/// Fail if the domain still has open profiles
+///
+/// `registry_info` must be the domain's ProfileRegistry PDA. A registry that
+/// was never created has no profiles. Profiles are authorized against the
+/// domain's vault, so they must be closed before that vault goes away.
+pub fn verify_no_open_profiles(registry_info: &AccountInfo) -> Result<()> {
+    if !is_program_account(registry_info) {
+        return Ok(());
+    }
+
+    let registry = {
+        let data = registry_info.try_borrow_data()?;
+        ProfileRegistry::try_deserialize(&mut &data[..])?
+    };
+    require!(registry.profiles.is_empty(), HydentityError::ProfilesStillOpen);
+
+    Ok(())
+}
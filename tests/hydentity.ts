@@ -1,8 +1,144 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Hydentity } from "../target/types/hydentity";
-import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  LAMPORTS_PER_SOL,
+  Transaction,
+  TransactionInstruction,
+} from "@solana/web3.js";
 import { expect } from "chai";
+import { createHash } from "crypto";
+
+// SNS programs, cloned into the local validator by Anchor.toml
+const SNS_NAME_PROGRAM_ID = new PublicKey("namesLPneVptA9Z5rqUDD9tMTWEJwofgaYwp8cawRkX");
+const SNS_RECORDS_PROGRAM_ID = new PublicKey("HP3D4D1ZCmohQGFVms2SS4LCANgJyksBf5s1F77FuFjZ");
+const NAME_HEADER_LEN = 96;
+
+const hashName = (name: string) => createHash("sha256").update("SPL Name Service" + name).digest();
+
+const nameAccountKey = (hashedName: Buffer, parent: PublicKey = PublicKey.default) =>
+  PublicKey.findProgramAddressSync(
+    [hashedName, PublicKey.default.toBuffer(), parent.toBuffer()],
+    SNS_NAME_PROGRAM_ID
+  )[0];
+
+/** Register `name` on the SNS name program, owned by `owner` (under `parent` if given) */
+async function createSnsName(
+  provider: anchor.AnchorProvider,
+  owner: Keypair,
+  name: string,
+  parent?: { key: PublicKey; owner: Keypair }
+): Promise<PublicKey> {
+  const hashedName = hashName(name);
+  const nameKey = nameAccountKey(hashedName, parent?.key);
+  const lamports = await provider.connection.getMinimumBalanceForRentExemption(NAME_HEADER_LEN);
+
+  const data = Buffer.alloc(1 + 4 + hashedName.length + 8 + 4);
+  data.writeUInt8(0, 0); // Create
+  data.writeUInt32LE(hashedName.length, 1);
+  hashedName.copy(data, 5);
+  data.writeBigUInt64LE(BigInt(lamports), 5 + hashedName.length);
+  data.writeUInt32LE(0, 13 + hashedName.length); // no data after the header
+
+  const keys = [
+    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    { pubkey: owner.publicKey, isSigner: true, isWritable: true },
+    { pubkey: nameKey, isSigner: false, isWritable: true },
+    { pubkey: owner.publicKey, isSigner: false, isWritable: false },
+    { pubkey: PublicKey.default, isSigner: false, isWritable: false },
+    { pubkey: parent?.key ?? PublicKey.default, isSigner: false, isWritable: false },
+  ];
+  const signers = [owner];
+  if (parent) {
+    keys.push({ pubkey: parent.owner.publicKey, isSigner: true, isWritable: false });
+    signers.push(parent.owner);
+  }
+
+  const ix = new TransactionInstruction({ programId: SNS_NAME_PROGRAM_ID, keys, data });
+  await provider.sendAndConfirm(new Transaction().add(ix), signers);
+  return nameKey;
+}
+
+/** Current owner of an SNS name */
+async function readSnsOwner(provider: anchor.AnchorProvider, nameKey: PublicKey): Promise<PublicKey> {
+  const info = await provider.connection.getAccountInfo(nameKey);
+  return new PublicKey(info!.data.subarray(32, 64));
+}
+
+/** Transfer an SNS name to `newOwner` */
+async function transferSnsName(
+  provider: anchor.AnchorProvider,
+  nameKey: PublicKey,
+  owner: Keypair,
+  newOwner: PublicKey
+) {
+  const ix = new TransactionInstruction({
+    programId: SNS_NAME_PROGRAM_ID,
+    keys: [
+      { pubkey: nameKey, isSigner: false, isWritable: true },
+      { pubkey: owner.publicKey, isSigner: true, isWritable: false },
+    ],
+    data: Buffer.concat([Buffer.from([2]), newOwner.toBuffer()]),
+  });
+  await provider.sendAndConfirm(new Transaction().add(ix), [owner]);
+}
+
+/** `UpdatePolicyParams` that change nothing, to spread overrides into */
+const NO_POLICY_CHANGES = {
+  enabled: null,
+  minSplits: null,
+  maxSplits: null,
+  minDelaySeconds: null,
+  maxDelaySeconds: null,
+  distribution: null,
+  privacyMode: null,
+  destinationMode: null,
+  destinations: null,
+  enforceDestinationAllowlist: null,
+  allowlistDelaySeconds: null,
+  policyUpdateDelaySeconds: null,
+};
+
+/** Airdrop `sol` SOL to `key` */
+async function fund(provider: anchor.AnchorProvider, key: PublicKey, sol: number) {
+  const sig = await provider.connection.requestAirdrop(key, sol * LAMPORTS_PER_SOL);
+  await provider.connection.confirmTransaction(sig);
+}
+
+/** Register a new domain for `owner` and open its vault */
+async function initializeVaultOnNewDomain(program: Program<Hydentity>, owner: Keypair, label: string) {
+  const provider = program.provider as anchor.AnchorProvider;
+  const domain = await createSnsName(provider, owner, `${label}-${Date.now()}`);
+  const pda = (seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+  const accounts = {
+    domain,
+    vault: pda([Buffer.from("vault"), domain.toBuffer()]),
+    vaultAuthority: pda([Buffer.from("vault_auth"), domain.toBuffer()]),
+    policy: pda([Buffer.from("policy"), domain.toBuffer()]),
+    policyHistory: pda([Buffer.from("policy_history"), domain.toBuffer()]),
+    ownerIndex: pda([Buffer.from("owner_index"), owner.publicKey.toBuffer()]),
+  };
+
+  await program.methods
+    .initializeVault()
+    .accounts({
+      owner: owner.publicKey,
+      snsNameAccount: domain,
+      vault: accounts.vault,
+      vaultAuthority: accounts.vaultAuthority,
+      policy: accounts.policy,
+      ownerIndex: accounts.ownerIndex,
+      systemProgram: SystemProgram.programId,
+      nftTokenAccount: null,
+    })
+    .signers([owner])
+    .rpc();
+
+  return accounts;
+}
 
 describe("hydentity", () => {
   // Configure the client to use the local cluster
@@ -14,6 +150,7 @@ describe("hydentity", () => {
   // Test keypairs
   const owner = Keypair.generate();
   const delegate = Keypair.generate();
+  const botDelegate = Keypair.generate();
   const snsNameAccount = Keypair.generate(); // Mock SNS name account
   
   // PDAs
@@ -25,6 +162,7 @@ describe("hydentity", () => {
   let policyBump: number;
   let delegateSessionPda: PublicKey;
   let delegateSessionBump: number;
+  let botSessionPda: PublicKey;
 
   before(async () => {
     // Airdrop SOL to owner
@@ -58,6 +196,15 @@ describe("hydentity", () => {
       ],
       program.programId
     );
+
+    [botSessionPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("delegate"),
+        snsNameAccount.publicKey.toBuffer(),
+        botDelegate.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
   });
 
   describe("initialize_vault", () => {
@@ -129,6 +276,9 @@ describe("hydentity", () => {
           privacyMode: null,
           destinationMode: null,
           destinations: null,
+          enforceDestinationAllowlist: null,
+          allowlistDelaySeconds: null,
+          policyUpdateDelaySeconds: null,
         })
         .accounts({
           authority: owner.publicKey,
@@ -149,6 +299,17 @@ describe("hydentity", () => {
       expect(policyAccount.minDelaySeconds).to.equal(60);
       expect(policyAccount.maxDelaySeconds).to.equal(7200);
       expect(policyAccount.policyNonce.toNumber()).to.equal(1);
+
+      // The change is recorded in the policy history
+      const [historyPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("policy_history"), snsNameAccount.publicKey.toBuffer()],
+        program.programId
+      );
+      const history = await program.account.policyHistory.fetch(historyPda);
+      expect(history.totalEntries.toNumber()).to.equal(1);
+      expect(history.entries[0].nonce.toNumber()).to.equal(1);
+      expect(history.entries[0].actor.toBase58()).to.equal(owner.publicKey.toBase58());
+      expect(history.entries[0].actorType).to.deep.equal({ owner: {} });
     });
 
     it("should fail with invalid split range", async () => {
@@ -164,6 +325,9 @@ describe("hydentity", () => {
             privacyMode: null,
             destinationMode: null,
             destinations: null,
+            enforceDestinationAllowlist: null,
+            allowlistDelaySeconds: null,
+            policyUpdateDelaySeconds: null,
           })
           .accounts({
             authority: owner.publicKey,
@@ -185,7 +349,7 @@ describe("hydentity", () => {
   describe("add_delegate", () => {
     it("should add a delegate", async () => {
       const expiresAt = Math.floor(Date.now() / 1000) + 86400; // 24 hours from now
-      const permissions = 0b00000011; // Both permissions
+      const permissions = 0b00000111; // All permissions, including sub-delegation
 
       const tx = await program.methods
         .addDelegate(new anchor.BN(expiresAt), permissions)
@@ -228,6 +392,9 @@ describe("hydentity", () => {
           privacyMode: null,
           destinationMode: null,
           destinations: null,
+          enforceDestinationAllowlist: null,
+          allowlistDelaySeconds: null,
+          policyUpdateDelaySeconds: null,
         })
         .accounts({
           authority: delegate.publicKey,
@@ -246,6 +413,68 @@ describe("hydentity", () => {
     });
   });
 
+  describe("add_sub_delegate", () => {
+    it("delegate should issue a narrower child session", async () => {
+      const parentSession = await program.account.delegateSession.fetch(delegateSessionPda);
+      const expiresAt = parentSession.expiresAt.toNumber() - 3600;
+      const permissions = 0b00000001; // Update policy only
+
+      const tx = await program.methods
+        .addSubDelegate(new anchor.BN(expiresAt), permissions)
+        .accounts({
+          authority: delegate.publicKey,
+          snsNameAccount: snsNameAccount.publicKey,
+          vault: vaultPda,
+          parentSession: delegateSessionPda,
+          delegate: botDelegate.publicKey,
+          delegateSession: botSessionPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([delegate])
+        .rpc();
+
+      console.log("Add sub-delegate tx:", tx);
+
+      const botSession = await program.account.delegateSession.fetch(botSessionPda);
+      expect(botSession.parent.toString()).to.equal(delegateSessionPda.toString());
+      expect(botSession.grantedBy.toString()).to.equal(delegate.publicKey.toString());
+      expect(botSession.permissions).to.equal(permissions);
+    });
+
+    it("should fail if the child outlives its parent", async () => {
+      const parentSession = await program.account.delegateSession.fetch(delegateSessionPda);
+      const outsider = Keypair.generate();
+      const [outsiderSessionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("delegate"),
+          snsNameAccount.publicKey.toBuffer(),
+          outsider.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .addSubDelegate(new anchor.BN(parentSession.expiresAt.toNumber() + 1), 0b00000001)
+          .accounts({
+            authority: delegate.publicKey,
+            snsNameAccount: snsNameAccount.publicKey,
+            vault: vaultPda,
+            parentSession: delegateSessionPda,
+            delegate: outsider.publicKey,
+            delegateSession: outsiderSessionPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([delegate])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("SubDelegationExceedsParent");
+      }
+    });
+  });
+
   describe("revoke_delegate", () => {
     it("should revoke a delegate", async () => {
       const tx = await program.methods
@@ -270,6 +499,151 @@ describe("hydentity", () => {
         expect(err.message).to.include("Account does not exist");
       }
     });
+
+    it("child session should stop validating once its parent is revoked", async () => {
+      const airdropSig = await provider.connection.requestAirdrop(
+        botDelegate.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+
+      try {
+        await program.methods
+          .updatePolicy({
+            enabled: null,
+            minSplits: 4,
+            maxSplits: null,
+            minDelaySeconds: null,
+            maxDelaySeconds: null,
+            distribution: null,
+            privacyMode: null,
+            destinationMode: null,
+            destinations: null,
+            enforceDestinationAllowlist: null,
+            allowlistDelaySeconds: null,
+            policyUpdateDelaySeconds: null,
+          })
+          .accounts({
+            authority: botDelegate.publicKey,
+            snsNameAccount: snsNameAccount.publicKey,
+            vault: vaultPda,
+            policy: policyPda,
+            delegateSession: botSessionPda,
+          })
+          .remainingAccounts([
+            { pubkey: delegateSessionPda, isSigner: false, isWritable: false },
+          ])
+          .signers([botDelegate])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("ParentDelegateRevoked");
+      }
+    });
+  });
+
+  describe("deposit_to_umbra", () => {
+    const umbraDelegate = Keypair.generate();
+    const umbraPool = Keypair.generate();
+    let umbraSessionPda: PublicKey;
+    let outflowLimitPda: PublicKey;
+
+    before(async () => {
+      [umbraSessionPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("delegate"),
+          snsNameAccount.publicKey.toBuffer(),
+          umbraDelegate.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+      [outflowLimitPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("outflow_limit"), vaultPda.toBuffer(), PublicKey.default.toBuffer()],
+        program.programId
+      );
+
+      const airdropSig = await provider.connection.requestAirdrop(
+        umbraDelegate.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+      const fundSig = await provider.connection.requestAirdrop(vaultPda, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(fundSig);
+
+      const expiresAt = Math.floor(Date.now() / 1000) + 86400;
+      await program.methods
+        .addDelegate(new anchor.BN(expiresAt), 0b00000010) // Deposit to Umbra only
+        .accounts({
+          owner: owner.publicKey,
+          snsNameAccount: snsNameAccount.publicKey,
+          vault: vaultPda,
+          delegate: umbraDelegate.publicKey,
+          delegateSession: umbraSessionPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc();
+    });
+
+    it("delegate with PERMISSION_DEPOSIT_UMBRA should move funds to the pool", async () => {
+      const amount = LAMPORTS_PER_SOL / 10;
+
+      const tx = await program.methods
+        .depositToUmbra(new anchor.BN(amount), null)
+        .accounts({
+          authority: umbraDelegate.publicKey,
+          snsNameAccount: snsNameAccount.publicKey,
+          vault: vaultPda,
+          vaultAuthority: vaultAuthorityPda,
+          policy: policyPda,
+          delegateSession: umbraSessionPda,
+          umbraProgram: SystemProgram.programId,
+          umbraPool: umbraPool.publicKey,
+          vaultTokenAccount: null,
+          umbraPoolTokenAccount: null,
+          ownerSet: null,
+          outflowLimit: outflowLimitPda,
+          recoveryConfig: null,
+          guardian: null,
+        })
+        .signers([umbraDelegate])
+        .rpc();
+
+      console.log("Deposit to Umbra tx:", tx);
+
+      const poolBalance = await provider.connection.getBalance(umbraPool.publicKey);
+      expect(poolBalance).to.equal(amount);
+    });
+
+    it("delegate without PERMISSION_DEPOSIT_UMBRA should be rejected", async () => {
+      try {
+        await program.methods
+          .depositToUmbra(new anchor.BN(LAMPORTS_PER_SOL / 10), null)
+          .accounts({
+            authority: botDelegate.publicKey,
+            snsNameAccount: snsNameAccount.publicKey,
+            vault: vaultPda,
+            vaultAuthority: vaultAuthorityPda,
+            policy: policyPda,
+            delegateSession: botSessionPda,
+            umbraProgram: SystemProgram.programId,
+            umbraPool: umbraPool.publicKey,
+            vaultTokenAccount: null,
+            umbraPoolTokenAccount: null,
+            ownerSet: null,
+            outflowLimit: outflowLimitPda,
+            recoveryConfig: null,
+            guardian: null,
+          })
+          .signers([botDelegate])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("InsufficientPermissions");
+      }
+    });
   });
 
   describe("withdraw_direct", () => {
@@ -346,5 +720,1307 @@ describe("hydentity", () => {
       }
     });
   });
+
+  describe("direct withdrawal timelock", () => {
+    const securityKey = Keypair.generate();
+    const destination = Keypair.generate();
+    const threshold = LAMPORTS_PER_SOL / 10;
+    let withdrawalConfigPda: PublicKey;
+    let outflowLimitPda: PublicKey;
+
+    const pendingPdaFor = (id: number) =>
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("direct_withdrawal"),
+          vaultPda.toBuffer(),
+          new anchor.BN(id).toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+
+    before(async () => {
+      [withdrawalConfigPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("withdraw_config"), snsNameAccount.publicKey.toBuffer()],
+        program.programId
+      );
+      [outflowLimitPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("outflow_limit"), vaultPda.toBuffer(), PublicKey.default.toBuffer()],
+        program.programId
+      );
+
+      const fundSig = await provider.connection.requestAirdrop(vaultAuthorityPda, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(fundSig);
+
+      await program.methods
+        .setWithdrawalTimelock(new anchor.BN(threshold), new anchor.BN(3600), securityKey.publicKey)
+        .accounts({
+          owner: owner.publicKey,
+          snsNameAccount: snsNameAccount.publicKey,
+          vault: vaultPda,
+          withdrawalConfig: withdrawalConfigPda,
+          systemProgram: SystemProgram.programId,
+          ownerSet: null,
+        })
+        .signers([owner])
+        .rpc();
+    });
+
+    it("should reject direct withdrawals above the threshold", async () => {
+      try {
+        await program.methods
+          .withdrawDirect(new anchor.BN(threshold + 1), null)
+          .accounts({
+            owner: owner.publicKey,
+            snsNameAccount: snsNameAccount.publicKey,
+            vault: vaultPda,
+            vaultAuthority: vaultAuthorityPda,
+            policy: policyPda,
+            destination: destination.publicKey,
+            vaultTokenAccount: null,
+            destinationTokenAccount: null,
+            tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            ownerSet: null,
+            withdrawalConfig: withdrawalConfigPda,
+            outflowLimit: outflowLimitPda,
+            recoveryConfig: null,
+            guardian: null,
+          })
+          .signers([owner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("DirectWithdrawalRequiresQueue");
+      }
+    });
+
+    it("should refuse a direct withdrawal that omits the config", async () => {
+      try {
+        await program.methods
+          .withdrawDirect(new anchor.BN(1000), null)
+          .accounts({
+            owner: owner.publicKey,
+            snsNameAccount: snsNameAccount.publicKey,
+            vault: vaultPda,
+            vaultAuthority: vaultAuthorityPda,
+            policy: policyPda,
+            destination: destination.publicKey,
+            vaultTokenAccount: null,
+            destinationTokenAccount: null,
+            tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            ownerSet: null,
+            withdrawalConfig: null,
+            outflowLimit: outflowLimitPda,
+            recoveryConfig: null,
+            guardian: null,
+          })
+          .signers([owner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("WithdrawalConfigRequired");
+      }
+    });
+
+    it("should queue a large withdrawal and keep it locked", async () => {
+      const config = await program.account.directWithdrawalConfig.fetch(withdrawalConfigPda);
+      const pendingPda = pendingPdaFor(config.nextWithdrawalId.toNumber());
+
+      await program.methods
+        .queueDirectWithdrawal(new anchor.BN(threshold * 2), null)
+        .accounts({
+          owner: owner.publicKey,
+          snsNameAccount: snsNameAccount.publicKey,
+          vault: vaultPda,
+          withdrawalConfig: withdrawalConfigPda,
+          policy: policyPda,
+          pendingWithdrawal: pendingPda,
+          destination: destination.publicKey,
+          systemProgram: SystemProgram.programId,
+          ownerSet: null,
+        })
+        .signers([owner])
+        .rpc();
+
+      const pending = await program.account.pendingDirectWithdrawal.fetch(pendingPda);
+      expect(pending.amount.toNumber()).to.equal(threshold * 2);
+      expect(pending.owner.toString()).to.equal(owner.publicKey.toString());
+      expect(pending.unlockAt.toNumber() - pending.requestedAt.toNumber()).to.equal(3600);
+
+      try {
+        await program.methods
+          .executeDirectWithdrawal()
+          .accounts({
+            caller: owner.publicKey,
+            snsNameAccount: snsNameAccount.publicKey,
+            vault: vaultPda,
+            vaultAuthority: vaultAuthorityPda,
+            policy: policyPda,
+            pendingWithdrawal: pendingPda,
+            requestedBy: owner.publicKey,
+            destination: destination.publicKey,
+            vaultTokenAccount: null,
+            tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+            outflowLimit: outflowLimitPda,
+          })
+          .signers([owner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("WithdrawalLocked");
+      }
+    });
+
+    it("security key should cancel a queued withdrawal", async () => {
+      const config = await program.account.directWithdrawalConfig.fetch(withdrawalConfigPda);
+      const pendingPda = pendingPdaFor(config.nextWithdrawalId.toNumber() - 1);
+
+      await program.methods
+        .cancelDirectWithdrawal()
+        .accounts({
+          authority: securityKey.publicKey,
+          snsNameAccount: snsNameAccount.publicKey,
+          vault: vaultPda,
+          withdrawalConfig: withdrawalConfigPda,
+          pendingWithdrawal: pendingPda,
+          requestedBy: owner.publicKey,
+          recoveryConfig: null,
+          ownerSet: null,
+          profile: null,
+        })
+        .signers([securityKey])
+        .rpc();
+
+      const closed = await provider.connection.getAccountInfo(pendingPda);
+      expect(closed).to.be.null;
+    });
+
+    it("should not let the owner switch the timelock off immediately", async () => {
+      await program.methods
+        .setWithdrawalTimelock(new anchor.BN(threshold), new anchor.BN(0), securityKey.publicKey)
+        .accounts({
+          owner: owner.publicKey,
+          snsNameAccount: snsNameAccount.publicKey,
+          vault: vaultPda,
+          withdrawalConfig: withdrawalConfigPda,
+          systemProgram: SystemProgram.programId,
+          ownerSet: null,
+        })
+        .signers([owner])
+        .rpc();
+
+      const config = await program.account.directWithdrawalConfig.fetch(withdrawalConfigPda);
+      expect(config.delaySeconds.toNumber()).to.equal(3600);
+      expect(config.pendingEffectiveAt.toNumber()).to.be.greaterThan(0);
+    });
+  });
+
+  describe("close_vault outflow checks", () => {
+    const closeOwner = Keypair.generate();
+    const closeName = Keypair.generate(); // Mock SNS name account
+    const pda = (seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+    const closeVaultPda = pda([Buffer.from("vault"), closeName.publicKey.toBuffer()]);
+    const closeAuthorityPda = pda([Buffer.from("vault_auth"), closeName.publicKey.toBuffer()]);
+    const closePolicyPda = pda([Buffer.from("policy"), closeName.publicKey.toBuffer()]);
+    const closeIndexPda = pda([Buffer.from("owner_index"), closeOwner.publicKey.toBuffer()]);
+    const closeOutflowPda = pda([
+      Buffer.from("outflow_limit"),
+      closeVaultPda.toBuffer(),
+      PublicKey.default.toBuffer(),
+    ]);
+
+    const closeAccounts = () => ({
+      owner: closeOwner.publicKey,
+      snsNameAccount: closeName.publicKey,
+      vault: closeVaultPda,
+      vaultAuthority: closeAuthorityPda,
+      policy: closePolicyPda,
+      ownerIndex: closeIndexPda,
+      ownerSet: null,
+      withdrawalConfig: null,
+      outflowLimit: closeOutflowPda,
+      recoveryConfig: null,
+      guardian: null,
+      profileRegistry: pda([Buffer.from("profile_registry"), closeName.publicKey.toBuffer()]),
+    });
+
+    before(async () => {
+      const airdropSig = await provider.connection.requestAirdrop(
+        closeOwner.publicKey,
+        5 * LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+
+      await program.methods
+        .initializeVault()
+        .accounts({
+          owner: closeOwner.publicKey,
+          snsNameAccount: closeName.publicKey,
+          vault: closeVaultPda,
+          vaultAuthority: closeAuthorityPda,
+          policy: closePolicyPda,
+          ownerIndex: closeIndexPda,
+          systemProgram: SystemProgram.programId,
+          nftTokenAccount: null,
+        })
+        .signers([closeOwner])
+        .rpc();
+
+      await program.methods
+        .setOutflowLimit(PublicKey.default, new anchor.BN(LAMPORTS_PER_SOL / 10))
+        .accounts({
+          owner: closeOwner.publicKey,
+          snsNameAccount: closeName.publicKey,
+          vault: closeVaultPda,
+          outflowLimit: closeOutflowPda,
+          systemProgram: SystemProgram.programId,
+          ownerSet: null,
+        })
+        .signers([closeOwner])
+        .rpc();
+
+      const fundSig = await provider.connection.requestAirdrop(closeAuthorityPda, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(fundSig);
+    });
+
+    it("should not drain a balance above the outflow limit by closing", async () => {
+      try {
+        await program.methods
+          .closeVault()
+          .accounts(closeAccounts())
+          .signers([closeOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("OutflowLimitExceeded");
+      }
+
+      const vaultInfo = await provider.connection.getAccountInfo(closeVaultPda);
+      expect(vaultInfo).to.not.be.null;
+    });
+
+    it("should check the outflow limit account address", async () => {
+      try {
+        await program.methods
+          .closeVault()
+          .accounts({ ...closeAccounts(), outflowLimit: Keypair.generate().publicKey })
+          .signers([closeOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("InvalidOutflowLimit");
+      }
+    });
+
+    it("should list the vault in the owner's index", async () => {
+      const index = await program.account.ownerIndex.fetch(closeIndexPda);
+      expect(index.owner.toString()).to.equal(closeOwner.publicKey.toString());
+      expect(index.vaults.map((vault) => vault.toString())).to.deep.equal([closeVaultPda.toString()]);
+    });
+  });
+
+  describe("profile withdrawal timelock", () => {
+    const profileOwner = Keypair.generate();
+    const profileName = Keypair.generate(); // Mock SNS name account
+    const securityKey = Keypair.generate();
+    const destination = Keypair.generate();
+    const threshold = LAMPORTS_PER_SOL / 10;
+    const profileId = 1;
+    const pda = (seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+    const name = profileName.publicKey.toBuffer();
+    const idSeed = Buffer.from([profileId]);
+    const vault = pda([Buffer.from("vault"), name]);
+    const vaultAuthority = pda([Buffer.from("vault_auth"), name]);
+    const policy = pda([Buffer.from("policy"), name]);
+    const ownerIndex = pda([Buffer.from("owner_index"), profileOwner.publicKey.toBuffer()]);
+    const registry = pda([Buffer.from("profile_registry"), name]);
+    const profile = pda([Buffer.from("vault_profile"), name, idSeed]);
+    const profileVaultAuthority = pda([Buffer.from("vault_auth"), name, idSeed]);
+    const profilePolicy = pda([Buffer.from("policy"), name, idSeed]);
+    const withdrawalConfig = pda([Buffer.from("withdraw_config"), name]);
+    const outflowLimit = pda([Buffer.from("outflow_limit"), vault.toBuffer(), PublicKey.default.toBuffer()]);
+
+    const pendingPdaFor = (id: number) =>
+      pda([
+        Buffer.from("direct_withdrawal"),
+        profile.toBuffer(),
+        new anchor.BN(id).toArrayLike(Buffer, "le", 8),
+      ]);
+
+    before(async () => {
+      const airdropSig = await provider.connection.requestAirdrop(
+        profileOwner.publicKey,
+        5 * LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+
+      await program.methods
+        .initializeVault()
+        .accounts({
+          owner: profileOwner.publicKey,
+          snsNameAccount: profileName.publicKey,
+          vault,
+          vaultAuthority,
+          policy,
+          ownerIndex,
+          systemProgram: SystemProgram.programId,
+          nftTokenAccount: null,
+        })
+        .signers([profileOwner])
+        .rpc();
+
+      await program.methods
+        .createVaultProfile("business", profileId)
+        .accounts({
+          owner: profileOwner.publicKey,
+          snsNameAccount: profileName.publicKey,
+          vault,
+          registry,
+          profile,
+          profileVaultAuthority,
+          profilePolicy,
+          systemProgram: SystemProgram.programId,
+          ownerSet: null,
+        })
+        .signers([profileOwner])
+        .rpc();
+
+      const fundSig = await provider.connection.requestAirdrop(profileVaultAuthority, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(fundSig);
+
+      await program.methods
+        .setWithdrawalTimelock(new anchor.BN(threshold), new anchor.BN(3600), securityKey.publicKey)
+        .accounts({
+          owner: profileOwner.publicKey,
+          snsNameAccount: profileName.publicKey,
+          vault,
+          withdrawalConfig,
+          systemProgram: SystemProgram.programId,
+          ownerSet: null,
+        })
+        .signers([profileOwner])
+        .rpc();
+    });
+
+    it("should reject direct profile withdrawals above the threshold", async () => {
+      try {
+        await program.methods
+          .withdrawProfileDirect(profileId, new anchor.BN(threshold + 1))
+          .accounts({
+            owner: profileOwner.publicKey,
+            snsNameAccount: profileName.publicKey,
+            vault,
+            profile,
+            profileVaultAuthority,
+            profilePolicy,
+            destination: destination.publicKey,
+            profileTokenAccount: null,
+            destinationTokenAccount: null,
+            tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            ownerSet: null,
+            withdrawalConfig,
+            outflowLimit,
+            recoveryConfig: null,
+            guardian: null,
+          })
+          .signers([profileOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("DirectWithdrawalRequiresQueue");
+      }
+    });
+
+    it("should queue a large profile withdrawal and keep it locked", async () => {
+      const config = await program.account.directWithdrawalConfig.fetch(withdrawalConfig);
+      const pendingPda = pendingPdaFor(config.nextWithdrawalId.toNumber());
+
+      await program.methods
+        .queueProfileWithdrawal(profileId, new anchor.BN(threshold * 2), null)
+        .accounts({
+          owner: profileOwner.publicKey,
+          snsNameAccount: profileName.publicKey,
+          vault,
+          profile,
+          withdrawalConfig,
+          profilePolicy,
+          pendingWithdrawal: pendingPda,
+          destination: destination.publicKey,
+          systemProgram: SystemProgram.programId,
+          ownerSet: null,
+        })
+        .signers([profileOwner])
+        .rpc();
+
+      const pending = await program.account.pendingDirectWithdrawal.fetch(pendingPda);
+      expect(pending.vault.toString()).to.equal(profile.toString());
+      expect(pending.amount.toNumber()).to.equal(threshold * 2);
+
+      try {
+        await program.methods
+          .executeProfileWithdrawal(profileId)
+          .accounts({
+            caller: profileOwner.publicKey,
+            snsNameAccount: profileName.publicKey,
+            vault,
+            profile,
+            profileVaultAuthority,
+            profilePolicy,
+            pendingWithdrawal: pendingPda,
+            requestedBy: profileOwner.publicKey,
+            destination: destination.publicKey,
+            profileTokenAccount: null,
+            tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+            outflowLimit,
+          })
+          .signers([profileOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("WithdrawalLocked");
+      }
+    });
+
+    it("security key should cancel a queued profile withdrawal", async () => {
+      const config = await program.account.directWithdrawalConfig.fetch(withdrawalConfig);
+      const pendingPda = pendingPdaFor(config.nextWithdrawalId.toNumber() - 1);
+
+      await program.methods
+        .cancelDirectWithdrawal()
+        .accounts({
+          authority: securityKey.publicKey,
+          snsNameAccount: profileName.publicKey,
+          vault,
+          withdrawalConfig,
+          pendingWithdrawal: pendingPda,
+          requestedBy: profileOwner.publicKey,
+          recoveryConfig: null,
+          ownerSet: null,
+          profile,
+        })
+        .signers([securityKey])
+        .rpc();
+
+      const closed = await provider.connection.getAccountInfo(pendingPda);
+      expect(closed).to.be.null;
+    });
+
+    it("should not close a vault that still has profiles", async () => {
+      try {
+        await program.methods
+          .closeVault()
+          .accounts({
+            owner: profileOwner.publicKey,
+            snsNameAccount: profileName.publicKey,
+            vault,
+            vaultAuthority,
+            policy,
+            ownerIndex,
+            ownerSet: null,
+            withdrawalConfig,
+            outflowLimit,
+            recoveryConfig: null,
+            guardian: null,
+            profileRegistry: registry,
+          })
+          .signers([profileOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("ProfilesStillOpen");
+      }
+    });
+  });
+
+  describe("set_vault_sol_record", () => {
+    const recordOwner = Keypair.generate();
+    const buyer = Keypair.generate();
+    const recordsCentralState = PublicKey.findProgramAddressSync(
+      [SNS_RECORDS_PROGRAM_ID.toBuffer()],
+      SNS_RECORDS_PROGRAM_ID
+    )[0];
+    let domain: PublicKey;
+    let recordVaultPda: PublicKey;
+    let recordAuthorityPda: PublicKey;
+    let solRecord: PublicKey;
+
+    const recordAccounts = () => ({
+      owner: recordOwner.publicKey,
+      snsNameAccount: domain,
+      vault: recordVaultPda,
+      vaultAuthority: recordAuthorityPda,
+      solRecord,
+      recordsCentralState,
+      snsRecordsProgram: SNS_RECORDS_PROGRAM_ID,
+      snsNameProgram: SNS_NAME_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      ownerSet: null,
+    });
+
+    before(async () => {
+      await fund(provider, recordOwner.publicKey, 5);
+
+      ({ domain, vault: recordVaultPda, vaultAuthority: recordAuthorityPda } =
+        await initializeVaultOnNewDomain(program, recordOwner, "record"));
+      solRecord = PublicKey.findProgramAddressSync(
+        [hashName("\x02SOL"), recordsCentralState.toBuffer(), domain.toBuffer()],
+        SNS_NAME_PROGRAM_ID
+      )[0];
+    });
+
+    it("should point the SOL record at the vault authority", async () => {
+      await program.methods
+        .setVaultSolRecord()
+        .accounts(recordAccounts())
+        .signers([recordOwner])
+        .rpc();
+
+      // Header, validation kinds and content length, staleness id, right of association id, content
+      const record = await provider.connection.getAccountInfo(solRecord);
+      const content = record!.data.subarray(NAME_HEADER_LEN + 72, NAME_HEADER_LEN + 104);
+      expect(new PublicKey(content).toString()).to.equal(recordAuthorityPda.toString());
+    });
+
+    it("should refuse a seller after the domain is sold", async () => {
+      await transferSnsName(provider, domain, recordOwner, buyer.publicKey);
+
+      try {
+        await program.methods
+          .setVaultSolRecord()
+          .accounts(recordAccounts())
+          .signers([recordOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("DomainClaimPending");
+      }
+    });
+  });
+
+  describe("owner transfer", () => {
+    const currentOwner = Keypair.generate();
+    const newOwner = Keypair.generate();
+    const stranger = Keypair.generate();
+    const pda = (seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+    let accounts: Awaited<ReturnType<typeof initializeVaultOnNewDomain>>;
+    let pendingTransferPda: PublicKey;
+
+    const proposeTransfer = (delaySeconds: number) =>
+      program.methods
+        .proposeOwnerTransfer(newOwner.publicKey, new anchor.BN(delaySeconds), true)
+        .accounts({
+          owner: currentOwner.publicKey,
+          snsNameAccount: accounts.domain,
+          vault: accounts.vault,
+          pendingTransfer: pendingTransferPda,
+          systemProgram: SystemProgram.programId,
+          ownerSet: null,
+        })
+        .signers([currentOwner])
+        .rpc();
+
+    const acceptTransfer = (signer: Keypair) =>
+      program.methods
+        .acceptOwnerTransfer()
+        .accounts({
+          newOwner: signer.publicKey,
+          snsNameAccount: accounts.domain,
+          vault: accounts.vault,
+          policy: accounts.policy,
+          policyHistory: accounts.policyHistory,
+          pendingTransfer: pendingTransferPda,
+          proposedBy: currentOwner.publicKey,
+          previousOwnerIndex: accounts.ownerIndex,
+          newOwnerIndex: pda([Buffer.from("owner_index"), signer.publicKey.toBuffer()]),
+          systemProgram: SystemProgram.programId,
+          domainHolder: currentOwner.publicKey,
+          nftTokenAccount: null,
+        })
+        .signers([signer, currentOwner])
+        .rpc();
+
+    before(async () => {
+      await fund(provider, currentOwner.publicKey, 5);
+      await fund(provider, newOwner.publicKey, 1);
+      await fund(provider, stranger.publicKey, 1);
+
+      accounts = await initializeVaultOnNewDomain(program, currentOwner, "rotate");
+      pendingTransferPda = pda([Buffer.from("owner_transfer"), accounts.domain.toBuffer()]);
+    });
+
+    it("should refuse acceptance before the delay has passed", async () => {
+      await proposeTransfer(3600);
+
+      try {
+        await acceptTransfer(newOwner);
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("OwnerTransferTimelockActive");
+      }
+
+      await program.methods
+        .cancelOwnerTransfer()
+        .accounts({
+          authority: currentOwner.publicKey,
+          snsNameAccount: accounts.domain,
+          vault: accounts.vault,
+          pendingTransfer: pendingTransferPda,
+          proposedBy: currentOwner.publicKey,
+          ownerSet: null,
+        })
+        .signers([currentOwner])
+        .rpc();
+
+      expect(await provider.connection.getAccountInfo(pendingTransferPda)).to.be.null;
+    });
+
+    it("should refuse acceptance by another key", async () => {
+      await proposeTransfer(0);
+
+      try {
+        await acceptTransfer(stranger);
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("Unauthorized");
+      }
+    });
+
+    it("should rotate the owner key when the new key accepts", async () => {
+      await acceptTransfer(newOwner);
+
+      const vault = await program.account.nameVault.fetch(accounts.vault);
+      expect(vault.owner.toString()).to.equal(newOwner.publicKey.toString());
+
+      // The proposal opted into a destination reset, which is recorded
+      const policy = await program.account.privacyPolicy.fetch(accounts.policy);
+      expect(policy.destinations.map((key) => key.toString())).to.deep.equal([newOwner.publicKey.toString()]);
+      const history = await program.account.policyHistory.fetch(accounts.policyHistory);
+      expect(history.totalEntries.toNumber()).to.equal(1);
+      expect(history.entries[0].actor.toString()).to.equal(newOwner.publicKey.toString());
+      expect(history.entries[0].actorType).to.deep.equal({ newOwner: {} });
+    });
+  });
+
+  describe("direct withdrawal allowlist", () => {
+    const allowOwner = Keypair.generate();
+    const allowed = Keypair.generate();
+    const notAllowed = Keypair.generate();
+    const pda = (seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+    let accounts: Awaited<ReturnType<typeof initializeVaultOnNewDomain>>;
+
+    const withdrawTo = (destination: PublicKey) =>
+      program.methods
+        .withdrawDirect(new anchor.BN(LAMPORTS_PER_SOL / 100), null)
+        .accounts({
+          owner: allowOwner.publicKey,
+          snsNameAccount: accounts.domain,
+          vault: accounts.vault,
+          vaultAuthority: accounts.vaultAuthority,
+          policy: accounts.policy,
+          destination,
+          vaultTokenAccount: null,
+          destinationTokenAccount: null,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          ownerSet: null,
+          withdrawalConfig: null,
+          outflowLimit: pda([Buffer.from("outflow_limit"), accounts.vault.toBuffer(), PublicKey.default.toBuffer()]),
+          recoveryConfig: null,
+          guardian: null,
+        })
+        .signers([allowOwner])
+        .rpc();
+
+    before(async () => {
+      await fund(provider, allowOwner.publicKey, 5);
+      accounts = await initializeVaultOnNewDomain(program, allowOwner, "allowlist");
+      await fund(provider, accounts.vaultAuthority, 1);
+
+      await program.methods
+        .updatePolicy({
+          ...NO_POLICY_CHANGES,
+          destinations: [allowed.publicKey],
+          enforceDestinationAllowlist: true,
+        })
+        .accounts({
+          authority: allowOwner.publicKey,
+          snsNameAccount: accounts.domain,
+          vault: accounts.vault,
+          policy: accounts.policy,
+          policyHistory: accounts.policyHistory,
+          systemProgram: SystemProgram.programId,
+          delegateSession: null,
+          ownerSet: null,
+        })
+        .signers([allowOwner])
+        .rpc();
+    });
+
+    it("should refuse a recipient outside the allowlist", async () => {
+      try {
+        await withdrawTo(notAllowed.publicKey);
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("DestinationNotAllowlisted");
+      }
+    });
+
+    it("should pay an allowlisted recipient", async () => {
+      await withdrawTo(allowed.publicKey);
+
+      const balance = await provider.connection.getBalance(allowed.publicKey);
+      expect(balance).to.equal(LAMPORTS_PER_SOL / 100);
+    });
+  });
+
+  describe("policy update timelock", () => {
+    const lockOwner = Keypair.generate();
+    const pda = (seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+    let accounts: Awaited<ReturnType<typeof initializeVaultOnNewDomain>>;
+    let pendingUpdatePda: PublicKey;
+
+    const updatePolicyAccounts = () => ({
+      authority: lockOwner.publicKey,
+      snsNameAccount: accounts.domain,
+      vault: accounts.vault,
+      policy: accounts.policy,
+      policyHistory: accounts.policyHistory,
+      systemProgram: SystemProgram.programId,
+      delegateSession: null,
+      ownerSet: null,
+    });
+
+    const queueUpdate = (minSplits: number) =>
+      program.methods
+        .queuePolicyUpdate({ ...NO_POLICY_CHANGES, minSplits })
+        .accounts({
+          authority: lockOwner.publicKey,
+          snsNameAccount: accounts.domain,
+          vault: accounts.vault,
+          policy: accounts.policy,
+          pendingUpdate: pendingUpdatePda,
+          systemProgram: SystemProgram.programId,
+          delegateSession: null,
+          ownerSet: null,
+        })
+        .signers([lockOwner])
+        .rpc();
+
+    const applyUpdate = () =>
+      program.methods
+        .applyPolicyUpdate()
+        .accounts({
+          cranker: provider.wallet.publicKey,
+          snsNameAccount: accounts.domain,
+          vault: accounts.vault,
+          policy: accounts.policy,
+          pendingUpdate: pendingUpdatePda,
+          payer: lockOwner.publicKey,
+          policyHistory: accounts.policyHistory,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    before(async () => {
+      await fund(provider, lockOwner.publicKey, 5);
+      accounts = await initializeVaultOnNewDomain(program, lockOwner, "policy-lock");
+      pendingUpdatePda = pda([Buffer.from("pending_policy"), accounts.domain.toBuffer()]);
+
+      await program.methods
+        .updatePolicy({ ...NO_POLICY_CHANGES, policyUpdateDelaySeconds: 2 })
+        .accounts(updatePolicyAccounts())
+        .signers([lockOwner])
+        .rpc();
+    });
+
+    it("should refuse an immediate update once timelocked", async () => {
+      try {
+        await program.methods
+          .updatePolicy({ ...NO_POLICY_CHANGES, minSplits: 3 })
+          .accounts(updatePolicyAccounts())
+          .signers([lockOwner])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("PolicyUpdateRequiresQueue");
+      }
+    });
+
+    it("should refuse to apply a queued update before its delay", async () => {
+      await queueUpdate(3);
+
+      try {
+        await applyUpdate();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("PolicyUpdateLocked");
+      }
+    });
+
+    it("should apply a queued update after its delay", async () => {
+      await new Promise((resolve) => setTimeout(resolve, 4000));
+      await applyUpdate();
+
+      const policy = await program.account.privacyPolicy.fetch(accounts.policy);
+      expect(policy.minSplits).to.equal(3);
+      expect(await provider.connection.getAccountInfo(pendingUpdatePda)).to.be.null;
+
+      // Attributed to the owner that queued it
+      const history = await program.account.policyHistory.fetch(accounts.policyHistory);
+      const latest = history.entries[history.totalEntries.toNumber() - 1];
+      expect(latest.actor.toString()).to.equal(lockOwner.publicKey.toString());
+      expect(latest.nonce.toNumber()).to.equal(policy.policyNonce.toNumber());
+    });
+
+    it("should let the owner cancel a queued update", async () => {
+      await queueUpdate(4);
+
+      await program.methods
+        .cancelPolicyUpdate()
+        .accounts({
+          owner: lockOwner.publicKey,
+          snsNameAccount: accounts.domain,
+          vault: accounts.vault,
+          pendingUpdate: pendingUpdatePda,
+          payer: lockOwner.publicKey,
+          ownerSet: null,
+        })
+        .signers([lockOwner])
+        .rpc();
+
+      expect(await provider.connection.getAccountInfo(pendingUpdatePda)).to.be.null;
+      const policy = await program.account.privacyPolicy.fetch(accounts.policy);
+      expect(policy.minSplits).to.equal(3);
+    });
+  });
+
+  describe("apply_policy_preset", () => {
+    const presetOwner = Keypair.generate();
+    let accounts: Awaited<ReturnType<typeof initializeVaultOnNewDomain>>;
+
+    const applyPreset = (preset: any) =>
+      program.methods
+        .applyPolicyPreset(preset)
+        .accounts({
+          authority: presetOwner.publicKey,
+          snsNameAccount: accounts.domain,
+          vault: accounts.vault,
+          policy: accounts.policy,
+          policyHistory: accounts.policyHistory,
+          systemProgram: SystemProgram.programId,
+          delegateSession: null,
+          ownerSet: null,
+        })
+        .signers([presetOwner])
+        .rpc();
+
+    before(async () => {
+      await fund(provider, presetOwner.publicKey, 5);
+      accounts = await initializeVaultOnNewDomain(program, presetOwner, "preset");
+    });
+
+    it("should apply a named preset", async () => {
+      await applyPreset({ high: {} });
+
+      const policy = await program.account.privacyPolicy.fetch(accounts.policy);
+      expect(policy.preset).to.deep.equal({ high: {} });
+      expect(policy.minSplits).to.equal(3);
+      expect(policy.maxSplits).to.equal(6);
+      expect(policy.minDelaySeconds).to.equal(7200);
+      expect(policy.maxDelaySeconds).to.equal(28800);
+      expect(policy.destinations.map((key) => key.toString())).to.deep.equal([presetOwner.publicKey.toString()]);
+
+      const history = await program.account.policyHistory.fetch(accounts.policyHistory);
+      expect(history.totalEntries.toNumber()).to.equal(1);
+    });
+
+    it("should refuse the custom preset", async () => {
+      try {
+        await applyPreset({ custom: {} });
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("InvalidPolicyPreset");
+      }
+    });
+  });
+
+  describe("events", () => {
+    const eventOwner = Keypair.generate();
+    const pda = (seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+    const parser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+    let accounts: Awaited<ReturnType<typeof initializeVaultOnNewDomain>>;
+
+    /** Events logged by a confirmed transaction */
+    const eventsOf = async (signature: string) => {
+      await provider.connection.confirmTransaction(signature, "confirmed");
+      const tx = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      return [...parser.parseLogs(tx!.meta!.logMessages!)];
+    };
+    const named = (events: anchor.Event[], name: string) =>
+      events.find((event) => event.name.toLowerCase() === name.toLowerCase());
+
+    before(async () => {
+      await fund(provider, eventOwner.publicKey, 5);
+      accounts = await initializeVaultOnNewDomain(program, eventOwner, "events");
+    });
+
+    it("should emit a versioned event for a policy update", async () => {
+      const signature = await program.methods
+        .updatePolicy({ ...NO_POLICY_CHANGES, minSplits: 2 })
+        .accounts({
+          authority: eventOwner.publicKey,
+          snsNameAccount: accounts.domain,
+          vault: accounts.vault,
+          policy: accounts.policy,
+          policyHistory: accounts.policyHistory,
+          systemProgram: SystemProgram.programId,
+          delegateSession: null,
+          ownerSet: null,
+        })
+        .signers([eventOwner])
+        .rpc();
+
+      const event = named(await eventsOf(signature), "PolicyUpdated");
+      expect(event).to.exist;
+      expect(event!.data.version).to.equal(1);
+      expect(event!.data.vault.toString()).to.equal(accounts.vault.toString());
+      expect(event!.data.actorType).to.deep.equal({ owner: {} });
+      expect(event!.data.policyNonce.toNumber()).to.equal(1);
+    });
+
+    it("should emit a versioned event for an outflow limit change", async () => {
+      const signature = await program.methods
+        .setOutflowLimit(PublicKey.default, new anchor.BN(LAMPORTS_PER_SOL))
+        .accounts({
+          owner: eventOwner.publicKey,
+          snsNameAccount: accounts.domain,
+          vault: accounts.vault,
+          outflowLimit: pda([Buffer.from("outflow_limit"), accounts.vault.toBuffer(), PublicKey.default.toBuffer()]),
+          systemProgram: SystemProgram.programId,
+          ownerSet: null,
+        })
+        .signers([eventOwner])
+        .rpc();
+
+      const event = named(await eventsOf(signature), "OutflowLimitUpdated");
+      expect(event).to.exist;
+      expect(event!.data.version).to.equal(1);
+      expect(event!.data.dailyLimit.toNumber()).to.equal(LAMPORTS_PER_SOL);
+    });
+
+    it("should not let another key trigger owner events", async () => {
+      const stranger = Keypair.generate();
+      await fund(provider, stranger.publicKey, 1);
+
+      try {
+        await program.methods
+          .setOutflowLimit(PublicKey.default, new anchor.BN(1))
+          .accounts({
+            owner: stranger.publicKey,
+            snsNameAccount: accounts.domain,
+            vault: accounts.vault,
+            outflowLimit: pda([Buffer.from("outflow_limit"), accounts.vault.toBuffer(), PublicKey.default.toBuffer()]),
+            systemProgram: SystemProgram.programId,
+            ownerSet: null,
+          })
+          .signers([stranger])
+          .rpc();
+
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("Unauthorized");
+      }
+    });
+  });
+
+  describe("transfer_domain_to_vault", () => {
+    const custodyOwner = Keypair.generate();
+    const stranger = Keypair.generate();
+    let accounts: Awaited<ReturnType<typeof initializeVaultOnNewDomain>>;
+
+    const transferToVault = (signer: Keypair) =>
+      program.methods
+        .transferDomainToVault()
+        .accounts({
+          owner: signer.publicKey,
+          snsNameAccount: accounts.domain,
+          vault: accounts.vault,
+          vaultAuthority: accounts.vaultAuthority,
+          snsNameProgram: SNS_NAME_PROGRAM_ID,
+          ownerSet: null,
+        })
+        .signers([signer])
+        .rpc();
+
+    before(async () => {
+      await fund(provider, custodyOwner.publicKey, 5);
+      await fund(provider, stranger.publicKey, 1);
+      accounts = await initializeVaultOnNewDomain(program, custodyOwner, "custody");
+    });
+
+    it("should refuse a signer other than the vault owner", async () => {
+      try {
+        await transferToVault(stranger);
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("Unauthorized");
+      }
+    });
+
+    it("should move the domain into custody and set the flag", async () => {
+      await transferToVault(custodyOwner);
+
+      const snsOwner = await readSnsOwner(provider, accounts.domain);
+      expect(snsOwner.toString()).to.equal(accounts.vaultAuthority.toString());
+      const vault = await program.account.nameVault.fetch(accounts.vault);
+      expect(vault.domainTransferred).to.be.true;
+    });
+
+    it("should refuse a domain already in custody", async () => {
+      try {
+        await transferToVault(custodyOwner);
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("DomainAlreadyTransferred");
+      }
+    });
+  });
+
+  describe("sync_domain_state", () => {
+    const syncOwner = Keypair.generate();
+    const buyer = Keypair.generate();
+    let accounts: Awaited<ReturnType<typeof initializeVaultOnNewDomain>>;
+
+    const syncAccounts = (snsNameAccount: PublicKey) => ({
+      snsNameAccount,
+      vault: accounts.vault,
+      vaultAuthority: accounts.vaultAuthority,
+      nftTokenAccount: null,
+    });
+
+    before(async () => {
+      await fund(provider, syncOwner.publicKey, 5);
+      accounts = await initializeVaultOnNewDomain(program, syncOwner, "sync");
+    });
+
+    it("should leave a vault in sync unchanged", async () => {
+      await program.methods.syncDomainState().accounts(syncAccounts(accounts.domain)).rpc();
+
+      const vault = await program.account.nameVault.fetch(accounts.vault);
+      expect(vault.domainTransferred).to.be.false;
+      expect(vault.ownerLostDomain).to.be.false;
+    });
+
+    it("should record a sale made outside the program", async () => {
+      await transferSnsName(provider, accounts.domain, syncOwner, buyer.publicKey);
+
+      // Permissionless: the provider wallet cranks it
+      await program.methods.syncDomainState().accounts(syncAccounts(accounts.domain)).rpc();
+
+      const vault = await program.account.nameVault.fetch(accounts.vault);
+      expect(vault.domainTransferred).to.be.false;
+      expect(vault.ownerLostDomain).to.be.true;
+    });
+
+    it("should refuse a name account not owned by the SNS program", async () => {
+      try {
+        await program.methods.syncDomainState().accounts(syncAccounts(syncOwner.publicKey)).rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("InvalidSnsName");
+      }
+    });
+  });
+
+  describe("subdomain vaults", () => {
+    const parentOwner = Keypair.generate();
+    const subOwner = Keypair.generate();
+    const pda = (seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+    let parent: Awaited<ReturnType<typeof initializeVaultOnNewDomain>>;
+
+    const subdomainAccounts = (subdomain: PublicKey, signer: PublicKey, parentVault: PublicKey | null) => ({
+      owner: signer,
+      snsNameAccount: subdomain,
+      parentNameAccount: parent.domain,
+      parentVault,
+      nftTokenAccount: null,
+      vault: pda([Buffer.from("vault"), subdomain.toBuffer()]),
+      vaultAuthority: pda([Buffer.from("vault_auth"), subdomain.toBuffer()]),
+      policy: pda([Buffer.from("policy"), subdomain.toBuffer()]),
+      subdomainLink: pda([Buffer.from("subdomain"), parent.domain.toBuffer(), subdomain.toBuffer()]),
+      ownerIndex: pda([Buffer.from("owner_index"), signer.toBuffer()]),
+      systemProgram: SystemProgram.programId,
+    });
+
+    before(async () => {
+      await fund(provider, parentOwner.publicKey, 10);
+      await fund(provider, subOwner.publicKey, 5);
+      parent = await initializeVaultOnNewDomain(program, parentOwner, "parent");
+    });
+
+    it("should refuse a subdomain through initialize_vault", async () => {
+      const subdomain = await createSnsName(provider, subOwner, "direct", { key: parent.domain, owner: parentOwner });
+      const accounts = subdomainAccounts(subdomain, subOwner.publicKey, null);
+      try {
+        await program.methods
+          .initializeVault()
+          .accounts({
+            owner: subOwner.publicKey,
+            snsNameAccount: subdomain,
+            vault: accounts.vault,
+            vaultAuthority: accounts.vaultAuthority,
+            policy: accounts.policy,
+            ownerIndex: accounts.ownerIndex,
+            systemProgram: SystemProgram.programId,
+            nftTokenAccount: null,
+          })
+          .signers([subOwner])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("SubdomainRequiresParent");
+      }
+    });
+
+    it("should let the subdomain holder open its vault", async () => {
+      const subdomain = await createSnsName(provider, subOwner, "holder", { key: parent.domain, owner: parentOwner });
+      const accounts = subdomainAccounts(subdomain, subOwner.publicKey, null);
+
+      await program.methods.initializeSubdomainVault().accounts(accounts).signers([subOwner]).rpc();
+
+      const link = await program.account.subdomainLink.fetch(accounts.subdomainLink);
+      expect(link.parentName.toString()).to.equal(parent.domain.toString());
+      expect(link.subdomainName.toString()).to.equal(subdomain.toString());
+      expect(link.vault.toString()).to.equal(accounts.vault.toString());
+
+      const vault = await program.account.nameVault.fetch(accounts.vault);
+      expect(vault.owner.toString()).to.equal(subOwner.publicKey.toString());
+      expect(vault.managedByParent).to.be.false;
+    });
+
+    it("should let the parent owner open subdomain vaults only once opted in", async () => {
+      const subdomain = await createSnsName(provider, subOwner, "managed", { key: parent.domain, owner: parentOwner });
+      const accounts = subdomainAccounts(subdomain, parentOwner.publicKey, parent.vault);
+
+      try {
+        await program.methods.initializeSubdomainVault().accounts(accounts).signers([parentOwner]).rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("ParentControlDisabled");
+      }
+
+      await program.methods
+        .setSubdomainPolicy(true)
+        .accounts({
+          owner: parentOwner.publicKey,
+          snsNameAccount: parent.domain,
+          vault: parent.vault,
+          ownerSet: null,
+        })
+        .signers([parentOwner])
+        .rpc();
+
+      await program.methods.initializeSubdomainVault().accounts(accounts).signers([parentOwner]).rpc();
+
+      const vault = await program.account.nameVault.fetch(accounts.vault);
+      expect(vault.owner.toString()).to.equal(parentOwner.publicKey.toString());
+      expect(vault.managedByParent).to.be.true;
+    });
+
+    it("should refuse a stranger claiming a subdomain", async () => {
+      const stranger = Keypair.generate();
+      await fund(provider, stranger.publicKey, 2);
+      const subdomain = await createSnsName(provider, subOwner, "stranger", { key: parent.domain, owner: parentOwner });
+
+      try {
+        await program.methods
+          .initializeSubdomainVault()
+          .accounts(subdomainAccounts(subdomain, stranger.publicKey, parent.vault))
+          .signers([stranger])
+          .rpc();
+        expect.fail("Should have thrown an error");
+      } catch (err: any) {
+        expect(err.error?.errorCode?.code).to.equal("SnsOwnershipVerificationFailed");
+      }
+    });
+  });
+
+  describe("reclaim destinations", () => {
+    const reclaimOwner = Keypair.generate();
+    let accounts: Awaited<ReturnType<typeof initializeVaultOnNewDomain>>;
+
+    const reclaimTo = (destination: PublicKey, allowProgramOwned = false) =>
+      program.methods
+        .reclaimDomain(allowProgramOwned)
+        .accounts({
+          owner: reclaimOwner.publicKey,
+          snsNameAccount: accounts.domain,
+          vault: accounts.vault,
+          vaultAuthority: accounts.vaultAuthority,
+          destination,
+          snsNameProgram: SNS_NAME_PROGRAM_ID,
+          ownerSet: null,
+          reclaimConfig: null,
+        })
+        .signers([reclaimOwner])
+        .rpc();
+
+    before(async () => {
+      await fund(provider, reclaimOwner.publicKey, 5);
+      accounts = await initializeVaultOnNewDomain(program, reclaimOwner, "reclaim");
+      await program.methods
+        .transferDomainToVault()
+        .accounts({
+          owner: reclaimOwner.publicKey,
+          snsNameAccount: accounts.domain,
+          vault: accounts.vault,
+          vaultAuthority: accounts.vaultAuthority,
+          snsNameProgram: SNS_NAME_PROGRAM_ID,
+          ownerSet: null,
+        })
+        .signers([reclaimOwner])
+        .rpc();
+    });
+
+    for (const [label, destination] of [
+      ["the zero key", () => PublicKey.default],
+      ["the vault authority", () => accounts.vaultAuthority],
+      ["a Hydentity account", () => accounts.policy],
+      ["an executable", () => SNS_NAME_PROGRAM_ID],
+    ] as [string, () => PublicKey][]) {
+      it(`should refuse reclaiming to ${label}`, async () => {
+        try {
+          await reclaimTo(destination(), true);
+          expect.fail("Should have thrown an error");
+        } catch (err: any) {
+          expect(err.error?.errorCode?.code).to.equal("InvalidReclaimDestination");
+        }
+      });
+    }
+
+    it("should return the domain to a wallet", async () => {
+      await reclaimTo(reclaimOwner.publicKey);
+
+      const snsOwner = await readSnsOwner(provider, accounts.domain);
+      expect(snsOwner.toString()).to.equal(reclaimOwner.publicKey.toString());
+      const vault = await program.account.nameVault.fetch(accounts.vault);
+      expect(vault.domainTransferred).to.be.false;
+    });
+  });
 });
 